@@ -0,0 +1,387 @@
+use core::{
+    export::Export,
+    livesplit::LiveSplit,
+    log_parser,
+    run::{
+        timed_run::RunEnum,
+        traits::Run,
+    },
+    save_manager::{SaveManager, SaveType},
+    split_stats::ObjectiveStats,
+};
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::{self, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use glr_core::split::Split;
+
+#[derive(Parser)]
+#[command(name = "gtfo_logger_cli", version, about = "Parse, list and export GTFO Logger runs")]
+struct Cli {
+    /// where the runs are loaded from and saved to.
+    #[arg(long, value_enum, default_value_t = StorageArg::Binary, global = true)]
+    storage: StorageArg,
+
+    /// folder of the runs, trash and merges, the one of the app when missing.
+    #[arg(long, global = true)]
+    data_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StorageArg {
+    Binary,
+    Json,
+    Sqlite,
+}
+
+impl From<StorageArg> for SaveType {
+    fn from(value: StorageArg) -> Self {
+        match value {
+            StorageArg::Binary => SaveType::Binary,
+            StorageArg::Json => SaveType::Json,
+            StorageArg::Sqlite => SaveType::Sqlite,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Csv,
+    Json,
+    Lss,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// parse log files or folders of logs and save the runs found.
+    Import {
+        paths: Vec<PathBuf>,
+
+        /// number of files parsed at the same time.
+        #[arg(short, long, default_value_t = 8)]
+        jobs: usize,
+
+        /// only print what was found, do not save anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// list every saved objective with its number of runs.
+    Objectives,
+    /// list the saved runs of an objective.
+    Runs {
+        objective: String,
+
+        #[arg(long)]
+        wins_only: bool,
+    },
+    /// print the best splits, the best run and the theoretical best.
+    Best { objective: String },
+    /// remove every run that holds neither the best time nor a best split.
+    Optimize {
+        /// objectives to optimize, all of them when empty.
+        objectives: Vec<String>,
+    },
+    /// print or export the statistics of every split of an objective.
+    Stats {
+        objective: String,
+
+        /// only csv and json are supported.
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+
+        /// file to write to, stdout when missing.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// export the runs of an objective.
+    Export {
+        objective: String,
+
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+
+        /// file to write to, stdout when missing.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    let mut save_manager = match cli.data_dir {
+        Some(directory) => SaveManager::with_directory(directory),
+        None => SaveManager::default(),
+    };
+
+    save_manager
+        .set_save_type(cli.storage.into())
+        .map_err(|e| format!("Failed to open run storage: {e}"))?;
+
+    match cli.command {
+        Command::Import { paths, jobs, dry_run } => import(&mut save_manager, paths, jobs, dry_run),
+        Command::Objectives => objectives(&mut save_manager),
+        Command::Runs { objective, wins_only } => runs(&mut save_manager, &objective, wins_only),
+        Command::Best { objective } => best(&mut save_manager, &objective),
+        Command::Optimize { objectives } => optimize(&mut save_manager, objectives),
+        Command::Stats { objective, format, output } => {
+            stats(&mut save_manager, &objective, format, output)
+        }
+        Command::Export { objective, format, output } => {
+            export(&mut save_manager, &objective, format, output)
+        }
+    }
+}
+
+fn load_objective(save_manager: &mut SaveManager, objective: &String) -> Result<Vec<RunEnum>, String> {
+    save_manager.load_advanced(objective);
+
+    match save_manager.get_runs(objective) {
+        Some(runs) => Ok(runs.clone()),
+        None => Err(format!("No runs saved for {objective}")),
+    }
+}
+
+fn import(
+    save_manager: &mut SaveManager,
+    paths: Vec<PathBuf>,
+    jobs: usize,
+    dry_run: bool,
+) -> Result<(), String> {
+    let files = log_parser::collect_log_files(&paths);
+    if files.is_empty() {
+        return Err("No log files found".to_owned());
+    }
+
+    let file_count = files.len();
+    let runs = log_parser::parse_files(files, jobs);
+    println!("Parsed {file_count} files, found {} runs", runs.len());
+
+    let objectives: BTreeSet<String> = runs
+        .iter()
+        .map(|v| v.get_objective().to_string())
+        .collect();
+
+    if dry_run {
+        for run in &runs {
+            println!("{} {} {}", run.get_objective(), run.get_time(), run.is_win());
+        }
+
+        return Ok(());
+    }
+
+    for objective in &objectives {
+        save_manager.load_advanced(objective);
+    }
+    save_manager.save_multiple(runs.into_iter().map(RunEnum::Level).collect());
+
+    for objective in &objectives {
+        save_manager.save_to_file(objective)?;
+        println!(
+            "{objective}: {} runs",
+            save_manager.get_runs(objective).map(|v| v.len()).unwrap_or_default()
+        );
+    }
+
+    Ok(())
+}
+
+fn objectives(save_manager: &mut SaveManager) -> Result<(), String> {
+    save_manager.load_all_runs();
+
+    for objective in save_manager.get_all_objectives() {
+        let count = save_manager.get_runs(&objective).map(|v| v.len()).unwrap_or_default();
+        println!("{objective}\t{count}");
+    }
+
+    Ok(())
+}
+
+fn runs(save_manager: &mut SaveManager, objective: &String, wins_only: bool) -> Result<(), String> {
+    let runs = load_objective(save_manager, objective)?;
+
+    for (id, run) in runs.iter().enumerate() {
+        if wins_only && !run.is_win() {
+            continue;
+        }
+
+        let metadata = run.get_metadata().map(|v| v.to_string()).unwrap_or_default();
+        let result = match run.is_win() {
+            true => "WIN",
+            false => "LOSS",
+        };
+        println!("{id:>5} {} {result:<4} {:>3} splits {metadata}", run.get_time(), run.len());
+    }
+
+    Ok(())
+}
+
+fn best(save_manager: &mut SaveManager, objective: &String) -> Result<(), String> {
+    let runs = load_objective(save_manager, objective)?;
+    let sum_of_best = save_manager.get_sum_of_best(objective).cloned().unwrap_or_default();
+
+    for gold in sum_of_best.get_golds() {
+        let name = gold.segment.to_string();
+        match (gold.time, gold.run) {
+            (Some(time), Some(id)) => {
+                let run_time = runs.get(id).map(|v| v.get_time().to_string()).unwrap_or_default();
+                println!("{name:<24} {time} run {id} ({run_time})");
+            }
+            _ => println!("{name:<24} not finished by any run"),
+        }
+    }
+
+    println!();
+    match save_manager.get_best_run(objective) {
+        Some(run) => println!("Best run:    {}", run.get_time()),
+        None => println!("Best run:    none"),
+    }
+    println!("Theoretical: {}", sum_of_best.get_total());
+
+    Ok(())
+}
+
+fn optimize(save_manager: &mut SaveManager, objectives: Vec<String>) -> Result<(), String> {
+    let objectives = match objectives.is_empty() {
+        true => {
+            save_manager.load_all_runs();
+            save_manager.get_all_objectives()
+        }
+        false => objectives,
+    };
+
+    for objective in objectives {
+        let before = load_objective(save_manager, &objective)?.len();
+        save_manager.optimize_obj(&objective);
+        save_manager.save_to_file(&objective)?;
+
+        let after = save_manager.get_runs(&objective).map(|v| v.len()).unwrap_or_default();
+        println!("{objective}: {before} -> {after} runs");
+    }
+
+    Ok(())
+}
+
+fn stats(
+    save_manager: &mut SaveManager,
+    objective: &String,
+    format: ExportFormat,
+    output: Option<PathBuf>,
+) -> Result<(), String> {
+    load_objective(save_manager, objective)?;
+    let stats = ObjectiveStats::from_save_manager(save_manager, objective)
+        .ok_or(format!("No splits saved for {objective}"))?;
+    let writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(File::create(path).map_err(|e| e.to_string())?),
+        None => Box::new(io::stdout()),
+    };
+
+    match format {
+        ExportFormat::Csv => stats.export_csv(writer),
+        ExportFormat::Json => stats.export_json(writer),
+        ExportFormat::Lss => Err("Stats can not be written as a LiveSplit file".to_owned()),
+    }
+}
+
+fn export(
+    save_manager: &mut SaveManager,
+    objective: &String,
+    format: ExportFormat,
+    output: Option<PathBuf>,
+) -> Result<(), String> {
+    let runs = load_objective(save_manager, objective)?;
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(File::create(path).map_err(|e| e.to_string())?),
+        None => Box::new(io::stdout()),
+    };
+
+    match format {
+        ExportFormat::Csv => Export::export_times(runs.iter(), writer),
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(writer, &runs).map_err(|e| e.to_string())?
+        }
+        ExportFormat::Lss => {
+            let data = LiveSplit::export_lss(save_manager, objective)?;
+            writer.write_all(data.as_bytes()).map_err(|e| e.to_string())?
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        run::{
+            objectives::{objective_enum::ObjectiveEnum, run_objective::RunObjective},
+            timed_run::{LevelRun, RunEnum},
+            traits::Run,
+        },
+        save_manager::SaveManager,
+    };
+    use std::path::Path;
+
+    use clap::Parser;
+    use glr_core::{split::NamedSplit, time::Time};
+
+    use super::{Cli, run};
+
+    fn level_run(door: &str, win: &str) -> RunEnum {
+        let objective = RunObjective::from_name("R1A1".to_owned()).with_player_count(1);
+        let mut run = LevelRun::new(ObjectiveEnum::Run(objective));
+        run.add_split(NamedSplit::new(Time::from(door).unwrap(), "door".to_owned()));
+        run.add_split(NamedSplit::new(Time::from(win).unwrap(), "WIN".to_owned()));
+        run.set_win(true);
+
+        RunEnum::Level(run)
+    }
+
+    fn cli(data_dir: &Path, args: &[&str]) -> Result<(), String> {
+        let data_dir = data_dir.to_str().unwrap();
+        let args = [&["gtfo_logger_cli", "--data-dir", data_dir], args].concat();
+
+        run(Cli::try_parse_from(args).map_err(|e| e.to_string())?)
+    }
+
+    #[test]
+    pub fn test_optimize_and_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut save_manager = SaveManager::with_directory(dir.path().to_path_buf());
+        save_manager.save_multiple(vec![
+            level_run("00:01:00.000", "00:02:00.000"),
+            level_run("00:00:50.000", "00:02:10.000"),
+            level_run("00:01:10.000", "00:02:20.000"),
+        ]);
+        save_manager.save_to_files().unwrap();
+        drop(save_manager);
+
+        cli(dir.path(), &["optimize"]).unwrap();
+        // the removed run goes to the trash of the folder given
+        assert!(dir.path().join("trash.bin").exists());
+
+        let output = dir.path().join("runs.json");
+        let output_str = output.to_str().unwrap();
+        cli(dir.path(), &["export", "R1A1_1.save", "--format", "json", "-o", output_str]).unwrap();
+        let exported: Vec<serde_json::Value> =
+            serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(exported.len(), 2);
+
+        assert!(cli(dir.path(), &["runs", "R2B1_1.save"]).is_err());
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt::Display;
+
+use glr_core::{split::Split, time::Time};
+
+use crate::{
+    run::timed_run::RunEnum,
+    save_manager::SaveManager,
+};
+
+/// what the compared runs are measured against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Baseline {
+    /// the fastest finished run of the objective.
+    #[default]
+    PersonalBest,
+    /// the best time of every split.
+    Gold,
+    /// one of the compared runs, by index.
+    Run(usize),
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Baseline::PersonalBest => write!(f, "Personal best"),
+            Baseline::Gold => write!(f, "Gold splits"),
+            Baseline::Run(id) => write!(f, "Run {}", id + 1),
+        }
+    }
+}
+
+/// running total of the splits.
+///
+/// a missing split has no total but the ones after it still count the
+/// splits that are there, like the saved runs table does.
+pub fn cumulative(splits: &[Option<Time>]) -> Vec<Option<Time>> {
+    let mut total = Time::new();
+
+    splits
+        .iter()
+        .map(|split| {
+            split.map(|time| {
+                total += time;
+                total
+            })
+        })
+        .collect()
+}
+
+/// `time - other` in milliseconds.
+pub fn difference(time: Time, other: Time) -> i64 {
+    time.get_stamp() as i64 - other.get_stamp() as i64
+}
+
+/// writes milliseconds like `01:02.003`, hours are only written when there are some.
+pub fn format_millis(millis: u64) -> String {
+    let (hours, minutes, seconds, millis) = (
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000,
+    );
+
+    match hours {
+        0 => format!("{minutes:02}:{seconds:02}.{millis:03}"),
+        _ => format!("{hours}:{minutes:02}:{seconds:02}.{millis:03}"),
+    }
+}
+
+/// writes a difference in milliseconds as `+01:02.003` or `-01:02.003`.
+pub fn format_delta(delta: i64) -> String {
+    let sign = match delta {
+        ..0 => '-',
+        0 => ' ',
+        _ => '+',
+    };
+
+    format!("{sign}{}", format_millis(delta.unsigned_abs()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparedRun {
+    pub name: String,
+    pub splits: Vec<Option<Time>>,
+    pub cumulative: Vec<Option<Time>>,
+}
+
+impl ComparedRun {
+    pub fn new(name: String, splits: Vec<Option<Time>>) -> Self {
+        Self {
+            name,
+            cumulative: cumulative(&splits),
+            splits,
+        }
+    }
+}
+
+/// split by split comparison of any number of runs against a baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    split_names: Vec<String>,
+    runs: Vec<ComparedRun>,
+    baseline: ComparedRun,
+}
+
+impl Comparison {
+    pub fn new(split_names: Vec<String>, runs: Vec<ComparedRun>, baseline: ComparedRun) -> Self {
+        Self {
+            split_names,
+            runs,
+            baseline,
+        }
+    }
+
+    /// compares the runs of an objective using its merged split names.
+    ///
+    /// returns none if the baseline does not exist, like a personal best
+    /// for an objective with no finished run.
+    pub fn from_runs(
+        save_manager: &SaveManager,
+        objective: &String,
+        runs: &[(String, &RunEnum)],
+        baseline: Baseline,
+    ) -> Option<Self> {
+        let split_names = save_manager.get_split_names(objective)?.clone();
+        let times = |run: &RunEnum| -> Vec<Option<Time>> {
+            split_names
+                .iter()
+                .map(|name| save_manager.get_merged_split_time(objective, run, name))
+                .collect()
+        };
+
+        let baseline = match baseline {
+            Baseline::PersonalBest => {
+                let best = save_manager.get_best_run(objective)?;
+                ComparedRun::new(
+                    format!("{} {}", Baseline::PersonalBest, best.get_time()),
+                    times(best),
+                )
+            }
+            Baseline::Gold => ComparedRun::new(
+                Baseline::Gold.to_string(),
+                split_names
+                    .iter()
+                    .map(|name| save_manager.get_best_split(objective, name).cloned())
+                    .collect(),
+            ),
+            Baseline::Run(id) => {
+                let (name, run) = runs.get(id)?;
+                ComparedRun::new(name.clone(), times(run))
+            }
+        };
+
+        let runs = runs
+            .iter()
+            .map(|(name, run)| ComparedRun::new(name.clone(), times(run)))
+            .collect();
+
+        Some(Self::new(split_names, runs, baseline))
+    }
+
+    pub fn get_split_names(&self) -> &Vec<String> {
+        &self.split_names
+    }
+
+    pub fn get_runs(&self) -> &Vec<ComparedRun> {
+        &self.runs
+    }
+
+    pub fn get_baseline(&self) -> &ComparedRun {
+        &self.baseline
+    }
+
+    /// cumulative time of the run minus the baseline's after each split,
+    /// in milliseconds. negative means the run was ahead.
+    pub fn get_deltas(&self, run: usize) -> Vec<Option<i64>> {
+        let Some(run) = self.runs.get(run) else {
+            return Vec::new();
+        };
+
+        run.cumulative
+            .iter()
+            .zip(&self.baseline.cumulative)
+            .map(|(time, base)| Some(difference((*time)?, (*base)?)))
+            .collect()
+    }
+
+    /// split time of the run minus the baseline's, in milliseconds.
+    pub fn get_split_deltas(&self, run: usize) -> Vec<Option<i64>> {
+        let Some(run) = self.runs.get(run) else {
+            return Vec::new();
+        };
+
+        run.splits
+            .iter()
+            .zip(&self.baseline.splits)
+            .map(|(time, base)| Some(difference((*time)?, (*base)?)))
+            .collect()
+    }
+
+    /// smallest and largest cumulative delta of every run, used to scale graphs.
+    pub fn get_delta_range(&self) -> (i64, i64) {
+        (0..self.runs.len())
+            .flat_map(|id| self.get_deltas(id))
+            .flatten()
+            .fold((0, 0), |(min, max), v| (min.min(v), max.max(v)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glr_core::time::Time;
+
+    use crate::test_utils;
+
+    use super::{ComparedRun, Comparison, cumulative, format_delta};
+
+    fn time(text: &str) -> Option<Time> {
+        Some(test_utils::time(text))
+    }
+
+    #[test]
+    pub fn test_comparison() {
+        let splits = vec![time("00:01:00.000"), None, time("00:00:30.000")];
+        assert_eq!(
+            cumulative(&splits),
+            vec![time("00:01:00.000"), None, time("00:01:30.000")]
+        );
+
+        let comparison = Comparison::new(
+            vec!["door".to_owned(), "bulk".to_owned(), "WIN".to_owned()],
+            vec![
+                ComparedRun::new("slow".to_owned(), splits),
+                ComparedRun::new(
+                    "fast".to_owned(),
+                    vec![time("00:00:50.000"), time("00:00:20.000"), time("00:00:15.000")],
+                ),
+            ],
+            ComparedRun::new(
+                "gold".to_owned(),
+                vec![time("00:00:55.000"), time("00:00:20.000"), time("00:00:10.000")],
+            ),
+        );
+
+        assert_eq!(comparison.get_deltas(0), vec![Some(5000), None, Some(5000)]);
+        assert_eq!(comparison.get_deltas(1), vec![Some(-5000), Some(-5000), Some(0)]);
+        assert_eq!(comparison.get_split_deltas(0), vec![Some(5000), None, Some(20000)]);
+        assert_eq!(comparison.get_delta_range(), (-5000, 5000));
+        assert!(comparison.get_deltas(2).is_empty());
+
+        assert_eq!(format_delta(-5000), "-00:05.000");
+        assert_eq!(format_delta(3_723_004), "+1:02:03.004");
+        assert_eq!(format_delta(0), " 00:00.000");
+    }
+}
@@ -0,0 +1,335 @@
+use crate::{
+    run::{merge_splits::MergeSplits, timed_run::RunEnum},
+    save_manager::SaveManager,
+    trash::TrashReason,
+};
+
+/// how many edits are kept before the oldest one is forgotten.
+pub const DEFAULT_HISTORY_LEN: usize = 100;
+
+/// a change that can be undone and done again.
+pub trait Edit {
+    type Target;
+
+    fn apply(&self, target: &mut Self::Target);
+    fn revert(&self, target: &mut Self::Target);
+
+    /// folds the next edit into this one so typing counts as a single step.
+    ///
+    /// returns true if the edit was merged.
+    fn merge(&mut self, _next: &Self) -> bool {
+        false
+    }
+}
+
+/// undo and redo stacks of edits.
+#[derive(Debug)]
+pub struct EditHistory<E> {
+    undo: Vec<E>,
+    redo: Vec<E>,
+    limit: usize,
+}
+
+impl<E> Default for EditHistory<E>
+where
+    E: Edit,
+{
+    fn default() -> Self {
+        Self::with_limit(DEFAULT_HISTORY_LEN)
+    }
+}
+
+impl<E> EditHistory<E>
+where
+    E: Edit,
+{
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit: limit.max(1),
+        }
+    }
+
+    /// records an edit that was already done to the target.
+    pub fn push(&mut self, edit: E) {
+        self.redo.clear();
+
+        if let Some(last) = self.undo.last_mut()
+            && last.merge(&edit)
+        {
+            return;
+        }
+
+        self.undo.push(edit);
+        if self.undo.len() > self.limit {
+            self.undo.remove(0);
+        }
+    }
+
+    /// does the edit and records it.
+    pub fn apply(&mut self, edit: E, target: &mut E::Target) {
+        edit.apply(target);
+        self.push(edit);
+    }
+
+    /// returns true if an edit was undone.
+    pub fn undo(&mut self, target: &mut E::Target) -> bool {
+        let Some(edit) = self.undo.pop() else {
+            return false;
+        };
+
+        edit.revert(target);
+        self.redo.push(edit);
+
+        true
+    }
+
+    /// returns true if an edit was done again.
+    pub fn redo(&mut self, target: &mut E::Target) -> bool {
+        let Some(edit) = self.redo.pop() else {
+            return false;
+        };
+
+        edit.apply(target);
+        self.undo.push(edit);
+
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+/// an edit of a list of runs.
+///
+/// runs are found again by value, so sorting the list or saving other
+/// runs in between does not break the history. edits of runs that are
+/// no longer in the list are skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunEdit<T> {
+    /// the run was removed from this index.
+    Remove(usize, T),
+    Replace { before: T, after: T },
+    /// edits done together, like a set all toggle.
+    Batch(Vec<RunEdit<T>>),
+}
+
+impl<T> RunEdit<T>
+where
+    T: PartialEq,
+{
+    /// the edits of a batch that changed a run, `None` if none did.
+    pub fn batch(before: Vec<T>, after: &[T]) -> Option<Self>
+    where
+        T: Clone,
+    {
+        let edits: Vec<RunEdit<T>> = before
+            .into_iter()
+            .zip(after)
+            .filter(|(before, after)| before != *after)
+            .map(|(before, after)| RunEdit::Replace {
+                before,
+                after: after.clone(),
+            })
+            .collect();
+
+        match edits.is_empty() {
+            true => None,
+            false => Some(RunEdit::Batch(edits)),
+        }
+    }
+
+    fn replace(runs: &mut [T], from: &T, to: &T)
+    where
+        T: Clone,
+    {
+        if let Some(run) = runs.iter_mut().find(|v| *v == from) {
+            *run = to.clone();
+        }
+    }
+}
+
+impl<T> Edit for RunEdit<T>
+where
+    T: PartialEq + Clone,
+{
+    type Target = Vec<T>;
+
+    fn apply(&self, target: &mut Vec<T>) {
+        match self {
+            RunEdit::Remove(index, run) => {
+                let position = match target.get(*index) == Some(run) {
+                    true => Some(*index),
+                    false => target.iter().position(|v| v == run),
+                };
+                if let Some(position) = position {
+                    target.remove(position);
+                }
+            }
+            RunEdit::Replace { before, after } => Self::replace(target, before, after),
+            RunEdit::Batch(edits) => edits.iter().for_each(|v| v.apply(target)),
+        }
+    }
+
+    fn revert(&self, target: &mut Vec<T>) {
+        match self {
+            RunEdit::Remove(index, run) => target.insert((*index).min(target.len()), run.clone()),
+            RunEdit::Replace { before, after } => Self::replace(target, after, before),
+            RunEdit::Batch(edits) => edits.iter().rev().for_each(|v| v.revert(target)),
+        }
+    }
+}
+
+/// an edit of the runs or merge splits kept by the save manager.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveEdit {
+    /// runs moved to the trash, undoing it takes them out of the trash again.
+    Trash {
+        objective: String,
+        /// the runs with their index, in the order they were removed.
+        removed: Vec<(usize, RunEnum)>,
+        reason: TrashReason,
+    },
+    MergeSplits {
+        objective: String,
+        before: Box<MergeSplits>,
+        after: Box<MergeSplits>,
+    },
+}
+
+impl SaveEdit {
+    fn objective(&self) -> &String {
+        match self {
+            SaveEdit::Trash { objective, .. } | SaveEdit::MergeSplits { objective, .. } => objective,
+        }
+    }
+
+    fn change(&self, save_manager: &mut SaveManager, undo: bool) {
+        match self {
+            SaveEdit::Trash {
+                objective,
+                removed,
+                reason,
+            } => match undo {
+                // runs restored or purged from the trash since are left alone.
+                true => {
+                    for (id, run) in removed.iter().rev() {
+                        if save_manager.get_trash_mut().take_run(objective, run).is_none() {
+                            continue;
+                        }
+                        save_manager.edit_runs(objective, |runs| {
+                            RunEdit::Remove(*id, run.clone()).revert(runs)
+                        });
+                    }
+                }
+                false => {
+                    for (id, run) in removed {
+                        let removed = save_manager.edit_runs(objective, |runs| {
+                            let len = runs.len();
+                            RunEdit::Remove(*id, run.clone()).apply(runs);
+                            runs.len() < len
+                        });
+                        let Some(removed) = removed else {
+                            break;
+                        };
+                        if removed {
+                            save_manager
+                                .get_trash_mut()
+                                .add(objective.clone(), run.clone(), *reason);
+                        }
+                    }
+                }
+            },
+            SaveEdit::MergeSplits {
+                objective,
+                before,
+                after,
+            } => {
+                let data = match undo {
+                    true => before,
+                    false => after,
+                };
+                if let Err(e) = save_manager.set_merge_splits(objective, data.as_ref().clone()) {
+                    eprintln!("Failed to save merge splits: {e}");
+                }
+            }
+        }
+
+        save_manager.calculate_best_splits(self.objective());
+    }
+}
+
+impl Edit for SaveEdit {
+    type Target = SaveManager;
+
+    fn apply(&self, target: &mut SaveManager) {
+        self.change(target, false);
+    }
+
+    fn revert(&self, target: &mut SaveManager) {
+        self.change(target, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EditHistory, RunEdit};
+
+    #[test]
+    pub fn test_edit_history() {
+        let mut runs = vec![1, 2, 3, 4, 5];
+        let mut history = EditHistory::with_limit(3);
+
+        // removed from the back first, like the windows do.
+        history.apply(RunEdit::Batch(vec![RunEdit::Remove(3, 4), RunEdit::Remove(1, 2)]), &mut runs);
+        history.apply(RunEdit::Replace { before: 5, after: 6 }, &mut runs);
+        assert_eq!(runs, vec![1, 3, 6]);
+
+        // sorting in between does not matter.
+        runs.reverse();
+        assert!(history.undo(&mut runs));
+        assert_eq!(runs, vec![5, 3, 1]);
+        runs.reverse();
+        assert!(history.undo(&mut runs));
+        assert_eq!(runs, vec![1, 2, 3, 4, 5]);
+        assert!(!history.undo(&mut runs));
+
+        assert!(history.redo(&mut runs));
+        assert_eq!(runs, vec![1, 3, 5]);
+        history.apply(RunEdit::Remove(0, 1), &mut runs);
+        assert!(!history.can_redo());
+
+        for run in [3, 5] {
+            history.apply(RunEdit::Remove(0, run), &mut runs);
+        }
+        assert!(runs.is_empty());
+        while history.undo(&mut runs) {}
+        assert_eq!(runs, vec![1, 3, 5]);
+    }
+
+    #[test]
+    pub fn test_run_edit_batch() {
+        let before = vec![1, 2, 3];
+        let after = [1, 4, 5];
+
+        assert_eq!(
+            RunEdit::batch(before, &after),
+            Some(RunEdit::Batch(vec![
+                RunEdit::Replace { before: 2, after: 4 },
+                RunEdit::Replace { before: 3, after: 5 },
+            ]))
+        );
+        assert_eq!(RunEdit::batch(vec![1], &after[0..1]), None);
+    }
+}
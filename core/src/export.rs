@@ -31,4 +31,12 @@ impl Export {
             let _ = buffered.write(b"\n");
         }
     }
+
+    /// quotes a csv field if it has a comma, a quote or a new line.
+    pub fn escape_csv(text: &str) -> String {
+        match text.contains([',', '"', '\n']) {
+            true => format!("\"{}\"", text.replace('"', "\"\"")),
+            false => text.to_owned(),
+        }
+    }
 }
@@ -0,0 +1,155 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use tempfile::NamedTempFile;
+
+pub const DEFAULT_BACKUP_COUNT: usize = 3;
+
+/// writes the data into a temp file in the same folder and then
+/// renames it over the target.
+///
+/// this way a crash mid write never leaves a truncated file behind.
+pub fn write_atomic(path: &Path, data: &[u8]) -> Result<(), String> {
+    let parent = match path.parent() {
+        Some(parent) => parent,
+        None => return Err(format!("No parent folder for {:?}", path)),
+    };
+
+    if !parent.exists() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let mut temp = NamedTempFile::new_in(parent).map_err(|e| e.to_string())?;
+    temp.write_all(data).map_err(|e| e.to_string())?;
+    temp.as_file().sync_all().map_err(|e| e.to_string())?;
+    temp.persist(path).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// path of a backup generation, 1 being the newest.
+pub fn backup_path(backup_dir: &Path, file_name: &str, generation: usize) -> PathBuf {
+    backup_dir.join(format!("{file_name}.{generation}"))
+}
+
+/// shifts all backups of the file by one generation and copies
+/// the current file in as generation 1.
+///
+/// the oldest generation over `count` is removed.
+pub fn rotate_backups(path: &Path, backup_dir: &Path, count: usize) -> Result<(), String> {
+    if count == 0 || !path.exists() {
+        return Ok(());
+    }
+
+    let file_name = match path.file_name().and_then(|v| v.to_str()) {
+        Some(name) => name,
+        None => return Err(format!("Invalid file name {:?}", path)),
+    };
+
+    if !backup_dir.exists() {
+        fs::create_dir_all(backup_dir).map_err(|e| e.to_string())?;
+    }
+
+    let oldest = backup_path(backup_dir, file_name, count);
+    if oldest.exists() {
+        fs::remove_file(&oldest).map_err(|e| e.to_string())?;
+    }
+
+    for generation in (1..count).rev() {
+        let from = backup_path(backup_dir, file_name, generation);
+        if from.exists() {
+            fs::rename(&from, backup_path(backup_dir, file_name, generation + 1))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    write_atomic(&backup_path(backup_dir, file_name, 1), &data)
+}
+
+/// returns all generations that exist for the file, newest first.
+pub fn list_backups(backup_dir: &Path, file_name: &str) -> Vec<(usize, PathBuf)> {
+    let mut result = Vec::new();
+    let mut generation = 1;
+
+    loop {
+        let path = backup_path(backup_dir, file_name, generation);
+        if !path.exists() {
+            break;
+        }
+
+        result.push((generation, path));
+        generation += 1;
+    }
+
+    result
+}
+
+/// copies the backup generation back over the file.
+pub fn restore_backup(
+    path: &Path,
+    backup_dir: &Path,
+    generation: usize,
+) -> Result<(), String> {
+    let file_name = match path.file_name().and_then(|v| v.to_str()) {
+        Some(name) => name,
+        None => return Err(format!("Invalid file name {:?}", path)),
+    };
+
+    let data = fs::read(backup_path(backup_dir, file_name, generation))
+        .map_err(|e| e.to_string())?;
+
+    write_atomic(path, &data)
+}
+
+/// moves a file that could not be read next to itself, so saving over
+/// it does not lose what it held.
+///
+/// returns where the file was moved to.
+pub fn keep_unreadable(path: &Path) -> Result<PathBuf, String> {
+    let file_name = match path.file_name().and_then(|v| v.to_str()) {
+        Some(name) => name,
+        None => return Err(format!("Invalid file name {:?}", path)),
+    };
+
+    let stamp = chrono::Local::now().format("%Y%m%d%H%M%S");
+    let mut target = path.with_file_name(format!("{file_name}.{stamp}.unreadable"));
+    let mut count = 1;
+    while target.exists() {
+        target = path.with_file_name(format!("{file_name}.{stamp}-{count}.unreadable"));
+        count += 1;
+    }
+    fs::rename(path, &target).map_err(|e| e.to_string())?;
+
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{list_backups, restore_backup, rotate_backups, write_atomic};
+
+    #[test]
+    pub fn test_rotate_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("R1A1_1.save");
+        let backup_dir = dir.path().join("backups");
+
+        for it in 0..5 {
+            rotate_backups(&path, &backup_dir, 3).unwrap();
+            write_atomic(&path, format!("gen{it}").as_bytes()).unwrap();
+        }
+
+        let backups = list_backups(&backup_dir, "R1A1_1.save");
+        assert_eq!(backups.len(), 3);
+        assert_eq!(fs::read_to_string(&backups[0].1).unwrap(), "gen3");
+        assert_eq!(fs::read_to_string(&backups[2].1).unwrap(), "gen1");
+
+        restore_backup(&path, &backup_dir, 2).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "gen2");
+    }
+}
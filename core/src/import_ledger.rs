@@ -0,0 +1,283 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::Read,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{file_backup, run::default_dirs::get_directory};
+
+const LEDGER_FILE_NAME: &str = "import_ledger.json";
+
+/// what is known about a log file without parsing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileFingerprint {
+    pub size: u64,
+    /// seconds since the unix epoch.
+    pub modified: Option<u64>,
+    pub hash: u64,
+}
+
+impl FileFingerprint {
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let metadata = std::fs::metadata(path).map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            size: metadata.len(),
+            modified: Self::modified_secs(&metadata),
+            hash: Self::hash_file(path)?,
+        })
+    }
+
+    fn modified_secs(metadata: &std::fs::Metadata) -> Option<u64> {
+        metadata
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|v| v.as_secs())
+    }
+
+    /// FNV-1a, stable between builds unlike the std hasher.
+    fn hash_file(path: &Path) -> Result<u64, String> {
+        let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+        let mut buffer = [0u8; 8192];
+        let mut hash: u64 = 0xcbf29ce484222325;
+
+        loop {
+            let read = file.read(&mut buffer).map_err(|e| e.to_string())?;
+            if read == 0 {
+                return Ok(hash);
+            }
+
+            for byte in &buffer[..read] {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub fingerprint: FileFingerprint,
+    pub runs: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileState {
+    New,
+    Changed,
+    Unchanged,
+}
+
+/// how many of the files given to an import were parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub new: usize,
+    pub changed: usize,
+    pub skipped: usize,
+}
+
+impl ImportSummary {
+    pub fn add(&mut self, state: FileState) {
+        match state {
+            FileState::New => self.new += 1,
+            FileState::Changed => self.changed += 1,
+            FileState::Unchanged => self.skipped += 1,
+        }
+    }
+}
+
+impl Display for ImportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} new, {} changed, {} skipped",
+            self.new, self.changed, self.skipped
+        )
+    }
+}
+
+/// every log file imported so far, so the same file is not parsed twice.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportLedger {
+    files: HashMap<PathBuf, LedgerEntry>,
+}
+
+impl ImportLedger {
+    fn get_path() -> Option<PathBuf> {
+        get_directory().map(|v| v.join(LEDGER_FILE_NAME))
+    }
+
+    /// the ledger stored next to the save data, empty if there is none.
+    pub fn load() -> Self {
+        Self::get_path()
+            .map(|path| Self::load_from(&path))
+            .unwrap_or_default()
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        let Ok(text) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            eprintln!("Failed to read import ledger {}: {e}", path.display());
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::get_path().ok_or("No save directory found".to_owned())?;
+
+        self.save_to(&path)
+    }
+
+    /// adds the files to the saved ledger once their runs are saved.
+    pub fn commit(self) -> Result<(), String> {
+        let path = Self::get_path().ok_or("No save directory found".to_owned())?;
+
+        self.commit_to(&path)
+    }
+
+    /// the saved ledger is read again, another import may have saved it since.
+    pub fn commit_to(self, path: &Path) -> Result<(), String> {
+        let mut current = Self::load_from(path);
+        current.merge(self);
+
+        current.save_to(path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        let text = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        file_backup::write_atomic(path, text.as_bytes())
+    }
+
+    fn key(path: &Path) -> PathBuf {
+        path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&LedgerEntry> {
+        self.files.get(&Self::key(path))
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// compares the file to the one imported before.
+    ///
+    /// the content is only hashed when the size or the modified time differ,
+    /// a file that was touched but not changed still counts as unchanged.
+    pub fn check(&self, path: &Path) -> Result<(FileState, FileFingerprint), String> {
+        let Some(entry) = self.get(path) else {
+            return Ok((FileState::New, FileFingerprint::read(path)?));
+        };
+
+        let metadata = std::fs::metadata(path).map_err(|e| e.to_string())?;
+        let known = entry.fingerprint;
+        if metadata.len() == known.size
+            && FileFingerprint::modified_secs(&metadata) == known.modified
+        {
+            return Ok((FileState::Unchanged, known));
+        }
+
+        let fingerprint = FileFingerprint::read(path)?;
+        let state = match fingerprint.size == known.size && fingerprint.hash == known.hash {
+            true => FileState::Unchanged,
+            false => FileState::Changed,
+        };
+
+        Ok((state, fingerprint))
+    }
+
+    /// the entry with the fingerprint of an unchanged file that was touched since,
+    /// so the next check does not hash it again.
+    pub fn refreshed(&self, path: &Path, fingerprint: FileFingerprint) -> Option<LedgerEntry> {
+        let entry = self.get(path)?;
+
+        (entry.fingerprint != fingerprint).then_some(LedgerEntry {
+            fingerprint,
+            runs: entry.runs,
+        })
+    }
+
+    /// adds the files of another ledger, used when two imports ran at the same time.
+    pub fn merge(&mut self, other: ImportLedger) {
+        self.files.extend(other.files);
+    }
+
+    pub fn record(&mut self, path: &Path, fingerprint: FileFingerprint, runs: usize) {
+        self.files
+            .insert(Self::key(path), LedgerEntry { fingerprint, runs });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_import_ledger() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("GTFO.2024.01.22 18.53.12_NAME_NETSTATUS.txt");
+        std::fs::write(&log, "first").unwrap();
+
+        let mut ledger = ImportLedger::default();
+        let (state, fingerprint) = ledger.check(&log).unwrap();
+        assert_eq!(state, FileState::New);
+        ledger.record(&log, fingerprint, 2);
+
+        assert_eq!(ledger.check(&log).unwrap().0, FileState::Unchanged);
+        assert_eq!(ledger.get(&log).unwrap().runs, 2);
+
+        // a touched file is hashed once and then known by its new modified time
+        let touched = FileFingerprint {
+            modified: fingerprint.modified.map(|v| v + 60),
+            ..fingerprint
+        };
+        let mut stale = ImportLedger::default();
+        stale.record(&log, touched, 2);
+        let (state, fingerprint) = stale.check(&log).unwrap();
+        assert_eq!(state, FileState::Unchanged);
+        let refreshed = stale.refreshed(&log, fingerprint).unwrap();
+        assert_eq!(refreshed.runs, 2);
+        stale.record(&log, refreshed.fingerprint, refreshed.runs);
+        assert_eq!(stale, ledger);
+        assert_eq!(ledger.refreshed(&log, fingerprint), None);
+
+        std::fs::write(&log, "second run").unwrap();
+        let (state, fingerprint) = ledger.check(&log).unwrap();
+        assert_eq!(state, FileState::Changed);
+        ledger.record(&log, fingerprint, 3);
+
+        let path = dir.path().join("ledger.json");
+        ledger.save_to(&path).unwrap();
+        let loaded = ImportLedger::load_from(&path);
+        assert_eq!(loaded, ledger);
+        assert_eq!(loaded.check(&log).unwrap().0, FileState::Unchanged);
+
+        let mut merged = ImportLedger::default();
+        merged.merge(loaded);
+        assert_eq!(merged.len(), 1);
+
+        let other = dir.path().join("other.txt");
+        std::fs::write(&other, "other").unwrap();
+        let mut pending = ImportLedger::default();
+        pending.record(&other, FileFingerprint::read(&other).unwrap(), 1);
+        pending.commit_to(&path).unwrap();
+        let committed = ImportLedger::load_from(&path);
+        assert_eq!(committed.len(), 2);
+        assert_eq!(committed.get(&log), ledger.get(&log));
+
+        assert!(ledger.check(&dir.path().join("missing.txt")).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    fmt::Display,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::export::Export;
+
+/// what happened to a single file given to an import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileStatus {
+    Parsed(usize),
+    /// parsed but something looks wrong, like a log without runs.
+    Warning(String),
+    Failed(String),
+    Skipped(String),
+    Cancelled,
+}
+
+impl FileStatus {
+    fn get_name(&self) -> &'static str {
+        match self {
+            FileStatus::Parsed(_) => "parsed",
+            FileStatus::Warning(_) => "warning",
+            FileStatus::Failed(_) => "failed",
+            FileStatus::Skipped(_) => "skipped",
+            FileStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// a log without runs is a warning, it may still be written to.
+    pub fn from_runs(runs: usize) -> Self {
+        match runs {
+            0 => FileStatus::Warning("no runs found".to_owned()),
+            runs => FileStatus::Parsed(runs),
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, FileStatus::Failed(_))
+    }
+}
+
+impl Display for FileStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileStatus::Parsed(runs) => write!(f, "parsed, {runs} runs"),
+            FileStatus::Warning(reason) => write!(f, "warning: {reason}"),
+            FileStatus::Failed(reason) => write!(f, "failed: {reason}"),
+            FileStatus::Skipped(reason) => write!(f, "skipped: {reason}"),
+            FileStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub status: FileStatus,
+}
+
+/// the result of every file of an import, in the order they finished.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub files: Vec<FileReport>,
+}
+
+impl ImportReport {
+    pub fn add(&mut self, path: PathBuf, status: FileStatus) {
+        self.files.push(FileReport { path, status });
+    }
+
+    pub fn count<F>(&self, filter: F) -> usize
+    where
+        F: Fn(&FileStatus) -> bool,
+    {
+        self.files.iter().filter(|v| filter(&v.status)).count()
+    }
+
+    /// the file is checked before parsing so a broken log gets a reason
+    /// instead of silently giving no runs.
+    pub fn check_log_file(path: &Path) -> Result<(), FileStatus> {
+        if path.extension().is_none_or(|e| e != "txt") {
+            return Err(FileStatus::Skipped("not a .txt log file".to_owned()));
+        }
+
+        let metadata = std::fs::metadata(path).map_err(|e| FileStatus::Failed(e.to_string()))?;
+        if !metadata.is_file() {
+            return Err(FileStatus::Skipped("not a file".to_owned()));
+        }
+        if metadata.len() == 0 {
+            return Err(FileStatus::Failed("the file is empty".to_owned()));
+        }
+
+        Ok(())
+    }
+
+    pub fn export_csv(&self, writer: impl Write) -> Result<(), String> {
+        let mut writer = std::io::BufWriter::new(writer);
+
+        let mut write = |text: String| writer.write_all(text.as_bytes()).map_err(|e| e.to_string());
+        write("Path,Status,Runs,Reason\n".to_owned())?;
+
+        for file in &self.files {
+            let (runs, reason) = match &file.status {
+                FileStatus::Parsed(runs) => (runs.to_string(), ""),
+                FileStatus::Warning(reason) => ("0".to_owned(), reason.as_str()),
+                FileStatus::Failed(reason) | FileStatus::Skipped(reason) => (String::new(), reason.as_str()),
+                FileStatus::Cancelled => (String::new(), ""),
+            };
+
+            write(format!(
+                "{},{},{runs},{}\n",
+                Export::escape_csv(&file.path.to_string_lossy()),
+                file.status.get_name(),
+                Export::escape_csv(reason),
+            ))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_import_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let empty = dir.path().join("empty.txt");
+        std::fs::write(&empty, "").unwrap();
+        let log = dir.path().join("log.txt");
+        std::fs::write(&log, "data").unwrap();
+
+        assert_eq!(ImportReport::check_log_file(&log), Ok(()));
+        assert!(ImportReport::check_log_file(&empty).unwrap_err().is_failed());
+        assert!(ImportReport::check_log_file(&dir.path().join("missing.txt")).unwrap_err().is_failed());
+        assert_eq!(
+            ImportReport::check_log_file(&dir.path().join("save.json")),
+            Err(FileStatus::Skipped("not a .txt log file".to_owned())),
+        );
+
+        assert_eq!(FileStatus::from_runs(3), FileStatus::Parsed(3));
+
+        let mut report = ImportReport::default();
+        report.add("a.txt".into(), FileStatus::from_runs(3));
+        report.add("e.txt".into(), FileStatus::from_runs(0));
+        report.add("b, c.txt".into(), FileStatus::Failed("bad \"log\"".to_owned()));
+        report.add("d.txt".into(), FileStatus::Cancelled);
+        assert_eq!(report.count(|v| v.is_failed()), 1);
+
+        let mut csv = Vec::new();
+        report.export_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "Path,Status,Runs,Reason\n\
+            a.txt,parsed,3,\n\
+            e.txt,warning,0,no runs found\n\
+            \"b, c.txt\",failed,,\"bad \"\"log\"\"\"\n\
+            d.txt,cancelled,,\n",
+        );
+    }
+}
@@ -1,5 +1,21 @@
+pub mod comparison;
+pub mod edit_history;
 pub mod export;
+pub mod file_backup;
+pub mod import_ledger;
+pub mod import_report;
+pub mod livesplit;
+pub mod livesplit_server;
+pub mod log_parser;
+pub mod logs_folder;
 pub mod run;
+pub mod save_format;
 pub mod save_manager;
 pub mod sort;
+pub mod split_stats;
+pub mod storage;
+pub mod sum_of_best;
+#[cfg(test)]
+mod test_utils;
+pub mod trash;
 pub mod version;
@@ -0,0 +1,349 @@
+use std::fmt::Write;
+
+use chrono::NaiveDateTime;
+use glr_core::{
+    split::{NamedSplit, Split},
+    time::Time,
+};
+
+use crate::{
+    run::{
+        metadata::RunMetadata,
+        objectives::{objective_enum::ObjectiveEnum, run_objective::RunObjective},
+        timed_run::{LevelRun, RunEnum},
+        traits::Run,
+    },
+    save_manager::SaveManager,
+};
+
+const GAME_NAME: &str = "GTFO";
+const DATE_FORMAT: &str = "%m/%d/%Y %H:%M:%S";
+
+/// bridge between the saved runs and LiveSplit split files.
+pub struct LiveSplit;
+
+impl LiveSplit {
+    /// builds a `.lss` file out of every saved run of the objective.
+    ///
+    /// segments follow the split names of the objective, so merged
+    /// splits show up as a single segment.
+    pub fn export_lss(save_manager: &SaveManager, objective: &String) -> Result<String, String> {
+        let runs = save_manager
+            .get_runs(objective)
+            .ok_or(format!("No runs loaded for {objective}"))?;
+        let empty = Vec::new();
+        let split_names = save_manager.get_split_names(objective).unwrap_or(&empty);
+        let best_run = save_manager.get_best_run(objective);
+
+        let mut out = String::new();
+        let _ = writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        let _ = writeln!(out, r#"<Run version="1.7.0">"#);
+        let _ = writeln!(out, "  <GameIcon />");
+        let _ = writeln!(out, "  <GameName>{GAME_NAME}</GameName>");
+        let _ = writeln!(out, "  <CategoryName>{}</CategoryName>", escape(objective));
+        let _ = writeln!(out, "  <Offset>00:00:00</Offset>");
+        let _ = writeln!(out, "  <AttemptCount>{}</AttemptCount>", runs.len());
+
+        let _ = writeln!(out, "  <AttemptHistory>");
+        for (id, run) in runs.iter().enumerate() {
+            let dates = run
+                .get_metadata()
+                .and_then(|v| v.date)
+                .map(|start| {
+                    let end = start + chrono::Duration::milliseconds(run.get_time().get_stamp() as i64);
+                    format!(
+                        r#" started="{}" isStartedSynced="False" ended="{}" isEndedSynced="False""#,
+                        start.format(DATE_FORMAT),
+                        end.format(DATE_FORMAT)
+                    )
+                })
+                .unwrap_or_default();
+
+            match run.is_win() {
+                true => {
+                    let _ = writeln!(out, r#"    <Attempt id="{}"{dates}>"#, id + 1);
+                    let _ = writeln!(out, "      <RealTime>{}</RealTime>", to_lss_time(run.get_time()));
+                    let _ = writeln!(out, "    </Attempt>");
+                }
+                false => {
+                    let _ = writeln!(out, r#"    <Attempt id="{}"{dates} />"#, id + 1);
+                }
+            }
+        }
+        let _ = writeln!(out, "  </AttemptHistory>");
+
+        let _ = writeln!(out, "  <Segments>");
+        let mut pb_total = Some(Time::new());
+        for name in split_names {
+            let _ = writeln!(out, "    <Segment>");
+            let _ = writeln!(out, "      <Name>{}</Name>", escape(name));
+            let _ = writeln!(out, "      <Icon />");
+
+            let _ = writeln!(out, "      <SplitTimes>");
+            pb_total = match (pb_total, best_run.and_then(|v| segment_time(save_manager, v, objective, name))) {
+                (Some(total), Some(time)) => Some(total + time),
+                _ => None,
+            };
+            match pb_total {
+                Some(total) => {
+                    let _ = writeln!(out, r#"        <SplitTime name="Personal Best">"#);
+                    let _ = writeln!(out, "          <RealTime>{}</RealTime>", to_lss_time(total));
+                    let _ = writeln!(out, "        </SplitTime>");
+                }
+                None => {
+                    let _ = writeln!(out, r#"        <SplitTime name="Personal Best" />"#);
+                }
+            }
+            let _ = writeln!(out, "      </SplitTimes>");
+
+            match save_manager.get_best_split(objective, name) {
+                Some(best) => {
+                    let _ = writeln!(out, "      <BestSegmentTime>");
+                    let _ = writeln!(out, "        <RealTime>{}</RealTime>", to_lss_time(*best));
+                    let _ = writeln!(out, "      </BestSegmentTime>");
+                }
+                None => {
+                    let _ = writeln!(out, "      <BestSegmentTime />");
+                }
+            }
+
+            let _ = writeln!(out, "      <SegmentHistory>");
+            for (id, run) in runs.iter().enumerate() {
+                if let Some(time) = segment_time(save_manager, run, objective, name) {
+                    let _ = writeln!(out, r#"        <Time id="{}">"#, id + 1);
+                    let _ = writeln!(out, "          <RealTime>{}</RealTime>", to_lss_time(time));
+                    let _ = writeln!(out, "        </Time>");
+                }
+            }
+            let _ = writeln!(out, "      </SegmentHistory>");
+            let _ = writeln!(out, "    </Segment>");
+        }
+        let _ = writeln!(out, "  </Segments>");
+        let _ = writeln!(out, "  <AutoSplitterSettings />");
+        let _ = writeln!(out, "</Run>");
+
+        Ok(out)
+    }
+
+    /// reads every attempt of a `.lss` file back into level runs.
+    ///
+    /// the category name is used as the objective when it is a valid one,
+    /// otherwise `objective` is used. files without any attempt history
+    /// give back their personal best.
+    pub fn import_lss(data: &str, objective: &str) -> Result<Vec<LevelRun>, String> {
+        let document = roxmltree::Document::parse(data).map_err(|e| e.to_string())?;
+        let root = document.root_element();
+        if !root.has_tag_name("Run") {
+            return Err("Not a LiveSplit file".to_owned());
+        }
+
+        let objective = child(root, "CategoryName")
+            .and_then(|v| v.text())
+            .and_then(|v| RunObjective::try_from(v.trim()).ok())
+            .or_else(|| RunObjective::try_from(objective).ok())
+            .ok_or(format!("Invalid objective {objective}"))?;
+
+        let segments: Vec<Segment> = child(root, "Segments")
+            .map(|v| children(v, "Segment").map(Segment::parse).collect())
+            .unwrap_or_default();
+
+        let mut result = Vec::new();
+        for attempt in child(root, "AttemptHistory")
+            .map(|v| children(v, "Attempt").collect::<Vec<_>>())
+            .unwrap_or_default()
+        {
+            let Some(id) = attempt.attribute("id").and_then(|v| v.parse::<i64>().ok()) else {
+                continue;
+            };
+
+            let mut run = LevelRun::new(ObjectiveEnum::Run(objective.clone()));
+            for segment in &segments {
+                match segment.history.iter().find(|(h_id, _)| *h_id == id) {
+                    Some((_, Some(time))) => run.add_split(NamedSplit::new(*time, segment.name.clone())),
+                    // skipped, the time is part of the next segment
+                    Some((_, None)) => continue,
+                    None => break,
+                }
+            }
+
+            if run.len() == 0 {
+                continue;
+            }
+
+            run.set_win(child(attempt, "RealTime").is_some());
+            if let Some(date) = attempt
+                .attribute("started")
+                .and_then(|v| NaiveDateTime::parse_from_str(v, DATE_FORMAT).ok())
+            {
+                run.set_metadata(Some(RunMetadata::default().with_date(date)));
+            }
+
+            result.push(run);
+        }
+
+        if result.is_empty() {
+            let mut run = LevelRun::new(ObjectiveEnum::Run(objective));
+            let mut previous = Time::new();
+
+            for segment in &segments {
+                let Some(total) = segment.personal_best else {
+                    break;
+                };
+
+                run.add_split(NamedSplit::new(total - previous, segment.name.clone()));
+                previous = total;
+            }
+
+            if run.len() > 0 {
+                run.set_win(run.len() == segments.len());
+                result.push(run);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+struct Segment {
+    name: String,
+    personal_best: Option<Time>,
+    history: Vec<(i64, Option<Time>)>,
+}
+
+impl Segment {
+    fn parse(node: roxmltree::Node) -> Self {
+        let name = child(node, "Name")
+            .and_then(|v| v.text())
+            .unwrap_or_default()
+            .trim()
+            .to_owned();
+
+        let personal_best = child(node, "SplitTimes")
+            .and_then(|v| {
+                children(v, "SplitTime").find(|v| v.attribute("name") == Some("Personal Best"))
+            })
+            .and_then(real_time);
+
+        let history = child(node, "SegmentHistory")
+            .map(|v| {
+                children(v, "Time")
+                    .filter_map(|v| Some((v.attribute("id")?.parse().ok()?, real_time(v))))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            name,
+            personal_best,
+            history,
+        }
+    }
+}
+
+fn child<'a, 'input>(node: roxmltree::Node<'a, 'input>, name: &'a str) -> Option<roxmltree::Node<'a, 'input>> {
+    children(node, name).next()
+}
+
+fn children<'a, 'input: 'a>(
+    node: roxmltree::Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> + 'a {
+    node.children().filter(move |v| v.has_tag_name(name))
+}
+
+fn real_time(node: roxmltree::Node) -> Option<Time> {
+    child(node, "RealTime")
+        .and_then(|v| v.text())
+        .and_then(from_lss_time)
+}
+
+/// time of one segment in the run, merged splits are summed up.
+fn segment_time(save_manager: &SaveManager, run: &RunEnum, objective: &String, name: &String) -> Option<Time> {
+    match save_manager.get_splits_req(objective, name) {
+        Some(required) => required
+            .iter()
+            .map(|v| run.get_time_for_split(v))
+            .try_fold(Time::new(), |total, time| Some(total + time?)),
+        None => run.get_time_for_split(name),
+    }
+}
+
+/// LiveSplit writes times as `HH:MM:SS.fffffff`.
+fn to_lss_time(time: Time) -> String {
+    format!("{}0000", time)
+}
+
+/// reads `[d.]HH:MM:SS[.fffffff]` keeping millisecond precision.
+fn from_lss_time(text: &str) -> Option<Time> {
+    let text = text.trim();
+    let (days, rest) = match text.split_once('.') {
+        Some((days, rest)) if !days.contains(':') => (days.parse::<u64>().ok()?, rest),
+        _ => (0, text),
+    };
+
+    let (clock, fraction) = rest.split_once('.').unwrap_or((rest, "0"));
+    let mut parts = clock.split(':');
+    let hours = parts.next()?.parse::<u64>().ok()? + days * 24;
+    let minutes = parts.next()?.parse::<u64>().ok()?;
+    let seconds = parts.next()?.parse::<u64>().ok()?;
+    let millis: String = fraction.chars().chain("000".chars()).take(3).collect();
+
+    Time::from(&format!("{hours:02}:{minutes:02}:{seconds:02}.{millis}"))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use glr_core::{split::Split, time::Time};
+
+    use crate::{
+        run::traits::Run,
+        save_manager::SaveManager,
+        test_utils::{OBJECTIVE, run, time},
+    };
+
+    use super::{LiveSplit, from_lss_time};
+
+    #[test]
+    pub fn test_lss_time() {
+        assert_eq!(from_lss_time("00:01:02.5000000"), Some(time("00:01:02.500")));
+        assert_eq!(from_lss_time("1.02:00:00"), Some(time("26:00:00.000")));
+        assert_eq!(from_lss_time("nonsense"), None);
+    }
+
+    #[test]
+    pub fn test_lss_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let objective = OBJECTIVE.to_owned();
+        let mut save_manager = SaveManager::with_directory(dir.path().to_path_buf());
+        save_manager.save_multiple(vec![
+            run(&[("door", "00:01:00.000"), ("WIN", "00:02:00.000")], true),
+            run(&[("door", "00:00:50.000"), ("WIN", "00:02:30.000")], true),
+            run(&[("door", "00:00:40.000"), ("LOSS", "00:00:10.000")], false),
+        ]);
+
+        let lss = LiveSplit::export_lss(&save_manager, &objective).unwrap();
+        assert!(lss.contains("<BestSegmentTime>\n        <RealTime>00:00:40.0000000</RealTime>"));
+        assert!(lss.contains("<RealTime>00:03:00.0000000</RealTime>"));
+
+        let imported = LiveSplit::import_lss(&lss, OBJECTIVE).unwrap();
+        let mut wins: Vec<Time> = imported
+            .iter()
+            .filter(|v| v.is_win())
+            .map(|v| v.get_time())
+            .collect();
+        wins.sort();
+
+        assert_eq!(imported.len(), 3);
+        assert_eq!(wins, vec![time("00:03:00.000"), time("00:03:20.000")]);
+        assert_eq!(imported[0].get_objective().to_string(), objective);
+        // the time after the last segment of a lost run is not kept
+        assert!(imported.iter().any(|v| !v.is_win() && v.get_time() == time("00:00:40.000")));
+    }
+}
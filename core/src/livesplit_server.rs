@@ -0,0 +1,274 @@
+use std::{
+    io::Write,
+    net::{TcpStream, ToSocketAddrs},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use glr_core::{split::Split, time::Time};
+
+use crate::run::{timed_run::LevelRun, traits::Run};
+
+pub const DEFAULT_ADDRESS: &str = "localhost:16834";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// drives an external LiveSplit through the LiveSplit Server tcp protocol.
+///
+/// the commands are sent by a background thread so connecting to a closed
+/// LiveSplit never blocks the splitter, the thread stops when the client
+/// is dropped.
+pub struct LiveSplitClient {
+    sender: Sender<String>,
+    connected: Arc<AtomicBool>,
+
+    game_time: Time,
+}
+
+impl LiveSplitClient {
+    pub fn new(address: String) -> Self {
+        let (sender, recv) = mpsc::channel();
+        let connection = Connection::new(address);
+        let connected = connection.connected.clone();
+
+        thread::spawn(move || connection.run(recv));
+
+        Self {
+            sender,
+            connected,
+            game_time: Time::new(),
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// a new level started, the previous attempt is reset first.
+    pub fn start_run(&mut self) {
+        self.game_time = Time::new();
+
+        self.send("reset");
+        self.send("starttimer");
+        self.send("initgametime");
+        self.send("pausegametime");
+    }
+
+    /// split times are the length of the split, the game time sent is the
+    /// sum of all of them.
+    pub fn add_split<S: Split>(&mut self, split: &S) {
+        self.game_time += split.get_time();
+
+        self.set_game_time(self.game_time);
+        self.send("split");
+    }
+
+    /// wins finish the timer on the last split, losses reset it.
+    pub fn end_run(&mut self, run: &LevelRun) {
+        self.game_time = run.get_time();
+
+        match run.is_win() {
+            true => {
+                self.set_game_time(self.game_time);
+                self.send("split");
+            }
+            false => self.send("reset"),
+        }
+    }
+
+    fn set_game_time(&mut self, time: Time) {
+        self.send(&format!("setgametime {time}"));
+    }
+
+    fn send(&self, command: &str) {
+        let _ = self.sender.send(command.to_owned());
+    }
+}
+
+/// the connection of the background thread.
+///
+/// it is opened lazily and reopened after it breaks, attempts are spaced
+/// out by the retry delay and the commands sent meanwhile are dropped.
+struct Connection {
+    address: String,
+    stream: Option<TcpStream>,
+    retry_delay: Duration,
+    last_attempt: Option<Instant>,
+
+    connected: Arc<AtomicBool>,
+}
+
+impl Connection {
+    fn new(address: String) -> Self {
+        Self {
+            address,
+            stream: None,
+            retry_delay: Duration::from_secs(5),
+            last_attempt: None,
+            connected: Default::default(),
+        }
+    }
+
+    #[cfg(test)]
+    fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    fn run(mut self, recv: Receiver<String>) {
+        for command in recv {
+            if let Err(e) = self.send(&command) {
+                eprintln!("LiveSplit Server: {e}");
+            }
+            self.connected.store(self.stream.is_some(), Ordering::Relaxed);
+        }
+    }
+
+    /// sends a single command, reconnecting once if the connection broke.
+    fn send(&mut self, command: &str) -> Result<(), String> {
+        let line = format!("{command}\r\n");
+
+        if let Some(stream) = &mut self.stream {
+            if stream.write_all(line.as_bytes()).is_ok() {
+                return Ok(());
+            }
+
+            self.stream = None;
+            self.last_attempt = None;
+        }
+
+        let stream = self.connect()?;
+        stream.write_all(line.as_bytes()).map_err(|e| {
+            self.stream = None;
+            e.to_string()
+        })
+    }
+
+    fn connect(&mut self) -> Result<&mut TcpStream, String> {
+        if self
+            .last_attempt
+            .is_some_and(|v| v.elapsed() < self.retry_delay)
+        {
+            return Err(format!("Not connected to LiveSplit at {}", self.address));
+        }
+        self.last_attempt = Some(Instant::now());
+
+        let address = self
+            .address
+            .to_socket_addrs()
+            .map_err(|e| e.to_string())?
+            .next()
+            .ok_or(format!("Invalid LiveSplit address {}", self.address))?;
+        let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).map_err(|e| e.to_string())?;
+        let _ = stream.set_nodelay(true);
+        let _ = stream.set_write_timeout(Some(CONNECT_TIMEOUT));
+
+        Ok(self.stream.insert(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        time::Duration,
+    };
+
+    use glr_core::{
+        split::{NamedSplit, Split},
+        time::Time,
+    };
+
+    use crate::run::{
+        objectives::{objective_enum::ObjectiveEnum, run_objective::RunObjective},
+        timed_run::LevelRun,
+        traits::Run,
+    };
+
+    use super::{Connection, LiveSplitClient};
+
+    fn read_lines(reader: &mut impl BufRead, count: usize) -> Vec<String> {
+        (0..count)
+            .map(|_| {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                line.trim_end().to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn test_livesplit_commands() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let mut client = LiveSplitClient::new(address);
+
+        client.start_run();
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        assert_eq!(
+            read_lines(&mut reader, 4),
+            vec!["reset", "starttimer", "initgametime", "pausegametime"]
+        );
+
+        let door = NamedSplit::new(Time::from("00:01:00.000").unwrap(), "door".to_owned());
+        let generator = NamedSplit::new(Time::from("00:00:30.500").unwrap(), "generator".to_owned());
+        client.add_split(&door);
+        client.add_split(&generator);
+        assert_eq!(
+            read_lines(&mut reader, 4),
+            vec![
+                "setgametime 00:01:00.000",
+                "split",
+                "setgametime 00:01:30.500",
+                "split"
+            ]
+        );
+
+        let objective = ObjectiveEnum::Run(RunObjective::from_name("R1A1".to_owned()));
+        let mut run = LevelRun::new(objective);
+        run.add_split(door);
+        run.add_split(generator);
+        run.add_split(NamedSplit::new(Time::from("00:00:10.000").unwrap(), "WIN".to_owned()));
+        run.set_win(true);
+        client.end_run(&run);
+        assert_eq!(
+            read_lines(&mut reader, 2),
+            vec![format!("setgametime {}", run.get_time()), "split".to_owned()]
+        );
+
+        run.set_win(false);
+        client.end_run(&run);
+        assert_eq!(read_lines(&mut reader, 1), vec!["reset"]);
+    }
+
+    #[test]
+    pub fn test_livesplit_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let mut connection = Connection::new(address.to_string())
+            .with_retry_delay(Duration::from_secs(60));
+        assert!(connection.send("reset").is_err());
+        assert!(connection.stream.is_none());
+
+        // still waiting for the retry delay, nothing is attempted.
+        let listener = TcpListener::bind(address).unwrap();
+        assert!(connection.send("reset").is_err());
+
+        let mut connection = connection.with_retry_delay(Duration::ZERO);
+        connection.send("reset").unwrap();
+        assert!(connection.stream.is_some());
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        assert_eq!(read_lines(&mut reader, 1), vec!["reset"]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    ffi::{CStr, c_char, c_void},
+    fs,
+    mem::ManuallyDrop,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Sender},
+    },
+    thread,
+};
+
+use glr_core::run_gen_result::RunGeneratorResult;
+use glr_lib::dll_exports::{
+    enums::{SubscribeCode, SubscriptionType},
+    structs::CallbackInfo,
+};
+
+use crate::run::{metadata::LogMetadata, rules::RuleSet, timed_run::LevelRun, traits::Run};
+
+/// every `.txt` file given, folders are searched one level deep.
+pub fn collect_log_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut result = Vec::new();
+
+    for path in paths {
+        if path.is_dir() {
+            if let Ok(entries) = fs::read_dir(path) {
+                result.extend(entries.filter_map(|v| v.ok()).map(|v| v.path()).filter(|v| is_log(v)));
+            }
+        } else if is_log(path) {
+            result.push(path.clone());
+        }
+    }
+
+    result.sort();
+    result
+}
+
+fn is_log(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|e| e == "txt")
+}
+
+/// every run of the log with the metadata of the log and the rules applied.
+pub fn parse_file(path: &Path) -> Vec<LevelRun> {
+    let (sender, recv) = mpsc::channel();
+    // the context owns a reference to the sender, it is taken back once the parse is done.
+    let context = Arc::into_raw(Arc::new(sender)) as *const c_void;
+
+    let callback_info = CallbackInfo::new(
+        SubscribeCode::RunInfo,
+        SubscriptionType::JSON,
+        0,
+        context.into(),
+        Some(callback),
+    );
+
+    glr_lib::dll_exports::functions::process_paths(vec![path.to_path_buf()], callback_info);
+    // SAFETY: the context came from Arc::into_raw above and is reclaimed only once.
+    drop(unsafe { Arc::from_raw(context as *const Sender<LevelRun>) });
+
+    let runs: Vec<LevelRun> = recv.into_iter().collect();
+    let metadata = LogMetadata::read(path).for_runs(runs.len());
+
+    runs.into_iter()
+        .zip(metadata)
+        .map(|(mut run, metadata)| {
+            let metadata = match run.get_metadata() {
+                Some(current) => metadata.merge(current.clone()),
+                None => metadata,
+            };
+            run.set_metadata(Some(metadata));
+            RuleSet::get().apply(&mut run);
+
+            run
+        })
+        .collect()
+}
+
+/// parses all files using `jobs` threads and returns every run found.
+pub fn parse_files(files: Vec<PathBuf>, jobs: usize) -> Vec<LevelRun> {
+    let files = Mutex::new(files.into_iter());
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.max(1))
+            .map(|_| {
+                let files = &files;
+
+                scope.spawn(move || {
+                    let mut runs = Vec::new();
+                    while let Some(path) = files.lock().ok().and_then(|mut v| v.next()) {
+                        runs.extend(parse_file(&path));
+                    }
+
+                    runs
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|v| v.join().unwrap_or_default())
+            .collect()
+    })
+}
+
+extern "C" fn callback(context: *const c_void, message: *const c_char) {
+    if message.is_null() || context.is_null() {
+        eprintln!("Null pointer in callback");
+        return;
+    }
+
+    // SAFETY: the context is the Arc of parse_file, it is only borrowed here
+    // so the reference it owns is left for parse_file to reclaim.
+    let (sender, message) = unsafe {
+        let arc = ManuallyDrop::new(Arc::from_raw(context as *const Sender<LevelRun>));
+        (Arc::clone(&arc), CStr::from_ptr(message))
+    };
+
+    let Ok(json_str) = message.to_str() else {
+        return;
+    };
+
+    if let Ok(RunGeneratorResult::LevelRun(run)) =
+        serde_json::from_str::<RunGeneratorResult>(json_str)
+    {
+        let _ = sender.send(run.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::collect_log_files;
+
+    #[test]
+    pub fn test_collect_log_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let inner = dir.path().join("inner");
+        fs::create_dir(&inner).unwrap();
+        for path in [
+            dir.path().join("b.txt"),
+            dir.path().join("a.txt"),
+            dir.path().join("notes.md"),
+            inner.join("c.txt"),
+        ] {
+            fs::write(path, "").unwrap();
+        }
+
+        let single = inner.join("c.txt");
+        assert_eq!(
+            collect_log_files(&[dir.path().to_path_buf(), single.clone()]),
+            vec![dir.path().join("a.txt"), dir.path().join("b.txt"), single]
+        );
+        assert!(collect_log_files(&[dir.path().join("missing.txt")]).is_empty());
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use directories::UserDirs;
+
+pub const GTFO_APP_ID: &str = "493520";
+
+const LOCAL_LOW: &[&str] = &["AppData", "LocalLow", "10 Chambers Collective", "GTFO"];
+const PROTON_USER: &[&str] = &["pfx", "drive_c", "users", "steamuser"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogsFolderSource {
+    /// the LocalLow folder of the current user, where windows installs write.
+    Native,
+    /// the wine prefix steam creates for GTFO when run through Proton.
+    Proton,
+    /// a folder picked by the user.
+    Custom,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteamLibrary {
+    pub path: PathBuf,
+    pub has_gtfo: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogsFolderCandidate {
+    pub path: PathBuf,
+    pub source: LogsFolderSource,
+    pub log_count: usize,
+    pub last_modified: Option<SystemTime>,
+}
+
+impl LogsFolderCandidate {
+    /// reads the folder, `None` if it does not exist.
+    pub fn inspect(path: PathBuf, source: LogsFolderSource) -> Option<Self> {
+        let entries = std::fs::read_dir(&path).ok()?;
+
+        let mut log_count = 0;
+        let mut last_modified = None;
+        for entry in entries.filter_map(|v| v.ok()) {
+            if !is_log_file(&entry.path()) {
+                continue;
+            }
+
+            log_count += 1;
+            let modified = entry.metadata().and_then(|v| v.modified()).ok();
+            last_modified = last_modified.max(modified);
+        }
+
+        Some(Self {
+            path,
+            source,
+            log_count,
+            last_modified,
+        })
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.log_count > 0
+    }
+}
+
+/// log files are named like `GTFO.2024.01.22 18.53.12_NAME_NETSTATUS.txt`.
+fn is_log_file(path: &Path) -> bool {
+    path.extension().is_some_and(|v| v == "txt")
+        && path
+            .file_name()
+            .and_then(|v| v.to_str())
+            .is_some_and(|v| v.starts_with("GTFO."))
+}
+
+/// every GTFO log directly inside the folder, oldest first.
+pub fn list_log_files(path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return Vec::new();
+    };
+
+    let mut result: Vec<PathBuf> = entries
+        .filter_map(|v| v.ok())
+        .map(|v| v.path())
+        .filter(|v| is_log_file(v))
+        .collect();
+    result.sort();
+
+    result
+}
+
+fn join_all(path: &Path, parts: &[&str]) -> PathBuf {
+    parts.iter().fold(path.to_path_buf(), |acc, v| acc.join(v))
+}
+
+/// the libraries listed in a steam `libraryfolders.vdf`.
+///
+/// only the quoted strings are read, a `path` key starts a new library and
+/// the GTFO app id inside it marks it as the one GTFO is installed in.
+pub fn parse_library_folders(text: &str) -> Vec<SteamLibrary> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars();
+    while chars.by_ref().any(|c| c == '"') {
+        let mut token = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => token.extend(chars.next()),
+                c => token.push(c),
+            }
+        }
+        tokens.push(token);
+    }
+
+    let mut result: Vec<SteamLibrary> = Vec::new();
+    let mut tokens = tokens.into_iter();
+    while let Some(token) = tokens.next() {
+        if token.eq_ignore_ascii_case("path") {
+            if let Some(path) = tokens.next() {
+                result.push(SteamLibrary {
+                    path: path.into(),
+                    has_gtfo: false,
+                });
+            }
+        } else if token == GTFO_APP_ID
+            && let Some(library) = result.last_mut()
+        {
+            library.has_gtfo = true;
+        }
+    }
+
+    result
+}
+
+fn steam_roots(home: &Path) -> Vec<PathBuf> {
+    let mut result = Vec::new();
+
+    for var in ["ProgramFiles(x86)", "ProgramFiles"] {
+        if let Some(dir) = std::env::var_os(var) {
+            result.push(PathBuf::from(dir).join("Steam"));
+        }
+    }
+
+    result.push(home.join(".steam").join("steam"));
+    result.push(home.join(".steam").join("root"));
+    result.push(join_all(home, &[".local", "share", "Steam"]));
+    result.push(join_all(
+        home,
+        &[".var", "app", "com.valvesoftware.Steam", ".local", "share", "Steam"],
+    ));
+
+    result
+}
+
+/// every steam library found on this machine, the install folders included.
+pub fn find_steam_libraries(home: &Path) -> Vec<SteamLibrary> {
+    let mut result: Vec<SteamLibrary> = Vec::new();
+
+    for root in steam_roots(home).into_iter().filter(|v| v.exists()) {
+        let libraries = ["steamapps", "config"]
+            .iter()
+            .map(|v| root.join(v).join("libraryfolders.vdf"))
+            .find_map(|v| std::fs::read_to_string(v).ok())
+            .map(|v| parse_library_folders(&v))
+            .unwrap_or_default();
+
+        let root = SteamLibrary {
+            path: root,
+            has_gtfo: false,
+        };
+
+        for library in std::iter::once(root).chain(libraries) {
+            let key = library.path.canonicalize().unwrap_or(library.path.clone());
+            match result
+                .iter_mut()
+                .find(|v| v.path.canonicalize().unwrap_or(v.path.clone()) == key)
+            {
+                Some(found) => found.has_gtfo |= library.has_gtfo,
+                None => result.push(library),
+            }
+        }
+    }
+
+    result
+}
+
+/// existing logs folders under `home`, the most likely one first.
+///
+/// folders with logs come before empty ones, then the most recently
+/// written, then the ones in a library GTFO is installed in.
+pub fn find_logs_folders_in(home: &Path) -> Vec<LogsFolderCandidate> {
+    let mut candidates = vec![(join_all(home, LOCAL_LOW), LogsFolderSource::Native, false)];
+
+    for library in find_steam_libraries(home) {
+        let prefix = join_all(&library.path, &["steamapps", "compatdata", GTFO_APP_ID]);
+        let path = join_all(&join_all(&prefix, PROTON_USER), LOCAL_LOW);
+        candidates.push((path, LogsFolderSource::Proton, library.has_gtfo));
+    }
+
+    let mut result: Vec<(LogsFolderCandidate, bool)> = candidates
+        .into_iter()
+        .filter_map(|(path, source, has_gtfo)| {
+            Some((LogsFolderCandidate::inspect(path, source)?, has_gtfo))
+        })
+        .collect();
+
+    result.sort_by(|(a, a_gtfo), (b, b_gtfo)| {
+        b.is_valid()
+            .cmp(&a.is_valid())
+            .then(b.last_modified.cmp(&a.last_modified))
+            .then(b_gtfo.cmp(a_gtfo))
+    });
+    result.dedup_by(|(a, _), (b, _)| a.path == b.path);
+
+    result.into_iter().map(|(v, _)| v).collect()
+}
+
+/// existing logs folders of the current user, the most likely one first.
+pub fn find_logs_folders() -> Vec<LogsFolderCandidate> {
+    UserDirs::new()
+        .map(|v| find_logs_folders_in(v.home_dir()))
+        .unwrap_or_default()
+}
+
+/// the message to show when the folder can not be used by the splitter.
+///
+/// a folder without logs is fine, GTFO only writes the first one once it starts.
+pub fn validate_logs_folder(path: &Path) -> Result<LogsFolderCandidate, String> {
+    if path.as_os_str().is_empty() {
+        return Err("No GTFO logs folder set. Pick one in the settings.".to_owned());
+    }
+
+    LogsFolderCandidate::inspect(path.to_path_buf(), LogsFolderSource::Custom)
+        .ok_or_else(|| format!("GTFO logs folder {} does not exist.", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    pub fn test_parse_library_folders() {
+        let text = r#"
+"libraryfolders"
+{
+    "0"
+    {
+        "path"      "C:\\Program Files (x86)\\Steam"
+        "apps"
+        {
+            "228980"        "0"
+        }
+    }
+    "1"
+    {
+        "path"      "/mnt/games/SteamLibrary"
+        "apps"
+        {
+            "493520"        "17181928092"
+        }
+    }
+}"#;
+
+        let libraries = parse_library_folders(text);
+        assert_eq!(libraries.len(), 2);
+        assert_eq!(libraries[0].path, PathBuf::from("C:\\Program Files (x86)\\Steam"));
+        assert!(!libraries[0].has_gtfo);
+        assert_eq!(libraries[1].path, PathBuf::from("/mnt/games/SteamLibrary"));
+        assert!(libraries[1].has_gtfo);
+    }
+
+    #[test]
+    pub fn test_find_logs_folders() {
+        let home = tempfile::tempdir().unwrap();
+        let home = home.path();
+
+        let library = home.join("games");
+        let steam = join_all(home, &[".local", "share", "Steam"]);
+        std::fs::create_dir_all(steam.join("steamapps")).unwrap();
+        std::fs::write(
+            steam.join("steamapps").join("libraryfolders.vdf"),
+            format!(
+                "\"libraryfolders\" {{ \"0\" {{ \"path\" \"{}\" \"apps\" {{ \"493520\" \"1\" }} }} }}",
+                library.display().to_string().replace('\\', "\\\\"),
+            ),
+        )
+        .unwrap();
+
+        let proton = join_all(
+            &join_all(&library, &["steamapps", "compatdata", GTFO_APP_ID]),
+            PROTON_USER,
+        );
+        let proton = join_all(&proton, LOCAL_LOW);
+        std::fs::create_dir_all(&proton).unwrap();
+        std::fs::write(proton.join("GTFO.2024.01.22 18.53.12_NAME_NETSTATUS.txt"), "").unwrap();
+        std::fs::write(proton.join("notes.txt"), "").unwrap();
+
+        let native = join_all(home, LOCAL_LOW);
+        std::fs::create_dir_all(&native).unwrap();
+
+        let found = find_logs_folders_in(home);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].path, proton);
+        assert_eq!(found[0].source, LogsFolderSource::Proton);
+        assert_eq!(found[0].log_count, 1);
+        assert_eq!(found[1].source, LogsFolderSource::Native);
+        assert!(!found[1].is_valid());
+
+        assert_eq!(list_log_files(&proton).len(), 1);
+        assert!(list_log_files(&native).is_empty());
+
+        assert!(validate_logs_folder(&proton).is_ok());
+        assert_eq!(validate_logs_folder(&native).unwrap().log_count, 0);
+        assert!(validate_logs_folder(&home.join("missing")).is_err());
+    }
+}
@@ -1,76 +1,176 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
+};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Serialize, Deserialize, Clone)]
-pub struct MergeSplits {
-    merged: HashMap<String, String>,
-    reversed: HashMap<String, Vec<String>>,
+use crate::file_backup;
+
+const MERGE_FILE_NAME: &str = "merge_splits.ron";
+/// merges saved by older versions, read once if there is no ron file yet.
+const LEGACY_MERGE_FILE_NAME: &str = "merge_data.bin";
+
+/// splits that count as a single split.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "Group")]
+pub struct MergeGroup {
+    /// the name the merged split is saved and shown with.
+    pub name: String,
+    /// shown instead of the name when set.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    pub splits: Vec<String>,
 }
 
-impl From<&str> for MergeSplits {
-    fn from(value: &str) -> Self {
-        let mut result = HashMap::new();
-        value
-            .split('|')
-            .filter_map(|v| {
-                let mut all = v.split(':');
-                let key = all.next()?;
-                let data = all.next()?;
-
-                Some((key.to_string(), data))
-            })
-            .for_each(|(key, data)| {
-                data.split(',').for_each(|v| {
-                    result.insert(v.to_string(), key.clone());
-                });
-            });
+/// how the splits of one objective are merged, as written in ron.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "Merge")]
+pub struct MergeConfig {
+    #[serde(default)]
+    pub groups: Vec<MergeGroup>,
+    /// split names in the order they are shown, the others follow in
+    /// the order they were first done.
+    #[serde(default)]
+    pub order: Vec<String>,
+}
 
-        // println!("{:?}", result);
+impl MergeConfig {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        ron::from_str(text).map_err(|e| e.to_string())
+    }
 
-        Self {
-            reversed: Self::reverse(&result),
-            merged: result,
-        }
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).unwrap_or_default()
     }
-}
 
-impl Into<String> for &MergeSplits {
-    fn into(self) -> String {
-        let mut hash_map: HashMap<&String, Vec<&String>> = HashMap::new();
+    /// everything that keeps the merges from working, empty if there is nothing.
+    pub fn get_errors(&self) -> Vec<String> {
+        let mut result = Vec::new();
+        let mut names = HashSet::new();
+        let mut owners: HashMap<&str, &str> = HashMap::new();
+
+        for group in &self.groups {
+            if group.name.trim().is_empty() {
+                result.push("A group has no name".to_owned());
+            } else if !names.insert(group.name.as_str()) {
+                result.push(format!("The group {} is written twice", group.name));
+            }
+            if group.splits.is_empty() {
+                result.push(format!("The group {} has no splits", group.name));
+            }
 
-        for (split_name, split_result) in &self.merged {
-            match hash_map.get_mut(split_result) {
-                Some(vec) => vec.push(split_name),
-                None => {
-                    hash_map.insert(split_result, vec![split_name]);
+            for split in &group.splits {
+                if split.trim().is_empty() {
+                    result.push(format!("The group {} has an empty split name", group.name));
+                } else if let Some(owner) = owners.insert(split, &group.name) {
+                    result.push(format!("{split} is in both {owner} and {}", group.name));
                 }
             }
         }
 
-        let mut result = "".to_owned();
-        let big_len = hash_map.len();
-        for (id, (key, vec)) in hash_map.iter().enumerate() {
-            result.push_str(&format!("{key}:"));
-            let len = vec.len();
-            for (id, elem) in vec.iter().enumerate() {
-                if len == id + 1 {
-                    result.push_str(elem);
-                    break;
+        for group in &self.groups {
+            for split in &group.splits {
+                if *split != group.name && names.contains(split.as_str()) {
+                    result.push(format!(
+                        "{split} in {} is the name of another group, groups can not be merged again",
+                        group.name
+                    ));
                 }
+            }
+        }
+
+        let mut order = HashSet::new();
+        for name in &self.order {
+            if !order.insert(name) {
+                result.push(format!("{name} is in the order twice"));
+            }
+        }
+
+        result
+    }
 
-                result.push_str(&format!("{elem},"));
+    /// parts that do not match the splits the runs of the objective have.
+    pub fn get_warnings(&self, split_names: &HashSet<String>) -> Vec<String> {
+        let mut result = Vec::new();
+
+        for group in &self.groups {
+            for split in group.splits.iter().filter(|v| !split_names.contains(*v)) {
+                result.push(format!("No loaded run has the split {split} of {}", group.name));
             }
+        }
 
-            if id + 1 < big_len {
-                result.push_str("|");
+        for name in &self.order {
+            let merged = self.groups.iter().any(|v| v.name == *name);
+            if !merged && !split_names.contains(name) {
+                result.push(format!("{name} in the order is not a split"));
             }
         }
+
         result
     }
 }
 
+/// the merges of one objective with lookups both ways.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "MergeConfig", into = "MergeConfig")]
+pub struct MergeSplits {
+    config: MergeConfig,
+    merged: HashMap<String, String>,
+    reversed: HashMap<String, Vec<String>>,
+}
+
+impl From<MergeConfig> for MergeSplits {
+    fn from(config: MergeConfig) -> Self {
+        let mut merged = HashMap::new();
+        for group in &config.groups {
+            for split in &group.splits {
+                merged.insert(split.clone(), group.name.clone());
+            }
+        }
+
+        // built from the groups so the splits keep the order they are written in.
+        let mut reversed: HashMap<String, Vec<String>> = HashMap::new();
+        for group in &config.groups {
+            for split in group.splits.iter().filter(|v| merged.get(*v) == Some(&group.name)) {
+                reversed.entry(group.name.clone()).or_default().push(split.clone());
+            }
+        }
+
+        Self {
+            config,
+            merged,
+            reversed,
+        }
+    }
+}
+
+impl From<MergeSplits> for MergeConfig {
+    fn from(value: MergeSplits) -> Self {
+        value.config
+    }
+}
+
 impl MergeSplits {
+    /// reads the ron of one objective, refusing anything with errors.
+    pub fn parse(text: &str) -> Result<Self, Vec<String>> {
+        let config = MergeConfig::parse(text).map_err(|e| vec![e])?;
+
+        let errors = config.get_errors();
+        match errors.is_empty() {
+            true => Ok(config.into()),
+            false => Err(errors),
+        }
+    }
+
+    pub fn get_config(&self) -> &MergeConfig {
+        &self.config
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.config.groups.is_empty() && self.config.order.is_empty()
+    }
+
     pub fn get_split(&self, split_name: &str) -> Option<&String> {
         self.merged.get(split_name)
     }
@@ -79,33 +179,270 @@ impl MergeSplits {
         self.reversed.get(split_name)
     }
 
-    fn reverse(merged: &HashMap<String, String>) -> HashMap<String, Vec<String>> {
-        let mut result: HashMap<String, Vec<String>> = HashMap::new();
+    pub fn get_display_name<'a>(&'a self, split_name: &'a str) -> &'a str {
+        self.config
+            .groups
+            .iter()
+            .find(|v| v.name == split_name)
+            .and_then(|v| v.display_name.as_deref())
+            .unwrap_or(split_name)
+    }
+
+    pub fn get_order_position(&self, split_name: &str) -> Option<usize> {
+        self.config.order.iter().position(|v| v == split_name)
+    }
+
+    /// puts the names in the order of the config, keeping the others as they are.
+    pub fn sort_names(&self, names: &mut [String]) {
+        names.sort_by_key(|v| self.get_order_position(v).unwrap_or(usize::MAX));
+    }
+}
+
+/// layout of `merge_data.bin`.
+#[derive(Deserialize)]
+struct LegacyMergeSplits {
+    // only read to get to the reversed map.
+    #[allow(dead_code)]
+    merged: HashMap<String, String>,
+    reversed: HashMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct LegacyLevelsMergeSplits {
+    levels: HashMap<String, LegacyMergeSplits>,
+}
 
-        for (key, value) in merged {
-            match result.get_mut(value) {
-                Some(vec) => vec.push(key.clone()),
-                None => {
-                    result.insert(value.clone(), vec![key.clone()]);
+impl From<LegacyMergeSplits> for MergeSplits {
+    fn from(value: LegacyMergeSplits) -> Self {
+        let mut groups: Vec<MergeGroup> = value
+            .reversed
+            .into_iter()
+            .map(|(name, mut splits)| {
+                splits.sort();
+                MergeGroup {
+                    name,
+                    display_name: None,
+                    splits,
                 }
-            }
-        }
+            })
+            .collect();
+        groups.sort_by(|a, b| a.name.cmp(&b.name));
 
-        result
+        MergeConfig {
+            groups,
+            order: Vec::new(),
+        }
+        .into()
     }
 }
 
+/// the merges of every objective, saved as `merge_splits.ron`.
 #[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename = "MergeSplits")]
 pub struct LevelsMergeSplits {
-    levels: HashMap<String, MergeSplits>,
+    levels: BTreeMap<String, MergeSplits>,
+
+    /// the ron file could not be read or had merges with errors, it is
+    /// moved aside before it is saved over.
+    #[serde(skip)]
+    unreadable: bool,
 }
 
 impl LevelsMergeSplits {
+    /// reads the merges saved in the folder, the ones of older versions
+    /// are used if they were never saved as ron.
+    ///
+    /// merges with errors are left out.
+    pub fn load_from(dir: &Path) -> Self {
+        if let Ok(text) = std::fs::read_to_string(dir.join(MERGE_FILE_NAME)) {
+            let mut result: Self = match ron::from_str(&text) {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("Failed to read {MERGE_FILE_NAME}: {e}");
+                    return Self {
+                        levels: BTreeMap::new(),
+                        unreadable: true,
+                    };
+                }
+            };
+
+            let len = result.levels.len();
+            result.levels.retain(|level, merge| {
+                let errors = merge.get_config().get_errors();
+                for error in &errors {
+                    eprintln!("Ignored the merges of {level}: {error}");
+                }
+
+                errors.is_empty()
+            });
+            result.unreadable = result.levels.len() < len;
+
+            return result;
+        }
+
+        let Ok(data) = std::fs::read(dir.join(LEGACY_MERGE_FILE_NAME)) else {
+            return Self::default();
+        };
+        match bincode::deserialize::<LegacyLevelsMergeSplits>(&data) {
+            Ok(legacy) => Self {
+                levels: legacy
+                    .levels
+                    .into_iter()
+                    .map(|(level, merge)| (level, merge.into()))
+                    .collect(),
+                unreadable: false,
+            },
+            Err(e) => {
+                eprintln!("Failed to read {LEGACY_MERGE_FILE_NAME}: {e}");
+                Self::default()
+            }
+        }
+    }
+
+    /// a file that could not be fully read is first moved aside.
+    pub fn save_to(&mut self, dir: &Path) -> Result<(), String> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| e.to_string())?;
+
+        let path = dir.join(MERGE_FILE_NAME);
+        if self.unreadable && path.exists() {
+            let kept = file_backup::keep_unreadable(&path)?;
+            eprintln!("Kept the unreadable merges as {}", kept.display());
+        }
+        self.unreadable = false;
+
+        file_backup::write_atomic(&path, text.as_bytes())
+    }
+
     pub fn get_level(&self, level_obj: &str) -> Option<&MergeSplits> {
         self.levels.get(level_obj)
     }
 
+    /// an empty merge removes the level.
     pub fn add_level(&mut self, level_obj: &str, data: impl Into<MergeSplits>) {
-        self.levels.insert(level_obj.to_owned(), data.into());
+        let data = data.into();
+        match data.is_empty() {
+            true => self.levels.remove(level_obj),
+            false => self.levels.insert(level_obj.to_owned(), data),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::{
+        LEGACY_MERGE_FILE_NAME, LevelsMergeSplits, MERGE_FILE_NAME, MergeConfig, MergeSplits,
+    };
+
+    const MERGE: &str = r#"Merge(
+        groups: [
+            Group(name: "bulk", display_name: Some("Bulkhead: A, B"), splits: ["bulk_a", "bulk_b"]),
+            Group(name: "door|1", splits: ["door|1"]),
+        ],
+        order: ["door|1", "bulk"],
+    )"#;
+
+    #[test]
+    pub fn test_merge_splits() {
+        let merge = MergeSplits::parse(MERGE).unwrap();
+        assert_eq!(merge.get_split("bulk_b"), Some(&"bulk".to_owned()));
+        assert_eq!(merge.get_req_splits("bulk").map(|v| v.len()), Some(2));
+        assert_eq!(merge.get_display_name("bulk"), "Bulkhead: A, B");
+        assert_eq!(merge.get_display_name("WIN"), "WIN");
+
+        let mut names = vec!["bulk".to_owned(), "WIN".to_owned(), "door|1".to_owned()];
+        merge.sort_names(&mut names);
+        assert_eq!(names, vec!["door|1", "bulk", "WIN"]);
+
+        let seen = HashSet::from(["bulk_a".to_owned(), "door|1".to_owned()]);
+        assert_eq!(merge.get_config().get_warnings(&seen).len(), 1);
+        assert_eq!(MergeSplits::parse(&merge.get_config().to_ron()), Ok(merge));
+
+        let errors = MergeSplits::parse(
+            r#"Merge(groups: [
+                Group(name: "a", splits: ["x", "b"]),
+                Group(name: "b", splits: ["x"]),
+                Group(name: "", splits: []),
+            ])"#,
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 4);
+        assert!(MergeSplits::parse("Merge(groups: [Group(name: \"a\")])").is_err());
+    }
+
+    #[test]
+    pub fn test_legacy_merge_splits() {
+        #[derive(serde::Serialize)]
+        struct Legacy {
+            merged: HashMap<String, String>,
+            reversed: HashMap<String, Vec<String>>,
+        }
+        #[derive(serde::Serialize)]
+        struct LegacyLevels {
+            levels: HashMap<String, Legacy>,
+        }
+
+        let legacy = LegacyLevels {
+            levels: HashMap::from([(
+                "R1A1_1.save".to_owned(),
+                Legacy {
+                    merged: HashMap::from([
+                        ("b".to_owned(), "a".to_owned()),
+                        ("c".to_owned(), "a".to_owned()),
+                    ]),
+                    reversed: HashMap::from([("a".to_owned(), vec!["c".to_owned(), "b".to_owned()])]),
+                },
+            )]),
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(LEGACY_MERGE_FILE_NAME),
+            bincode::serialize(&legacy).unwrap(),
+        )
+        .unwrap();
+        let mut levels = LevelsMergeSplits::load_from(dir.path());
+        let merge = levels.get_level("R1A1_1.save").unwrap();
+        assert_eq!(merge.get_config().groups[0].splits, vec!["b", "c"]);
+
+        levels.add_level("R1A2_1.save", MergeConfig::parse(MERGE).unwrap());
+        levels.save_to(dir.path()).unwrap();
+        let loaded = LevelsMergeSplits::load_from(dir.path());
+        assert_eq!(loaded.get_level("R1A2_1.save"), levels.get_level("R1A2_1.save"));
+        assert_eq!(loaded.get_level("R1A1_1.save"), levels.get_level("R1A1_1.save"));
+
+        levels.add_level("R1A2_1.save", MergeConfig::default());
+        assert!(levels.get_level("R1A2_1.save").is_none());
+    }
+
+    #[test]
+    pub fn test_invalid_merge_file_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(MERGE_FILE_NAME);
+        let invalid = r#"MergeSplits(levels: {
+            "R1A1_1.save": Merge(groups: [Group(name: "a", splits: ["b"]), Group(name: "a", splits: ["c"])]),
+            "R1A2_1.save": Merge(groups: [Group(name: "a", splits: ["b", "c"])]),
+        })"#;
+        std::fs::write(&path, invalid).unwrap();
+
+        let mut levels = LevelsMergeSplits::load_from(dir.path());
+        assert!(levels.get_level("R1A1_1.save").is_none());
+        assert!(levels.get_level("R1A2_1.save").is_some());
+
+        levels.save_to(dir.path()).unwrap();
+        let kept: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|v| v.unwrap().path())
+            .filter(|v| v != &path)
+            .collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(std::fs::read_to_string(&kept[0]).unwrap(), invalid);
+
+        std::fs::write(&path, "not ron").unwrap();
+        let mut levels = LevelsMergeSplits::load_from(dir.path());
+        levels.save_to(dir.path()).unwrap();
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 3);
     }
 }
@@ -0,0 +1,231 @@
+use std::{fmt::Display, fs, path::Path, time::SystemTime};
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::run::rules::RunFlag;
+
+const BUILD_PREFIX: &str = "Setting watermark revision to ";
+const BUILDER_LINE: &str = "Builder.Build, Start Building";
+const SESSION_SEED_PREFIX: &str = "SessionSeed: ";
+
+/// extra information about where and when a run happened.
+///
+/// every field is optional since not every source knows all of them.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RunMetadata {
+    pub date: Option<NaiveDateTime>,
+    pub session_seed: Option<u64>,
+    pub log_file: Option<String>,
+    pub game_build: Option<u32>,
+    /// flags set by the rules instead of by hand, left to be reviewed.
+    pub inferred: Vec<RunFlag>,
+}
+
+impl RunMetadata {
+    /// metadata for a run that just finished.
+    pub fn now() -> Self {
+        Self::default().with_date(Local::now().naive_local())
+    }
+
+    pub fn with_date(mut self, date: NaiveDateTime) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    pub fn with_session_seed(mut self, session_seed: u64) -> Self {
+        self.session_seed = Some(session_seed);
+        self
+    }
+
+    pub fn with_log_file(mut self, log_file: String) -> Self {
+        self.log_file = Some(log_file);
+        self
+    }
+
+    pub fn with_game_build(mut self, game_build: u32) -> Self {
+        self.game_build = Some(game_build);
+        self
+    }
+
+    pub fn with_inferred(mut self, inferred: Vec<RunFlag>) -> Self {
+        self.inferred = inferred;
+        self
+    }
+
+    /// the flag was checked by hand so it is no longer inferred.
+    pub fn confirm(&mut self, flag: RunFlag) {
+        self.inferred.retain(|v| *v != flag);
+    }
+
+    /// fills in every field that is missing in self from other.
+    pub fn merge(mut self, other: RunMetadata) -> Self {
+        self.date = self.date.or(other.date);
+        self.session_seed = self.session_seed.or(other.session_seed);
+        self.log_file = self.log_file.or(other.log_file);
+        self.game_build = self.game_build.or(other.game_build);
+        if self.inferred.is_empty() {
+            self.inferred = other.inferred;
+        }
+        self
+    }
+}
+
+impl Display for RunMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+
+        if let Some(date) = &self.date {
+            parts.push(format!("Date: {}", date.format("%Y-%m-%d %H:%M:%S")));
+        }
+        if let Some(seed) = &self.session_seed {
+            parts.push(format!("Seed: {seed}"));
+        }
+        if let Some(build) = &self.game_build {
+            parts.push(format!("Build: {build}"));
+        }
+        if let Some(log_file) = &self.log_file {
+            parts.push(format!("Log: {log_file}"));
+        }
+        if !self.inferred.is_empty() {
+            let flags: Vec<String> = self.inferred.iter().map(|v| v.to_string()).collect();
+            parts.push(format!("Inferred: {}", flags.join(", ")));
+        }
+
+        write!(f, "{}", parts.join(" | "))
+    }
+}
+
+/// information found by scanning a whole log file.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LogMetadata {
+    pub date: Option<NaiveDateTime>,
+    pub log_file: Option<String>,
+    pub game_build: Option<u32>,
+    pub session_seeds: Vec<u64>,
+}
+
+impl LogMetadata {
+    pub fn read(path: &Path) -> Self {
+        let file_name = path
+            .file_name()
+            .and_then(|v| v.to_str())
+            .map(|v| v.to_owned());
+        let date = file_name
+            .as_deref()
+            .and_then(date_from_file_name)
+            .or_else(|| {
+                fs::metadata(path)
+                    .and_then(|v| v.modified())
+                    .ok()
+                    .map(date_from_system_time)
+            });
+
+        let mut result = match fs::read(path) {
+            Ok(data) => Self::parse(&String::from_utf8_lossy(&data)),
+            Err(_) => Self::default(),
+        };
+
+        result.date = date;
+        result.log_file = file_name;
+        result
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let mut result = Self::default();
+
+        for line in text.lines() {
+            if result.game_build.is_none() {
+                result.game_build = line
+                    .find(BUILD_PREFIX)
+                    .and_then(|pos| parse_number(&line[pos + BUILD_PREFIX.len()..]));
+            }
+
+            if !line.contains(BUILDER_LINE) {
+                continue;
+            }
+
+            if let Some(seed) = line
+                .find(SESSION_SEED_PREFIX)
+                .and_then(|pos| parse_number(&line[pos + SESSION_SEED_PREFIX.len()..]))
+            {
+                result.session_seeds.push(seed);
+            }
+        }
+
+        result
+    }
+
+    /// metadata for each of the `run_count` runs generated from this log.
+    ///
+    /// seeds are only given out when every run can be matched to a
+    /// level build, otherwise we cannot know which seed belongs to which run.
+    pub fn for_runs(&self, run_count: usize) -> Vec<RunMetadata> {
+        let seeds_match = self.session_seeds.len() == run_count;
+
+        (0..run_count)
+            .map(|id| RunMetadata {
+                date: self.date,
+                session_seed: match seeds_match {
+                    true => Some(self.session_seeds[id]),
+                    false => None,
+                },
+                log_file: self.log_file.clone(),
+                game_build: self.game_build,
+                inferred: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// log files are named like `GTFO.2024.01.22 18.53.12_NAME_NETSTATUS.txt`.
+fn date_from_file_name(file_name: &str) -> Option<NaiveDateTime> {
+    let date = file_name.strip_prefix("GTFO.")?.get(0..19)?;
+
+    NaiveDateTime::parse_from_str(date, "%Y.%m.%d %H.%M.%S").ok()
+}
+
+fn date_from_system_time(time: SystemTime) -> NaiveDateTime {
+    DateTime::<Local>::from(time).naive_local()
+}
+
+fn parse_number<T: std::str::FromStr>(text: &str) -> Option<T> {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+
+    text[..end].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::NaiveDate;
+
+    use super::{LogMetadata, date_from_file_name};
+
+    #[test]
+    pub fn test_read_log() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../examples/log_files/R1A1_host.maid_exp_comp.txt");
+        let log = LogMetadata::read(&path);
+
+        assert_eq!(log.game_build, Some(34855));
+        assert_eq!(log.session_seeds, vec![1043970173]);
+        assert_eq!(log.log_file.as_deref(), Some("R1A1_host.maid_exp_comp.txt"));
+
+        let runs = log.for_runs(1);
+        assert_eq!(runs[0].session_seed, Some(1043970173));
+        assert_eq!(log.for_runs(2)[1].session_seed, None);
+    }
+
+    #[test]
+    pub fn test_date_from_file_name() {
+        assert_eq!(
+            date_from_file_name("GTFO.2024.01.22 18.53.12_Tgb03_NETSTATUS.txt"),
+            NaiveDate::from_ymd_opt(2024, 1, 22).and_then(|v| v.and_hms_opt(18, 53, 12))
+        );
+        assert_eq!(date_from_file_name("R1A1_host.maid_exp_comp.txt"), None);
+    }
+}
@@ -1,5 +1,8 @@
 pub mod default_dirs;
 pub mod merge_splits;
+pub mod metadata;
+pub mod notes;
+pub mod rules;
 pub mod timed_run;
 pub mod traits;
 
@@ -0,0 +1,179 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// a recording of the run, the timestamp is where the run starts in it.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+pub struct VodLink {
+    pub url: String,
+    /// seconds from the start of the video.
+    pub timestamp: Option<u32>,
+}
+
+impl VodLink {
+    /// the url with the timestamp added as `t=1h2m3s`, which both
+    /// youtube and twitch understand.
+    pub fn get_link(&self) -> String {
+        let Some(timestamp) = self.timestamp else {
+            return self.url.clone();
+        };
+
+        let separator = match self.url.contains('?') {
+            true => '&',
+            false => '?',
+        };
+        let (hours, minutes, seconds) = (timestamp / 3600, timestamp / 60 % 60, timestamp % 60);
+
+        format!("{}{separator}t={hours}h{minutes}m{seconds}s", self.url)
+    }
+}
+
+/// reads `1:02:03`, `2:03` or `123` as seconds.
+pub fn parse_timestamp(text: &str) -> Option<u32> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let mut result: u32 = 0;
+    for (id, part) in text.split(':').enumerate() {
+        let value: u32 = part.parse().ok()?;
+        if id > 2 || (id > 0 && value >= 60) {
+            return None;
+        }
+
+        result = result.checked_mul(60)?.checked_add(value)?;
+    }
+
+    Some(result)
+}
+
+pub fn format_timestamp(timestamp: u32) -> String {
+    let (hours, minutes, seconds) = (timestamp / 3600, timestamp / 60 % 60, timestamp % 60);
+
+    match hours {
+        0 => format!("{minutes}:{seconds:02}"),
+        _ => format!("{hours}:{minutes:02}:{seconds:02}"),
+    }
+}
+
+/// what the user wrote about a run.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RunNotes {
+    pub notes: String,
+    pub tags: Vec<String>,
+    pub vod: Option<VodLink>,
+}
+
+impl RunNotes {
+    pub fn is_empty(&self) -> bool {
+        self.notes.trim().is_empty() && self.tags.is_empty() && self.vod.is_none()
+    }
+
+    /// tags are written separated by commas, empty and repeated ones are dropped.
+    pub fn parse_tags(text: &str) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+        for tag in text.split(',').map(|v| v.trim()).filter(|v| !v.is_empty()) {
+            if !result.iter().any(|v| v.eq_ignore_ascii_case(tag)) {
+                result.push(tag.to_owned());
+            }
+        }
+
+        result
+    }
+
+    /// case insensitive search in the notes, tags and vod url.
+    pub fn matches(&self, search: &str) -> bool {
+        let search = search.trim().to_lowercase();
+
+        search.is_empty()
+            || self.notes.to_lowercase().contains(&search)
+            || self.tags.iter().any(|v| v.to_lowercase().contains(&search))
+            || self
+                .vod
+                .as_ref()
+                .is_some_and(|v| v.url.to_lowercase().contains(&search))
+    }
+
+    /// keeps what both copies of the same run have.
+    pub fn merge(mut self, other: RunNotes) -> Self {
+        let notes = other.notes.trim();
+        if self.notes.trim().is_empty() {
+            self.notes = other.notes;
+        } else if !notes.is_empty() && !self.notes.contains(notes) {
+            self.notes = format!("{}\n{notes}", self.notes);
+        }
+
+        for tag in other.tags {
+            if !self.tags.iter().any(|v| v.eq_ignore_ascii_case(&tag)) {
+                self.tags.push(tag);
+            }
+        }
+        self.vod = self.vod.or(other.vod);
+
+        self
+    }
+}
+
+impl Display for RunNotes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+
+        if !self.tags.is_empty() {
+            parts.push(format!("Tags: {}", self.tags.join(", ")));
+        }
+        if let Some(vod) = &self.vod {
+            parts.push(format!("VOD: {}", vod.get_link()));
+        }
+        if !self.notes.trim().is_empty() {
+            parts.push(self.notes.trim().to_owned());
+        }
+
+        write!(f, "{}", parts.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RunNotes, VodLink, format_timestamp, parse_timestamp};
+
+    #[test]
+    pub fn test_timestamps() {
+        assert_eq!(parse_timestamp("1:02:03"), Some(3723));
+        assert_eq!(parse_timestamp(" 2:03 "), Some(123));
+        assert_eq!(parse_timestamp("95"), Some(95));
+        assert_eq!(parse_timestamp("1:60"), None);
+        assert_eq!(parse_timestamp("1:2:3:4"), None);
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(format_timestamp(3723), "1:02:03");
+        assert_eq!(format_timestamp(123), "2:03");
+
+        let vod = VodLink {
+            url: "https://youtu.be/rvlCpxyXw_k".to_owned(),
+            timestamp: Some(3723),
+        };
+        assert_eq!(vod.get_link(), "https://youtu.be/rvlCpxyXw_k?t=1h2m3s");
+    }
+
+    #[test]
+    pub fn test_notes() {
+        let notes = RunNotes {
+            notes: "Clean run".to_owned(),
+            tags: RunNotes::parse_tags("verified, pb,, Verified "),
+            vod: None,
+        };
+        assert_eq!(notes.tags, vec!["verified", "pb"]);
+        assert!(notes.matches("PB") && notes.matches("clean") && !notes.matches("glitch"));
+
+        let merged = notes.clone().merge(RunNotes {
+            notes: "Door skip at 2:00".to_owned(),
+            tags: vec!["PB".to_owned(), "wr".to_owned()],
+            vod: Some(VodLink::default()),
+        });
+        assert_eq!(merged.notes, "Clean run\nDoor skip at 2:00");
+        assert_eq!(merged.tags, vec!["verified", "pb", "wr"]);
+        assert!(merged.vod.is_some());
+        assert_eq!(notes.clone().merge(notes.clone()), notes);
+        assert!(RunNotes::default().is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::run::default_dirs::get_config_directory;
+
+use super::run_objective::RunObjective;
+
+const CATEGORIES_FILE_NAME: &str = "categories.ron";
+
+static CATEGORIES: OnceLock<Categories> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "Level")]
+pub struct CategoryLevel {
+    pub id: String,
+    #[serde(default)]
+    pub secondary: bool,
+    #[serde(default)]
+    pub overload: bool,
+}
+
+/// a multi level run written by the user, like all E levels or a marathon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "Category")]
+pub struct Category {
+    pub name: String,
+    pub player_count: u8,
+    pub levels: Vec<CategoryLevel>,
+}
+
+impl Category {
+    /// the name ends up in the `.rsave` file name, so it has to stay parsable.
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && !name
+                .chars()
+                .any(|c| matches!(c, '_' | '.' | '/' | '\\') || c.is_whitespace())
+    }
+
+    /// the levels in the order they have to be played.
+    pub fn get_levels(&self) -> Vec<RunObjective> {
+        self.levels
+            .iter()
+            .map(|level| {
+                RunObjective::from_name(level.id.to_uppercase())
+                    .with_secondary(level.secondary)
+                    .with_overload(level.overload)
+                    .with_player_count(self.player_count)
+            })
+            .collect()
+    }
+}
+
+/// every category found in `categories.ron` in the config directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Categories {
+    pub categories: Vec<Category>,
+}
+
+impl Categories {
+    /// the categories used by the whole app, loaded on first use.
+    pub fn get() -> &'static Categories {
+        CATEGORIES.get_or_init(Self::load)
+    }
+
+    /// categories with a name that can not be saved are skipped.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut result: Categories = ron::from_str(text).map_err(|e| e.to_string())?;
+
+        result.categories.retain(|category| {
+            let valid = Category::is_valid_name(&category.name);
+            if !valid {
+                eprintln!("Skipped category with invalid name: {}", category.name);
+            }
+
+            valid
+        });
+
+        Ok(result)
+    }
+
+    fn load() -> Self {
+        let Some(path) = get_config_directory().map(|v| v.join(CATEGORIES_FILE_NAME)) else {
+            return Self::default();
+        };
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        Self::parse(&text).unwrap_or_else(|e| {
+            eprintln!("Failed to read categories {}: {e}", path.display());
+            Self::default()
+        })
+    }
+
+    pub fn get_category(&self, name: &str) -> Option<&Category> {
+        self.categories.iter().find(|v| v.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::run::objectives::game_objective::GameObjective;
+
+    use super::Categories;
+
+    #[test]
+    pub fn test_parse_categories() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../examples/categories/categories.ron");
+        let categories = Categories::parse(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+        let category = categories.get_category("R8Main").unwrap();
+        assert_eq!(category.player_count, 4);
+        let levels = category.get_levels();
+        assert_eq!(levels.len(), 12);
+        assert_eq!(levels[0].to_string(), "R8A1_4.save");
+        assert!(levels.iter().all(|v| !v.secondary && !v.overload));
+
+        let category = categories.get_category("AllE").unwrap();
+        assert!(category.get_levels().iter().any(|v| v.secondary));
+
+        let objective = GameObjective::from_category(category);
+        let file_name = objective.to_string();
+        assert_eq!(GameObjective::parse(&file_name, &categories), Ok(objective));
+        assert!(GameObjective::parse("Unknown_4.rsave", &categories).is_err());
+
+        let categories = Categories::parse(
+            r#"Categories(categories: [
+                Category(name: "bad_name", player_count: 1, levels: []),
+                Category(name: "Good", player_count: 1, levels: [Level(id: "r1a1")]),
+            ])"#,
+        )
+        .unwrap();
+        assert_eq!(categories.categories.len(), 1);
+        assert_eq!(categories.categories[0].get_levels()[0].to_string(), "R1A1_1.save");
+    }
+}
@@ -2,11 +2,15 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
-use crate::run::objectives::constants::OPTIONALS_ALL;
+use crate::run::{timed_run::LevelRun, traits::Run};
 
 use super::{
-    Objective, error::ObjectiveParseError, game_run_objective::GameRunObjective,
-    game_run_rundown::GameRunRundown, run_objective::RunObjective,
+    Objective,
+    category::{Categories, Category},
+    error::ObjectiveParseError,
+    game_run_objective::GameRunObjective,
+    game_run_rundown::GameRunRundown,
+    run_objective::RunObjective,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Default, PartialOrd, Ord)]
@@ -16,8 +20,13 @@ pub struct GameObjective {
     pub player_count: u8,
 }
 
+/// categories do not depend on a rundown so they are saved as `<name>_<player count>.rsave`.
 impl Display for GameObjective {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let GameRunObjective::Category(name) = &self.objective {
+            return write!(f, "{}_{}.rsave", name, self.player_count);
+        }
+
         write!(
             f,
             "{}_{}_{}.rsave",
@@ -30,6 +39,13 @@ impl<'a> TryFrom<&'a str> for GameObjective {
     type Error = ObjectiveParseError;
 
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(value, Categories::get())
+    }
+}
+
+impl GameObjective {
+    /// reads a `.rsave` file name, `<name>_<player count>.rsave` has to name one of the categories.
+    pub fn parse(value: &str, categories: &Categories) -> Result<Self, ObjectiveParseError> {
         let mut obj = GameObjective::default();
 
         if !value.ends_with(".rsave") {
@@ -40,6 +56,22 @@ impl<'a> TryFrom<&'a str> for GameObjective {
 
         let mut split = value.trim_end_matches(".rsave").split('_');
 
+        if value.matches('_').count() == 1 {
+            let name = split.next().unwrap_or_default();
+            if categories.get_category(name).is_none() {
+                return Err(ObjectiveParseError::FailedParseIntoGameObjective);
+            }
+
+            obj.objective = GameRunObjective::Category(name.to_owned());
+            obj.player_count = split
+                .next()
+                .unwrap_or_default()
+                .parse::<u8>()
+                .unwrap_or_default();
+
+            return Ok(obj);
+        }
+
         obj.rundown = match split.next().unwrap_or_default().try_into() {
             Err(e) => return Err(e),
             Ok(r) => r,
@@ -76,35 +108,16 @@ impl Objective for GameObjective {
 
 impl Into<Vec<RunObjective>> for &GameObjective {
     fn into(self) -> Vec<RunObjective> {
-        let levels: &[&str] = self.rundown.clone().into();
-        let objectives: &[(bool, bool)] = match self.rundown {
-            GameRunRundown::Rundown1 => &OPTIONALS_ALL[0..6],
-            GameRunRundown::Rundown2 => &OPTIONALS_ALL[6..16],
-            GameRunRundown::Rundown3 => &OPTIONALS_ALL[16..23],
-            GameRunRundown::Rundown4 => &OPTIONALS_ALL[23..35],
-            GameRunRundown::Rundown5 => &OPTIONALS_ALL[35..48],
-            GameRunRundown::Rundown6 => &OPTIONALS_ALL[48..61],
-            GameRunRundown::Rundown7 => &OPTIONALS_ALL[61..71],
-            GameRunRundown::Rundown8 => &OPTIONALS_ALL[71..83],
-            GameRunRundown::FullGame => &OPTIONALS_ALL,
-        };
-
-        let mut result = Vec::new();
-
-        for (level, objective) in levels.iter().zip(objectives) {
-            let mut obj_data = RunObjective::from_name(level.to_string());
-
-            if objective.0 {
-                obj_data = obj_data.with_secondary(true)
-            }
-            if objective.1 {
-                obj_data = obj_data.with_overload(true)
-            }
-
-            result.push(obj_data.with_player_count(self.player_count));
-        }
-
-        result
+        self.rundown
+            .get_levels()
+            .into_iter()
+            .map(|level| {
+                RunObjective::from_name(level.id.clone())
+                    .with_secondary(level.secondary)
+                    .with_overload(level.overload)
+                    .with_player_count(self.player_count)
+            })
+            .collect()
     }
 }
 
@@ -124,13 +137,66 @@ impl GameObjective {
     pub fn get_mut_objectives(&mut self) -> &mut GameRunObjective {
         &mut self.objective
     }
+
+    pub fn from_category(category: &Category) -> Self {
+        Self {
+            rundown: GameRunRundown::default(),
+            objective: GameRunObjective::Category(category.name.clone()),
+            player_count: category.player_count,
+        }
+    }
+
+    /// every level that has to be finished, any% only needs the main objective.
+    pub fn get_levels(&self) -> Vec<RunObjective> {
+        match &self.objective {
+            GameRunObjective::AnyPercent => Into::<Vec<RunObjective>>::into(self)
+                .into_iter()
+                .map(|v| v.with_secondary(false).with_overload(false))
+                .collect(),
+            GameRunObjective::FullPercent => self.into(),
+            GameRunObjective::Category(name) => Categories::get()
+                .get_category(name)
+                .map(|v| v.get_levels())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|v| v.with_player_count(self.player_count))
+                .collect(),
+        }
+    }
+
+    /// whether the run finishes the level, doing more optionals than needed is fine.
+    pub fn completes_level(level: &RunObjective, run: &LevelRun) -> bool {
+        let Some(objective) = run.get_objective().as_level_run() else {
+            return false;
+        };
+
+        run.is_win()
+            && objective.level_name.eq_ignore_ascii_case(&level.level_name)
+            && (objective.secondary || !level.secondary)
+            && (objective.overload || !level.overload)
+    }
+
+    /// levels not yet finished by any of the runs, in rundown order.
+    pub fn get_missing_levels<'a>(&self, runs: impl Iterator<Item = &'a LevelRun> + Clone) -> Vec<RunObjective> {
+        self.get_levels()
+            .into_iter()
+            .filter(|level| !runs.clone().any(|run| Self::completes_level(level, run)))
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::run::objectives::{
-        game_objective::GameObjective, game_run_objective::GameRunObjective,
-        game_run_rundown::GameRunRundown,
+    use crate::{
+        run::{
+            objectives::{
+                Objective, category::Categories, error::ObjectiveParseError,
+                game_objective::GameObjective, game_run_objective::GameRunObjective,
+                game_run_rundown::GameRunRundown, run_objective::RunObjective,
+            },
+            timed_run::LevelRun,
+        },
+        test_utils,
     };
 
     #[test]
@@ -152,5 +218,55 @@ mod tests {
             Ok(run_obj.clone())
         );
         assert_eq!(run_obj.to_string(), "Rundown3_100%_2.rsave");
+
+        let categories = Categories::parse(
+            r#"Categories(categories: [Category(name: "AllE", player_count: 2, levels: [])])"#,
+        )
+        .unwrap();
+        run_obj.objective = GameRunObjective::Category("AllE".to_owned());
+        assert_eq!(run_obj.to_string(), "AllE_2.rsave");
+        assert_eq!(
+            GameObjective::parse("AllE_2.rsave", &categories),
+            Ok(GameObjective {
+                rundown: GameRunRundown::default(),
+                ..run_obj
+            })
+        );
+        assert_eq!(
+            GameObjective::parse("Marathon_2.rsave", &categories),
+            Err(ObjectiveParseError::FailedParseIntoGameObjective)
+        );
+    }
+
+    fn level_run(objective: RunObjective, is_win: bool) -> LevelRun {
+        test_utils::level_run(objective, &[("WIN", "00:10:00.000")], is_win)
+    }
+
+    #[test]
+    pub fn test_missing_levels() {
+        let mut objective = GameObjective::default().with_player_count(4);
+        let levels = objective.get_levels();
+        assert_eq!(levels.len(), 6);
+        assert!(levels.iter().all(|v| !v.secondary && !v.overload));
+
+        let mut runs: Vec<LevelRun> = levels
+            .iter()
+            .skip(1)
+            .map(|v| level_run(v.clone(), true))
+            .collect();
+        runs.push(level_run(levels[0].clone(), false));
+        let missing = objective.get_missing_levels(runs.iter());
+        assert_eq!(missing, vec![levels[0].clone()]);
+
+        runs.push(level_run(RunObjective::from_name("r1a1".to_owned()).with_secondary(true), true));
+        assert!(objective.get_missing_levels(runs.iter()).is_empty());
+
+        objective.objective = GameRunObjective::FullPercent;
+        assert!(objective.get_missing_levels(runs.iter()).is_empty());
+
+        objective.rundown = GameRunRundown::Rundown4;
+        let missing = objective.get_missing_levels(runs.iter());
+        assert_eq!(missing.len(), 12);
+        assert!(missing.iter().all(|v| v.secondary || v.overload));
     }
 }
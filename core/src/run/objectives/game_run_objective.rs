@@ -1,17 +1,16 @@
 use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
-use strum_macros::EnumIter;
 
-use super::error::ObjectiveParseError;
+use super::{category::Categories, error::ObjectiveParseError};
 
-#[derive(
-    Clone, Debug, PartialEq, Eq, EnumIter, Hash, Serialize, Deserialize, Default, PartialOrd, Ord,
-)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Default, PartialOrd, Ord)]
 pub enum GameRunObjective {
     #[default]
     AnyPercent,
     FullPercent,
+    /// user defined category, its levels ignore the rundown.
+    Category(String),
 }
 
 impl Display for GameRunObjective {
@@ -19,10 +18,26 @@ impl Display for GameRunObjective {
         match self {
             GameRunObjective::AnyPercent => write!(f, "Any%"),
             GameRunObjective::FullPercent => write!(f, "100%"),
+            GameRunObjective::Category(name) => write!(f, "{name}"),
         }
     }
 }
 
+impl GameRunObjective {
+    /// any%, 100% and every category the user wrote.
+    pub fn all() -> Vec<GameRunObjective> {
+        [GameRunObjective::AnyPercent, GameRunObjective::FullPercent]
+            .into_iter()
+            .chain(
+                Categories::get()
+                    .categories
+                    .iter()
+                    .map(|v| GameRunObjective::Category(v.name.clone())),
+            )
+            .collect()
+    }
+}
+
 impl<'a> TryInto<GameRunObjective> for &'a str {
     type Error = ObjectiveParseError;
 
@@ -30,6 +45,9 @@ impl<'a> TryInto<GameRunObjective> for &'a str {
         match self {
             "Any%" => Ok(GameRunObjective::AnyPercent),
             "100%" => Ok(GameRunObjective::FullPercent),
+            name if Categories::get().get_category(name).is_some() => {
+                Ok(GameRunObjective::Category(name.to_owned()))
+            }
             _ => Err(ObjectiveParseError::FailedParseIntoGameObjective),
         }
     }
@@ -1,13 +1,16 @@
 use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
-use strum_macros::EnumIter;
 
-use super::{constants::LEVELS_ALL, error::ObjectiveParseError};
+use super::{
+    error::ObjectiveParseError,
+    level_catalog::{LevelCatalog, LevelInfo},
+};
 
-#[derive(
-    Debug, Clone, PartialEq, Eq, EnumIter, Hash, Serialize, Deserialize, Default, PartialOrd, Ord,
-)]
+/// the rundowns a game run can be done for, levels come from the level catalog.
+///
+/// rundowns added through the catalog are `Other`, their id can not contain `_`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default, PartialOrd, Ord)]
 pub enum GameRunRundown {
     #[default]
     Rundown1,
@@ -20,6 +23,7 @@ pub enum GameRunRundown {
     Rundown8,
 
     FullGame,
+    Other(String),
 }
 
 impl Display for GameRunRundown {
@@ -34,22 +38,32 @@ impl Display for GameRunRundown {
             GameRunRundown::Rundown7 => write!(f, "Rundown7"),
             GameRunRundown::Rundown8 => write!(f, "Rundown8"),
             GameRunRundown::FullGame => write!(f, "FullGame"),
+            GameRunRundown::Other(id) => write!(f, "{id}"),
         }
     }
 }
 
-impl Into<&[&str]> for GameRunRundown {
-    fn into(self) -> &'static [&'static str] {
+impl GameRunRundown {
+    /// every rundown of the catalog followed by the full game.
+    pub fn all() -> Vec<GameRunRundown> {
+        LevelCatalog::get()
+            .rundowns
+            .iter()
+            .filter_map(|v| v.id.as_str().try_into().ok())
+            .chain([GameRunRundown::FullGame])
+            .collect()
+    }
+
+    /// the levels of the rundown in play order.
+    pub fn get_levels(&self) -> Vec<&'static LevelInfo> {
+        let catalog = LevelCatalog::get();
+
         match self {
-            GameRunRundown::Rundown1 => &LEVELS_ALL[0..6],
-            GameRunRundown::Rundown2 => &LEVELS_ALL[6..16],
-            GameRunRundown::Rundown3 => &LEVELS_ALL[16..23],
-            GameRunRundown::Rundown4 => &LEVELS_ALL[23..35],
-            GameRunRundown::Rundown5 => &LEVELS_ALL[35..48],
-            GameRunRundown::Rundown6 => &LEVELS_ALL[48..61],
-            GameRunRundown::Rundown7 => &LEVELS_ALL[61..71],
-            GameRunRundown::Rundown8 => &LEVELS_ALL[71..83],
-            GameRunRundown::FullGame => &LEVELS_ALL,
+            GameRunRundown::FullGame => catalog.iter_levels().collect(),
+            rundown => catalog
+                .get_rundown(&rundown.to_string())
+                .map(|v| v.levels.iter().collect())
+                .unwrap_or_default(),
         }
     }
 }
@@ -68,6 +82,9 @@ impl<'a> TryInto<GameRunRundown> for &'a str {
             "Rundown7" => Ok(GameRunRundown::Rundown7),
             "Rundown8" => Ok(GameRunRundown::Rundown8),
             "FullGame" => Ok(GameRunRundown::FullGame),
+            id if !id.contains('_') && LevelCatalog::get().get_rundown(id).is_some() => {
+                Ok(GameRunRundown::Other(id.to_owned()))
+            }
             _ => Err(ObjectiveParseError::FailedParseIntoGameRundown),
         }
     }
@@ -80,9 +97,12 @@ mod tests {
 
     #[test]
     pub fn test_objectives_grr() {
-        let objectives_1: &[&str] = GameRunRundown::Rundown1.into();
-        let objectives_4: &[&str] = GameRunRundown::Rundown4.into();
-        let objectives_7: &[&str] = GameRunRundown::Rundown7.into();
+        let ids = |rundown: GameRunRundown| -> Vec<&str> {
+            rundown.get_levels().iter().map(|v| v.id.as_str()).collect()
+        };
+        let objectives_1 = ids(GameRunRundown::Rundown1);
+        let objectives_4 = ids(GameRunRundown::Rundown4);
+        let objectives_7 = ids(GameRunRundown::Rundown7);
 
         assert_eq!(
             objectives_1,
@@ -0,0 +1,149 @@
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::run::default_dirs::get_config_directory;
+
+const BUNDLED_CATALOG: &str = include_str!("../../../../resources/levels.ron");
+const CATALOG_FILE_NAME: &str = "levels.ron";
+
+static CATALOG: OnceLock<LevelCatalog> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "Level")]
+pub struct LevelInfo {
+    pub id: String,
+    pub tier: String,
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(default)]
+    pub secondary: bool,
+    #[serde(default)]
+    pub overload: bool,
+}
+
+impl LevelInfo {
+    /// the display name, the level id if it has none.
+    pub fn get_display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "Rundown")]
+pub struct RundownInfo {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub levels: Vec<LevelInfo>,
+}
+
+impl RundownInfo {
+    pub fn get_display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.id)
+    }
+}
+
+/// every known level grouped by rundown.
+///
+/// the bundled catalog is used unless `levels.ron` exists in the config
+/// directory, so new rundowns do not need a new build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelCatalog {
+    pub rundowns: Vec<RundownInfo>,
+}
+
+impl Default for LevelCatalog {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
+
+impl LevelCatalog {
+    /// the catalog used by the whole app, loaded on first use.
+    pub fn get() -> &'static LevelCatalog {
+        CATALOG.get_or_init(Self::load)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        ron::from_str(text).map_err(|e| e.to_string())
+    }
+
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_CATALOG).expect("bundled level catalog is valid")
+    }
+
+    fn load() -> Self {
+        let Some(path) = get_config_directory().map(|v| v.join(CATALOG_FILE_NAME)) else {
+            return Self::bundled();
+        };
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return Self::bundled();
+        };
+
+        match Self::parse(&text) {
+            Ok(catalog) => catalog,
+            Err(e) => {
+                eprintln!("Failed to read level catalog {}: {e}", path.display());
+                Self::bundled()
+            }
+        }
+    }
+
+    pub fn get_rundown(&self, id: &str) -> Option<&RundownInfo> {
+        self.rundowns.iter().find(|v| v.id == id)
+    }
+
+    pub fn iter_levels(&self) -> impl Iterator<Item = &LevelInfo> {
+        self.rundowns.iter().flat_map(|v| v.levels.iter())
+    }
+
+    pub fn get_level(&self, id: &str) -> Option<&LevelInfo> {
+        self.iter_levels().find(|v| v.id.eq_ignore_ascii_case(id))
+    }
+
+    /// place of the level in play order, used for sorting.
+    pub fn get_position(&self, id: &str) -> Option<usize> {
+        self.iter_levels().position(|v| v.id.eq_ignore_ascii_case(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LevelCatalog;
+
+    #[test]
+    pub fn test_bundled_catalog() {
+        let catalog = LevelCatalog::bundled();
+
+        assert_eq!(catalog.rundowns.len(), 8);
+        assert_eq!(catalog.iter_levels().count(), 83);
+        assert_eq!(catalog.get_rundown("Rundown3").unwrap().levels.len(), 7);
+
+        let level = catalog.get_level("r4b2").unwrap();
+        assert_eq!(level.tier, "B");
+        assert!(level.secondary && level.overload);
+        assert_eq!(level.get_display_name(), "R4B2");
+        assert_eq!(catalog.get_position("R2A1"), Some(6));
+    }
+
+    #[test]
+    pub fn test_custom_catalog() {
+        let catalog = LevelCatalog::parse(
+            r#"LevelCatalog(rundowns: [
+                Rundown(id: "Modded", name: Some("Modded levels"), levels: [
+                    Level(id: "M1A1", tier: "A", name: Some("First"), overload: true),
+                ]),
+            ])"#,
+        )
+        .unwrap();
+
+        let level = catalog.get_level("M1A1").unwrap();
+        assert_eq!(level.get_display_name(), "First");
+        assert!(!level.secondary && level.overload);
+        assert_eq!(catalog.get_rundown("Modded").unwrap().get_display_name(), "Modded levels");
+
+        assert!(LevelCatalog::parse("LevelCatalog(rundowns: [Rundown()])").is_err());
+    }
+}
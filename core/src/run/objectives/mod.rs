@@ -1,7 +1,8 @@
 use std::fmt::Display;
 
-mod constants;
+pub mod category;
 pub mod error;
+pub mod level_catalog;
 
 pub mod game_objective;
 pub mod run_objective;
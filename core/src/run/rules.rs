@@ -0,0 +1,345 @@
+use std::{fmt::Display, sync::OnceLock};
+
+use glr_core::{split::Split, time::Time};
+use serde::{Deserialize, Serialize};
+
+use crate::run::{
+    default_dirs::get_config_directory,
+    metadata::RunMetadata,
+    objectives::objective_enum::ObjectiveEnum,
+    timed_run::LevelRun,
+    traits::Run,
+};
+
+const BUNDLED_RULES: &str = include_str!("../../../resources/rules.ron");
+const RULES_FILE_NAME: &str = "rules.ron";
+
+/// rules written for every level.
+pub const ANY_LEVEL: &str = "*";
+
+static RULES: OnceLock<RuleSet> = OnceLock::new();
+
+/// the parts of a level objective that the logs do not tell directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum RunFlag {
+    Secondary,
+    Overload,
+    Glitched,
+    EarlyDrop,
+}
+
+impl Display for RunFlag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            RunFlag::Secondary => "Secondary",
+            RunFlag::Overload => "Overload",
+            RunFlag::Glitched => "Glitched",
+            RunFlag::EarlyDrop => "Early Drop",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// something that can be checked on the splits of a run.
+///
+/// times are written like `"00:12:30.000"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Condition {
+    HasSplit(String),
+    SplitCountAtLeast(usize),
+    SplitCountAtMost(usize),
+    /// the whole run took less than the time.
+    TimeBelow(String),
+    /// the whole run took more than the time.
+    TimeAbove(String),
+    /// the split was reached before the time, counting from the start of the run.
+    SplitBefore(String, String),
+    IsWin,
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    fn parse_time(text: &str) -> Result<Time, String> {
+        Time::from(text).ok_or_else(|| format!("Invalid time: {text}"))
+    }
+
+    /// checks that every time in the condition can be read.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Condition::TimeBelow(time)
+            | Condition::TimeAbove(time)
+            | Condition::SplitBefore(_, time) => Self::parse_time(time).map(|_| ()),
+            Condition::All(conditions) | Condition::Any(conditions) => {
+                conditions.iter().try_for_each(|v| v.validate())
+            }
+            Condition::Not(condition) => condition.validate(),
+            Condition::HasSplit(_)
+            | Condition::SplitCountAtLeast(_)
+            | Condition::SplitCountAtMost(_)
+            | Condition::IsWin => Ok(()),
+        }
+    }
+
+    pub fn matches(&self, run: &LevelRun) -> bool {
+        match self {
+            Condition::HasSplit(name) => run.get_splits().any(|v| v.get_name() == name),
+            Condition::SplitCountAtLeast(count) => run.len() >= *count,
+            Condition::SplitCountAtMost(count) => run.len() <= *count,
+            Condition::TimeBelow(time) => {
+                Self::parse_time(time).is_ok_and(|time| run.get_time() < time)
+            }
+            Condition::TimeAbove(time) => {
+                Self::parse_time(time).is_ok_and(|time| run.get_time() > time)
+            }
+            Condition::SplitBefore(name, time) => {
+                let Ok(time) = Self::parse_time(time) else {
+                    return false;
+                };
+                let mut total = Time::new();
+                for split in run.get_splits() {
+                    total += split.get_time();
+                    if split.get_name() == name {
+                        return total < time;
+                    }
+                }
+
+                false
+            }
+            Condition::IsWin => run.is_win(),
+            Condition::All(conditions) => conditions.iter().all(|v| v.matches(run)),
+            Condition::Any(conditions) => conditions.iter().any(|v| v.matches(run)),
+            Condition::Not(condition) => !condition.matches(run),
+        }
+    }
+}
+
+fn default_value() -> bool {
+    true
+}
+
+/// sets the flag to `value` when the condition matches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "Rule")]
+pub struct Rule {
+    pub flag: RunFlag,
+    #[serde(default = "default_value")]
+    pub value: bool,
+    pub when: Condition,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "Level")]
+pub struct LevelRules {
+    /// the level id or `*` for every level.
+    pub level: String,
+    pub rules: Vec<Rule>,
+}
+
+/// rules that guess the flags of a run from its splits.
+///
+/// the bundled rules are used unless `rules.ron` exists in the config
+/// directory. rules for every level are checked first and a later
+/// matching rule overrides an earlier one for the same flag.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleSet {
+    pub levels: Vec<LevelRules>,
+}
+
+impl RuleSet {
+    /// the rules used by the whole app, loaded on first use.
+    pub fn get() -> &'static RuleSet {
+        RULES.get_or_init(Self::load)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let result: RuleSet = ron::from_str(text).map_err(|e| e.to_string())?;
+
+        for level in &result.levels {
+            for rule in &level.rules {
+                rule.when
+                    .validate()
+                    .map_err(|e| format!("Rule for {} on {}: {e}", rule.flag, level.level))?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_RULES).expect("bundled rules are valid")
+    }
+
+    fn load() -> Self {
+        let Some(path) = get_config_directory().map(|v| v.join(RULES_FILE_NAME)) else {
+            return Self::bundled();
+        };
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return Self::bundled();
+        };
+
+        match Self::parse(&text) {
+            Ok(rules) => rules,
+            Err(e) => {
+                eprintln!("Failed to read rules {}: {e}", path.display());
+                Self::bundled()
+            }
+        }
+    }
+
+    fn rules_for<'a>(&'a self, level: &'a str) -> impl Iterator<Item = &'a Rule> + 'a {
+        let any = self.levels.iter().filter(|v| v.level == ANY_LEVEL);
+        let exact = self
+            .levels
+            .iter()
+            .filter(move |v| v.level != ANY_LEVEL && v.level.eq_ignore_ascii_case(level));
+
+        any.chain(exact).flat_map(|v| v.rules.iter())
+    }
+
+    /// the value of every flag a rule decides for this run.
+    pub fn classify(&self, run: &LevelRun) -> Vec<(RunFlag, bool)> {
+        let Some(objective) = run.get_objective().as_level_run() else {
+            return Vec::new();
+        };
+
+        let mut result: Vec<(RunFlag, bool)> = Vec::new();
+        for rule in self.rules_for(&objective.level_name) {
+            if !rule.when.matches(run) {
+                continue;
+            }
+
+            match result.iter_mut().find(|(flag, _)| *flag == rule.flag) {
+                Some((_, value)) => *value = rule.value,
+                None => result.push((rule.flag, rule.value)),
+            }
+        }
+
+        result
+    }
+
+    /// sets the flags decided by the rules on the objective of the run
+    /// and marks them as inferred in the metadata so they can be reviewed.
+    ///
+    /// returns the flags that were set.
+    pub fn apply(&self, run: &mut LevelRun) -> Vec<RunFlag> {
+        let flags = self.classify(run);
+        if flags.is_empty() {
+            return Vec::new();
+        }
+
+        let Some(mut objective) = run.get_objective().as_level_run().cloned() else {
+            return Vec::new();
+        };
+
+        for (flag, value) in &flags {
+            match flag {
+                RunFlag::Secondary => objective.secondary = *value,
+                RunFlag::Overload => objective.overload = *value,
+                RunFlag::Glitched => objective.glitched = *value,
+                RunFlag::EarlyDrop => objective.early_drop = *value,
+            }
+        }
+        run.set_objective(ObjectiveEnum::Run(objective));
+
+        let flags: Vec<RunFlag> = flags.into_iter().map(|(flag, _)| flag).collect();
+        let metadata = run
+            .get_metadata()
+            .cloned()
+            .unwrap_or_else(RunMetadata::now)
+            .with_inferred(flags.clone());
+        run.set_metadata(Some(metadata));
+
+        flags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        run::{objectives::run_objective::RunObjective, timed_run::LevelRun, traits::Run},
+        test_utils,
+    };
+
+    use super::{RuleSet, RunFlag};
+
+    const RULES: &str = r#"RuleSet(levels: [
+        Level(level: "*", rules: [
+            Rule(flag: EarlyDrop, when: TimeBelow("00:00:30.000")),
+        ]),
+        Level(level: "R1C1", rules: [
+            Rule(flag: Secondary, when: HasSplit("secondary_door")),
+            Rule(flag: Glitched, when: All([
+                IsWin,
+                Not(SplitBefore("door_1", "00:01:00.000")),
+            ])),
+            Rule(flag: Glitched, value: false, when: SplitCountAtLeast(4)),
+        ]),
+    ])"#;
+
+    fn run(level: &str, splits: &[(&str, &str)]) -> LevelRun {
+        let objective = RunObjective::from_name(level.to_owned()).with_player_count(1);
+
+        test_utils::level_run(objective, splits, true)
+    }
+
+    #[test]
+    pub fn test_classify() {
+        let rules = RuleSet::parse(RULES).unwrap();
+
+        let secondary = run(
+            "R1C1",
+            &[
+                ("door_1", "00:00:50.000"),
+                ("secondary_door", "00:02:00.000"),
+                ("WIN", "00:01:00.000"),
+            ],
+        );
+        assert_eq!(rules.classify(&secondary), vec![(RunFlag::Secondary, true)]);
+
+        let glitched = run("r1c1", &[("door_1", "00:01:10.000"), ("WIN", "00:00:10.000")]);
+        assert_eq!(rules.classify(&glitched), vec![(RunFlag::Glitched, true)]);
+
+        let long = run(
+            "R1C1",
+            &[
+                ("door_1", "00:01:10.000"),
+                ("door_2", "00:00:10.000"),
+                ("door_3", "00:00:10.000"),
+                ("WIN", "00:00:10.000"),
+            ],
+        );
+        assert_eq!(rules.classify(&long), vec![(RunFlag::Glitched, false)]);
+
+        let early = run("R2A1", &[("LOSS", "00:00:20.000")]);
+        assert_eq!(rules.classify(&early), vec![(RunFlag::EarlyDrop, true)]);
+    }
+
+    #[test]
+    pub fn test_apply() {
+        let rules = RuleSet::parse(RULES).unwrap();
+        let mut run = run("R1C1", &[("door_1", "00:00:10.000"), ("secondary_door", "00:00:10.000")]);
+
+        assert_eq!(rules.apply(&mut run), vec![RunFlag::EarlyDrop, RunFlag::Secondary]);
+        let objective = run.get_objective().as_level_run().unwrap();
+        assert!(objective.secondary && objective.early_drop && !objective.glitched);
+        assert_eq!(run.get_objective().to_string(), "R1C1_1_sec_edrop.save");
+        assert_eq!(
+            run.get_metadata().unwrap().inferred,
+            vec![RunFlag::EarlyDrop, RunFlag::Secondary]
+        );
+    }
+
+    #[test]
+    pub fn test_invalid_rules() {
+        RuleSet::bundled();
+        assert!(RuleSet::parse(
+            r#"RuleSet(levels: [Level(level: "*", rules: [
+                Rule(flag: Overload, when: TimeAbove("soon")),
+            ])])"#
+        )
+        .is_err());
+    }
+}
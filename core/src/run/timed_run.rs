@@ -1,3 +1,5 @@
+use std::hash::{Hash, Hasher};
+
 use enum_dispatch::enum_dispatch;
 use glr_core::{
     split::{NamedSplit, Split},
@@ -6,6 +8,8 @@ use glr_core::{
 use serde::{Deserialize, Serialize};
 
 use crate::run::{
+    metadata::RunMetadata,
+    notes::RunNotes,
     objectives::{objective_enum::ObjectiveEnum, run_objective::RunObjective},
     traits::Run,
 };
@@ -36,7 +40,7 @@ impl Split for RunEnum {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TimedRun<S>
 where
     S: Split,
@@ -49,6 +53,41 @@ where
     objective: ObjectiveEnum,
     objective_cache: String,
     splits: Vec<S>,
+
+    metadata: Option<RunMetadata>,
+    notes: Option<RunNotes>,
+}
+
+// metadata and notes are left out so the same run coming from different
+// sources is still seen as a duplicate.
+impl<S> PartialEq for TimedRun<S>
+where
+    S: Split + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.total_time == other.total_time
+            && self.used_checkpoint == other.used_checkpoint
+            && self.is_win == other.is_win
+            && self.objective == other.objective
+            && self.objective_cache == other.objective_cache
+            && self.splits == other.splits
+    }
+}
+
+impl<S> Eq for TimedRun<S> where S: Split + Eq {}
+
+impl<S> Hash for TimedRun<S>
+where
+    S: Split + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.total_time.hash(state);
+        self.used_checkpoint.hash(state);
+        self.is_win.hash(state);
+        self.objective.hash(state);
+        self.objective_cache.hash(state);
+        self.splits.hash(state);
+    }
 }
 
 impl<S> Split for TimedRun<S>
@@ -106,6 +145,22 @@ where
             self.objective_cache = objective.to_owned();
         }
     }
+
+    fn get_metadata(&self) -> Option<&RunMetadata> {
+        self.metadata.as_ref()
+    }
+
+    fn set_metadata(&mut self, metadata: Option<RunMetadata>) {
+        self.metadata = metadata;
+    }
+
+    fn get_notes(&self) -> Option<&RunNotes> {
+        self.notes.as_ref()
+    }
+
+    fn set_notes(&mut self, notes: Option<RunNotes>) {
+        self.notes = notes.filter(|v| !v.is_empty());
+    }
 }
 
 impl<S: Split> Default for TimedRun<S> {
@@ -117,6 +172,8 @@ impl<S: Split> Default for TimedRun<S> {
             splits: Default::default(),
             objective: Default::default(),
             objective_cache: Default::default(),
+            metadata: Default::default(),
+            notes: Default::default(),
         }
     }
 }
@@ -133,10 +190,72 @@ where
         }
     }
 
+    /// builds a run from already known parts, used when upgrading older save layouts.
+    pub(crate) fn from_parts(
+        total_time: Time,
+        used_checkpoint: bool,
+        is_win: bool,
+        objective: ObjectiveEnum,
+        objective_cache: String,
+        splits: Vec<S>,
+    ) -> Self {
+        Self {
+            total_time,
+            used_checkpoint,
+            is_win,
+            objective,
+            objective_cache,
+            splits,
+            metadata: None,
+            notes: None,
+        }
+    }
+
     pub fn add_split(&mut self, split: S) {
         self.total_time += split.get_time();
         self.splits.push(split);
     }
+
+    /// keeps the metadata and notes of another copy of the same run.
+    pub fn merge_duplicate(&mut self, other: Self) {
+        self.metadata = match (self.metadata.take(), other.metadata) {
+            (Some(current), Some(other)) => Some(current.merge(other)),
+            (current, other) => current.or(other),
+        };
+        self.notes = match (self.notes.take(), other.notes) {
+            (Some(current), Some(other)) => Some(current.merge(other)),
+            (current, other) => current.or(other),
+        };
+    }
+}
+
+impl RunEnum {
+    /// keeps the metadata and notes of another copy of the same run.
+    pub fn merge_duplicate(&mut self, other: RunEnum) {
+        match (self, other) {
+            (RunEnum::Level(current), RunEnum::Level(other)) => current.merge_duplicate(other),
+            (RunEnum::Game(current), RunEnum::Game(other)) => current.merge_duplicate(other),
+            _ => {}
+        }
+    }
+}
+
+impl GameRun {
+    /// levels of the game objective that no split finishes yet.
+    pub fn get_missing_levels(&self) -> Vec<RunObjective> {
+        match &self.objective {
+            ObjectiveEnum::Game(objective) => objective.get_missing_levels(self.splits.iter()),
+            ObjectiveEnum::Run(_) => Vec::new(),
+        }
+    }
+
+    /// marks the run as a win if every level of the objective was finished.
+    pub fn validate(&mut self) -> bool {
+        self.is_win = matches!(self.objective, ObjectiveEnum::Game(_))
+            && self.get_missing_levels().is_empty();
+
+        self.is_win
+    }
 }
 
 impl From<glr_core::run::TimedRun<glr_core::split::NamedSplit>> for LevelRun {
@@ -149,6 +268,7 @@ impl From<glr_core::run::TimedRun<glr_core::split::NamedSplit>> for LevelRun {
 
         lr.set_objective(ObjectiveEnum::Run(objective));
         lr.set_win(value.get_is_win());
+        lr.set_metadata(Some(RunMetadata::now()));
 
         for split in value.iter_splits() {
             lr.add_split(split.clone());
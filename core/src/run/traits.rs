@@ -2,7 +2,10 @@ use crate::run::timed_run::{GameRun, LevelRun};
 use enum_dispatch::enum_dispatch;
 use glr_core::{split::Split, time::Time};
 
-use crate::run::{objectives::objective_enum::ObjectiveEnum, timed_run::RunEnum};
+use crate::run::{
+    metadata::RunMetadata, notes::RunNotes, objectives::objective_enum::ObjectiveEnum,
+    timed_run::RunEnum,
+};
 
 #[enum_dispatch]
 pub trait Run: Split {
@@ -18,6 +21,13 @@ pub trait Run: Split {
     fn set_objective(&mut self, objective: ObjectiveEnum);
     fn set_objective_str(&mut self, objective: &str);
 
+    fn get_metadata(&self) -> Option<&RunMetadata>;
+    fn set_metadata(&mut self, metadata: Option<RunMetadata>);
+
+    fn get_notes(&self) -> Option<&RunNotes>;
+    /// empty notes are not kept.
+    fn set_notes(&mut self, notes: Option<RunNotes>);
+
     fn get_split_by_name<'a>(&'a self, split_name: &str) -> Option<&'a dyn Split> {
         self.get_splits().find(|s| s.get_name() == split_name)
     }
@@ -0,0 +1,718 @@
+use serde::Deserialize;
+
+use crate::{run::timed_run::RunEnum, save_manager::SaveType};
+
+/// every save file written by the app starts with these bytes.
+pub const MAGIC: &[u8; 4] = b"GLSV";
+
+/// version of the layout of the runs inside the envelope.
+///
+/// bump this every time a field is added to `TimedRun` or to the
+/// objectives and add a migration step from the previous version.
+pub const FORMAT_VERSION: u16 = 4;
+
+const HEADER_LEN: usize = MAGIC.len() + 2 + 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Encoding {
+    Bincode = 0,
+    Json = 1,
+}
+
+impl From<SaveType> for Encoding {
+    fn from(value: SaveType) -> Self {
+        match value {
+            SaveType::Binary => Encoding::Bincode,
+            SaveType::Json => Encoding::Json,
+            // the database keeps every run as a bincode blob
+            SaveType::Sqlite => Encoding::Bincode,
+        }
+    }
+}
+
+impl TryFrom<u8> for Encoding {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Encoding::Bincode),
+            1 => Ok(Encoding::Json),
+            _ => Err(format!("Unknown encoding tag: {value}")),
+        }
+    }
+}
+
+/// header found at the start of a save file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Envelope {
+    pub version: u16,
+    pub encoding: Encoding,
+}
+
+impl Envelope {
+    /// reads the header, returns `None` for files written before
+    /// the envelope existed.
+    pub fn read(data: &[u8]) -> Result<Option<(Self, &[u8])>, String> {
+        if data.len() < HEADER_LEN || &data[0..MAGIC.len()] != MAGIC {
+            return Ok(None);
+        }
+
+        let version = u16::from_le_bytes([data[4], data[5]]);
+        let encoding = Encoding::try_from(data[6])?;
+
+        Ok(Some((Self { version, encoding }, &data[HEADER_LEN..])))
+    }
+
+    pub fn write(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(MAGIC);
+        buffer.extend_from_slice(&self.version.to_le_bytes());
+        buffer.push(self.encoding as u8);
+    }
+}
+
+/// serializes the runs with the current format version.
+pub fn encode_runs(runs: &[RunEnum], save_type: SaveType) -> Result<Vec<u8>, String> {
+    let envelope = Envelope {
+        version: FORMAT_VERSION,
+        encoding: save_type.into(),
+    };
+    let mut buffer = Vec::new();
+    envelope.write(&mut buffer);
+
+    match envelope.encoding {
+        Encoding::Bincode => bincode::serialize_into(&mut buffer, runs).map_err(|e| e.to_string())?,
+        Encoding::Json => serde_json::to_writer(&mut buffer, runs).map_err(|e| e.to_string())?,
+    }
+
+    Ok(buffer)
+}
+
+/// reads runs from any layout the app ever wrote and upgrades
+/// them to the current one.
+pub fn decode_runs(data: &[u8]) -> Result<Vec<RunEnum>, String> {
+    let payload = match Envelope::read(data)? {
+        Some((envelope, payload)) => match envelope.version {
+            1 => Payload::V1(decode(envelope.encoding, payload)?),
+            2 => Payload::V2(decode(envelope.encoding, payload)?),
+            3 => Payload::V3(decode(envelope.encoding, payload)?),
+            4 => Payload::V4(decode(envelope.encoding, payload)?),
+            version => {
+                return Err(format!(
+                    "Save format version {version} is newer than supported {FORMAT_VERSION}"
+                ));
+            }
+        },
+        None => Payload::V0(decode_legacy(data)?),
+    };
+
+    Ok(payload.migrate())
+}
+
+fn decode<T>(encoding: Encoding, payload: &[u8]) -> Result<T, String>
+where
+    T: for<'a> Deserialize<'a>,
+{
+    match encoding {
+        Encoding::Bincode => bincode::deserialize(payload).map_err(|e| e.to_string()),
+        Encoding::Json => serde_json::from_slice(payload).map_err(|e| e.to_string()),
+    }
+}
+
+/// files without a header were either plain bincode or plain json.
+fn decode_legacy(data: &[u8]) -> Result<Vec<v0::RunEnum>, String> {
+    let looks_like_json = data
+        .iter()
+        .find(|v| !v.is_ascii_whitespace())
+        .is_some_and(|v| *v == b'[');
+    let order = match looks_like_json {
+        true => [Encoding::Json, Encoding::Bincode],
+        false => [Encoding::Bincode, Encoding::Json],
+    };
+
+    let mut errors = Vec::new();
+    for encoding in order {
+        match decode(encoding, data) {
+            Ok(runs) => return Ok(runs),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    Err(errors.join(", "))
+}
+
+/// runs as they were read from disk, one variant per format version.
+enum Payload {
+    V0(Vec<v0::RunEnum>),
+    /// same layout as v0, only the header was added.
+    V1(Vec<v0::RunEnum>),
+    /// adds the optional run metadata.
+    V2(Vec<v2::RunEnum>),
+    /// adds the flags inferred by the rules to the metadata.
+    V3(Vec<v3::RunEnum>),
+    /// adds the notes, tags and vod link.
+    V4(Vec<RunEnum>),
+}
+
+impl Payload {
+    /// applies every migration step until the current version is reached.
+    fn migrate(self) -> Vec<RunEnum> {
+        match self {
+            Payload::V0(runs) => Payload::V1(runs).migrate(),
+            Payload::V1(runs) => Payload::V2(runs.into_iter().map(|v| v.into()).collect()).migrate(),
+            Payload::V2(runs) => Payload::V3(runs.into_iter().map(|v| v.into()).collect()).migrate(),
+            Payload::V3(runs) => Payload::V4(runs.into_iter().map(|v| v.into()).collect()).migrate(),
+            Payload::V4(runs) => runs,
+        }
+    }
+}
+
+/// frozen copy of the layout used by headerless save files and version 1.
+///
+/// never change these types, bincode depends on the exact field order.
+mod v0 {
+    use glr_core::{split::NamedSplit, time::Time};
+    use serde::{Deserialize, Serialize};
+
+    use crate::run::objectives::run_objective;
+
+    use super::{v2, v3};
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub enum ObjectiveEnum {
+        Run(RunObjective),
+        Game(GameObjective),
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct RunObjective {
+        pub level_name: String,
+        pub secondary: bool,
+        pub overload: bool,
+        pub glitched: bool,
+        pub early_drop: bool,
+        pub player_count: u8,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct GameObjective {
+        pub rundown: GameRunRundown,
+        pub objective: GameRunObjective,
+        pub player_count: u8,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub enum GameRunObjective {
+        AnyPercent,
+        FullPercent,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub enum GameRunRundown {
+        Rundown1,
+        Rundown2,
+        Rundown3,
+        Rundown4,
+        Rundown5,
+        Rundown6,
+        Rundown7,
+        Rundown8,
+        FullGame,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub enum RunEnum {
+        Level(TimedRun<NamedSplit>),
+        Game(TimedRun<TimedRun<NamedSplit>>),
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct TimedRun<S> {
+        pub total_time: Time,
+
+        pub used_checkpoint: bool,
+        pub is_win: bool,
+
+        pub objective: ObjectiveEnum,
+        pub objective_cache: String,
+        pub splits: Vec<S>,
+    }
+
+    impl<S, T> From<TimedRun<S>> for v2::TimedRun<T>
+    where
+        T: From<S>,
+    {
+        fn from(value: TimedRun<S>) -> Self {
+            v2::TimedRun {
+                total_time: value.total_time,
+                used_checkpoint: value.used_checkpoint,
+                is_win: value.is_win,
+                objective: value.objective,
+                objective_cache: value.objective_cache,
+                splits: value.splits.into_iter().map(|v| v.into()).collect(),
+                metadata: None,
+            }
+        }
+    }
+
+    impl From<RunEnum> for v2::RunEnum {
+        fn from(value: RunEnum) -> Self {
+            match value {
+                RunEnum::Level(run) => v2::RunEnum::Level(run.into()),
+                RunEnum::Game(run) => v2::RunEnum::Game(run.into()),
+            }
+        }
+    }
+
+    impl From<RunObjective> for run_objective::RunObjective {
+        fn from(value: RunObjective) -> Self {
+            run_objective::RunObjective {
+                level_name: value.level_name,
+                secondary: value.secondary,
+                overload: value.overload,
+                glitched: value.glitched,
+                early_drop: value.early_drop,
+                player_count: value.player_count,
+            }
+        }
+    }
+
+    impl From<GameRunObjective> for v3::GameRunObjective {
+        fn from(value: GameRunObjective) -> Self {
+            match value {
+                GameRunObjective::AnyPercent => v3::GameRunObjective::AnyPercent,
+                GameRunObjective::FullPercent => v3::GameRunObjective::FullPercent,
+            }
+        }
+    }
+
+    impl From<GameRunRundown> for v3::GameRunRundown {
+        fn from(value: GameRunRundown) -> Self {
+            match value {
+                GameRunRundown::Rundown1 => v3::GameRunRundown::Rundown1,
+                GameRunRundown::Rundown2 => v3::GameRunRundown::Rundown2,
+                GameRunRundown::Rundown3 => v3::GameRunRundown::Rundown3,
+                GameRunRundown::Rundown4 => v3::GameRunRundown::Rundown4,
+                GameRunRundown::Rundown5 => v3::GameRunRundown::Rundown5,
+                GameRunRundown::Rundown6 => v3::GameRunRundown::Rundown6,
+                GameRunRundown::Rundown7 => v3::GameRunRundown::Rundown7,
+                GameRunRundown::Rundown8 => v3::GameRunRundown::Rundown8,
+                GameRunRundown::FullGame => v3::GameRunRundown::FullGame,
+            }
+        }
+    }
+
+    impl From<GameObjective> for v3::GameObjective {
+        fn from(value: GameObjective) -> Self {
+            v3::GameObjective {
+                rundown: value.rundown.into(),
+                objective: value.objective.into(),
+                player_count: value.player_count,
+            }
+        }
+    }
+
+    impl From<ObjectiveEnum> for v3::ObjectiveEnum {
+        fn from(value: ObjectiveEnum) -> Self {
+            match value {
+                ObjectiveEnum::Run(objective) => v3::ObjectiveEnum::Run(objective),
+                ObjectiveEnum::Game(objective) => v3::ObjectiveEnum::Game(objective.into()),
+            }
+        }
+    }
+}
+
+/// frozen copy of the layout used by version 2, the objectives did not
+/// change since version 1.
+///
+/// never change these types, bincode depends on the exact field order.
+mod v2 {
+    use chrono::NaiveDateTime;
+    use glr_core::{split::NamedSplit, time::Time};
+    use serde::{Deserialize, Serialize};
+
+    use super::{v0::ObjectiveEnum, v3};
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub enum RunEnum {
+        Level(TimedRun<NamedSplit>),
+        Game(TimedRun<TimedRun<NamedSplit>>),
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+    pub struct RunMetadata {
+        pub date: Option<NaiveDateTime>,
+        pub session_seed: Option<u64>,
+        pub log_file: Option<String>,
+        pub game_build: Option<u32>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct TimedRun<S> {
+        pub total_time: Time,
+
+        pub used_checkpoint: bool,
+        pub is_win: bool,
+
+        pub objective: ObjectiveEnum,
+        pub objective_cache: String,
+        pub splits: Vec<S>,
+
+        pub metadata: Option<RunMetadata>,
+    }
+
+    impl From<RunMetadata> for v3::RunMetadata {
+        fn from(value: RunMetadata) -> Self {
+            v3::RunMetadata {
+                date: value.date,
+                session_seed: value.session_seed,
+                log_file: value.log_file,
+                game_build: value.game_build,
+                inferred: Vec::new(),
+            }
+        }
+    }
+
+    impl<S, T> From<TimedRun<S>> for v3::TimedRun<T>
+    where
+        T: From<S>,
+    {
+        fn from(value: TimedRun<S>) -> Self {
+            v3::TimedRun {
+                total_time: value.total_time,
+                used_checkpoint: value.used_checkpoint,
+                is_win: value.is_win,
+                objective: value.objective.into(),
+                objective_cache: value.objective_cache,
+                splits: value.splits.into_iter().map(|v| v.into()).collect(),
+                metadata: value.metadata.map(|v| v.into()),
+            }
+        }
+    }
+
+    impl From<RunEnum> for v3::RunEnum {
+        fn from(value: RunEnum) -> Self {
+            match value {
+                RunEnum::Level(run) => v3::RunEnum::Level(run.into()),
+                RunEnum::Game(run) => v3::RunEnum::Game(run.into()),
+            }
+        }
+    }
+}
+
+/// frozen copy of the layout used by version 3, game objectives got
+/// categories and other rundowns.
+///
+/// never change these types, bincode depends on the exact field order.
+mod v3 {
+    use chrono::NaiveDateTime;
+    use glr_core::{
+        split::{NamedSplit, Split},
+        time::Time,
+    };
+    use serde::{Deserialize, Serialize};
+
+    use crate::run::{
+        metadata,
+        objectives::{game_objective, game_run_objective, game_run_rundown, objective_enum},
+        rules,
+        timed_run::{self, TimedRun as CurrentTimedRun},
+        traits::Run,
+    };
+
+    use super::v0::RunObjective;
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub enum ObjectiveEnum {
+        Run(RunObjective),
+        Game(GameObjective),
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct GameObjective {
+        pub rundown: GameRunRundown,
+        pub objective: GameRunObjective,
+        pub player_count: u8,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub enum GameRunObjective {
+        AnyPercent,
+        FullPercent,
+        Category(String),
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub enum GameRunRundown {
+        Rundown1,
+        Rundown2,
+        Rundown3,
+        Rundown4,
+        Rundown5,
+        Rundown6,
+        Rundown7,
+        Rundown8,
+        FullGame,
+        Other(String),
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+    pub enum RunFlag {
+        Secondary,
+        Overload,
+        Glitched,
+        EarlyDrop,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct RunMetadata {
+        pub date: Option<NaiveDateTime>,
+        pub session_seed: Option<u64>,
+        pub log_file: Option<String>,
+        pub game_build: Option<u32>,
+        pub inferred: Vec<RunFlag>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub enum RunEnum {
+        Level(TimedRun<NamedSplit>),
+        Game(TimedRun<TimedRun<NamedSplit>>),
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct TimedRun<S> {
+        pub total_time: Time,
+
+        pub used_checkpoint: bool,
+        pub is_win: bool,
+
+        pub objective: ObjectiveEnum,
+        pub objective_cache: String,
+        pub splits: Vec<S>,
+
+        pub metadata: Option<RunMetadata>,
+    }
+
+    impl From<GameRunObjective> for game_run_objective::GameRunObjective {
+        fn from(value: GameRunObjective) -> Self {
+            use game_run_objective::GameRunObjective as Current;
+
+            match value {
+                GameRunObjective::AnyPercent => Current::AnyPercent,
+                GameRunObjective::FullPercent => Current::FullPercent,
+                GameRunObjective::Category(name) => Current::Category(name),
+            }
+        }
+    }
+
+    impl From<GameRunRundown> for game_run_rundown::GameRunRundown {
+        fn from(value: GameRunRundown) -> Self {
+            use game_run_rundown::GameRunRundown as Current;
+
+            match value {
+                GameRunRundown::Rundown1 => Current::Rundown1,
+                GameRunRundown::Rundown2 => Current::Rundown2,
+                GameRunRundown::Rundown3 => Current::Rundown3,
+                GameRunRundown::Rundown4 => Current::Rundown4,
+                GameRunRundown::Rundown5 => Current::Rundown5,
+                GameRunRundown::Rundown6 => Current::Rundown6,
+                GameRunRundown::Rundown7 => Current::Rundown7,
+                GameRunRundown::Rundown8 => Current::Rundown8,
+                GameRunRundown::FullGame => Current::FullGame,
+                GameRunRundown::Other(name) => Current::Other(name),
+            }
+        }
+    }
+
+    impl From<GameObjective> for game_objective::GameObjective {
+        fn from(value: GameObjective) -> Self {
+            game_objective::GameObjective {
+                rundown: value.rundown.into(),
+                objective: value.objective.into(),
+                player_count: value.player_count,
+            }
+        }
+    }
+
+    impl From<ObjectiveEnum> for objective_enum::ObjectiveEnum {
+        fn from(value: ObjectiveEnum) -> Self {
+            match value {
+                ObjectiveEnum::Run(objective) => objective_enum::ObjectiveEnum::Run(objective.into()),
+                ObjectiveEnum::Game(objective) => objective_enum::ObjectiveEnum::Game(objective.into()),
+            }
+        }
+    }
+
+    impl From<RunFlag> for rules::RunFlag {
+        fn from(value: RunFlag) -> Self {
+            match value {
+                RunFlag::Secondary => rules::RunFlag::Secondary,
+                RunFlag::Overload => rules::RunFlag::Overload,
+                RunFlag::Glitched => rules::RunFlag::Glitched,
+                RunFlag::EarlyDrop => rules::RunFlag::EarlyDrop,
+            }
+        }
+    }
+
+    impl From<RunMetadata> for metadata::RunMetadata {
+        fn from(value: RunMetadata) -> Self {
+            metadata::RunMetadata {
+                date: value.date,
+                session_seed: value.session_seed,
+                log_file: value.log_file,
+                game_build: value.game_build,
+                inferred: value.inferred.into_iter().map(|v| v.into()).collect(),
+            }
+        }
+    }
+
+    impl<S, T> From<TimedRun<S>> for CurrentTimedRun<T>
+    where
+        T: Split + From<S>,
+    {
+        fn from(value: TimedRun<S>) -> Self {
+            let mut run = CurrentTimedRun::from_parts(
+                value.total_time,
+                value.used_checkpoint,
+                value.is_win,
+                value.objective.into(),
+                value.objective_cache,
+                value.splits.into_iter().map(|v| v.into()).collect(),
+            );
+            run.set_metadata(value.metadata.map(|v| v.into()));
+
+            run
+        }
+    }
+
+    impl From<RunEnum> for timed_run::RunEnum {
+        fn from(value: RunEnum) -> Self {
+            match value {
+                RunEnum::Level(run) => timed_run::RunEnum::Level(run.into()),
+                RunEnum::Game(run) => timed_run::RunEnum::Game(run.into()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use glr_core::split::Split;
+
+    use crate::{
+        run::{
+            metadata::RunMetadata,
+            notes::{RunNotes, VodLink},
+            rules::RunFlag,
+            traits::Run,
+        },
+        save_manager::SaveType,
+        test_utils::time,
+    };
+
+    use super::{Envelope, FORMAT_VERSION, decode_runs, encode_runs};
+
+    /// every version of the app that changed the layout, the files in
+    /// `examples/save_files` were written by the build of that version.
+    const VERSIONS: [u16; 5] = [0, 1, 2, 3, 4];
+
+    fn fixture(version: u16, encoding: &str, extension: &str) -> Vec<u8> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join(format!("../examples/save_files/v{version}_{encoding}.{extension}"));
+
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    pub fn test_load_fixtures() {
+        for version in VERSIONS {
+            for encoding in ["bincode", "json"] {
+                let data = fixture(version, encoding, "save");
+                let header = Envelope::read(&data).unwrap().map(|(v, _)| v.version);
+                assert_eq!(header, Some(version).filter(|v| *v > 0));
+
+                let runs = decode_runs(&data).unwrap();
+                assert_eq!(runs.len(), 2);
+                assert_eq!(runs[0].get_objective().to_string(), "R1A1_1_sec.save");
+                assert_eq!(runs[0].len(), 3);
+                assert_eq!(runs[0].get_time(), time("00:05:30.000"));
+                assert_eq!(runs[0].get_time_for_split("door_2"), Some(time("00:02:00.000")));
+                assert!(runs[0].is_win());
+                assert_eq!(runs[1].len(), 2);
+                assert!(!runs[1].is_win());
+                assert_eq!(runs[1].get_metadata(), None);
+
+                let metadata = runs[0].get_metadata();
+                match version {
+                    0 | 1 => assert_eq!(metadata, None),
+                    _ => {
+                        let metadata = metadata.unwrap();
+                        assert_eq!(metadata.session_seed, Some(1043970173));
+                        assert_eq!(metadata.game_build, Some(34855));
+                        let inferred = match version {
+                            2 => vec![],
+                            _ => vec![RunFlag::Secondary],
+                        };
+                        assert_eq!(metadata.inferred, inferred);
+                    }
+                }
+
+                let games = decode_runs(&fixture(version, encoding, "rsave")).unwrap();
+                let notes = runs[0].get_notes().map(|v| v.notes.as_str());
+                assert_eq!(notes, Some("Checked against the VOD").filter(|_| version >= 4));
+
+                let objective = match version {
+                    0..=2 => "Rundown1_Any%_2.rsave",
+                    _ => "AllE_2.rsave",
+                };
+                assert_eq!(games.len(), 1);
+                assert_eq!(games[0].get_objective().to_string(), objective);
+                assert_eq!(games[0].len(), 1);
+                assert_eq!(games[0].get_time(), time("00:03:00.000"));
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_round_trip() {
+        let mut runs = decode_runs(&fixture(0, "bincode", "save")).unwrap();
+        let metadata = RunMetadata::default()
+            .with_session_seed(1043970173)
+            .with_log_file("R1A1_host.maid_exp_comp.txt".to_owned())
+            .with_game_build(34855)
+            .with_inferred(vec![RunFlag::Secondary]);
+        runs[0].set_metadata(Some(metadata.clone()));
+        let notes = RunNotes {
+            notes: "Checked against the VOD".to_owned(),
+            tags: vec!["verified".to_owned()],
+            vod: Some(VodLink {
+                url: "https://youtu.be/rvlCpxyXw_k".to_owned(),
+                timestamp: Some(95),
+            }),
+        };
+        runs[0].set_notes(Some(notes.clone()));
+
+        for save_type in [SaveType::Binary, SaveType::Json] {
+            let data = encode_runs(&runs, save_type).unwrap();
+            let (envelope, _) = Envelope::read(&data).unwrap().unwrap();
+
+            assert_eq!(envelope.version, FORMAT_VERSION);
+            assert_eq!(envelope.encoding, save_type.into());
+            let decoded = decode_runs(&data).unwrap();
+            assert_eq!(decoded, runs);
+            assert_eq!(decoded[0].get_metadata(), Some(&metadata));
+            assert_eq!(decoded[0].get_notes(), Some(&notes));
+            assert_eq!(decoded[1].get_metadata(), None);
+        }
+    }
+
+    #[test]
+    pub fn test_newer_version_fails() {
+        let mut data = encode_runs(&[], SaveType::Binary).unwrap();
+        data[4] = 0xFF;
+
+        assert!(decode_runs(&data).is_err());
+    }
+}
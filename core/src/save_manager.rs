@@ -1,7 +1,7 @@
 use std::{
+    cell::OnceCell,
     collections::{HashMap, HashSet},
-    fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use directories::ProjectDirs;
@@ -9,12 +9,19 @@ use glr_core::{split::Split, time::Time};
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    file_backup::DEFAULT_BACKUP_COUNT,
     run::{
         merge_splits::{LevelsMergeSplits, MergeSplits},
+        objectives::{Objective, game_objective::GameObjective, run_objective::RunObjective},
         timed_run::RunEnum,
         traits::Run,
     },
     sort::Sortable,
+    storage::{
+        self, RunStorage, file_storage::FileStorage, sqlite_storage::SqliteStorage,
+    },
+    sum_of_best::SumOfBest,
+    trash::{Trash, TrashReason},
 };
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
@@ -22,6 +29,7 @@ use crate::{
 pub enum SaveType {
     #[default] Binary,
     Json,
+    Sqlite,
 }
 
 impl ToString for SaveType {
@@ -29,54 +37,184 @@ impl ToString for SaveType {
         match self {
             SaveType::Binary => "Binary".to_owned(),
             SaveType::Json => "Json".to_owned(),
+            SaveType::Sqlite => "Sqlite".to_owned(),
         }
     }
 }
 
+/// the golds of the runs of one objective.
+struct BestSplits {
+    sum_of_best: SumOfBest,
+
+    /// the gold of the first time each split is done, the same split the
+    /// tables show the time of.
+    best_splits: HashMap<String, Time>,
+    split_names: Vec<String>,
+}
+
+impl BestSplits {
+    fn new(runs: &[RunEnum], merge_splits: Option<&MergeSplits>) -> Self {
+        let sum_of_best = SumOfBest::new(runs, merge_splits);
+        let mut best_splits = HashMap::new();
+        let mut split_names = Vec::new();
+
+        for gold in sum_of_best.get_golds().iter().filter(|v| v.segment.occurrence == 1) {
+            split_names.push(gold.segment.name.clone());
+            if let Some(time) = gold.time {
+                best_splits.insert(gold.segment.name.clone(), time);
+            }
+        }
+
+        Self {
+            sum_of_best,
+            best_splits,
+            split_names,
+        }
+    }
+}
+
+/// the runs of one objective and what is derived from them.
+#[derive(Default)]
+struct ObjectiveRuns {
+    runs: Vec<RunEnum>,
+
+    /// worked out the first time it is needed after the runs change.
+    best_splits: OnceCell<BestSplits>,
+
+    /// the runs changed since they were loaded or saved.
+    dirty: bool,
+    /// the stored runs could not be read, they are never saved over.
+    load_error: Option<String>,
+}
+
+impl ObjectiveRuns {
+    fn get_best_splits(&self, merge_splits: Option<&MergeSplits>) -> &BestSplits {
+        self.best_splits
+            .get_or_init(|| BestSplits::new(&self.runs, merge_splits))
+    }
+
+    fn calculate_best_splits(&mut self) {
+        self.best_splits = OnceCell::new();
+    }
+}
+
+/// the notes and metadata of every copy of a run are kept on the one left.
+///
+/// the runs keep the order they were added in, so saving runs that did
+/// not change writes the same file again.
+fn remove_duplicates(runs: Vec<RunEnum>) -> Vec<RunEnum> {
+    let mut result: Vec<RunEnum> = Vec::with_capacity(runs.len());
+    let mut positions: HashMap<RunEnum, usize> = HashMap::with_capacity(runs.len());
+    for run in runs {
+        match positions.get(&run) {
+            Some(id) => result[*id].merge_duplicate(run),
+            None => {
+                positions.insert(run.clone(), result.len());
+                result.push(run);
+            }
+        }
+    }
+
+    result
+}
+
 /// Save manager struct
 ///
 /// This handles loading runs into memory and then ultimately saving them
+///
+/// the runs of an objective are only read from storage the first time
+/// they are needed and only the objectives that changed are saved.
 pub struct SaveManager {
-    loaded_runs: HashMap<String, Vec<RunEnum>>,
-
-    best_splits: HashMap<String, HashMap<String, Time>>,
-    split_names: HashMap<String, Vec<String>>,
+    loaded_runs: HashMap<String, OnceCell<ObjectiveRuns>>,
 
     split_merges: LevelsMergeSplits,
 
     automatic_saving: bool,
+    backup_count: usize,
+
+    save_type: SaveType,
+    storage: Option<Box<dyn RunStorage>>,
+
+    trash: Trash,
+
+    /// where the runs, merges and trash are kept.
+    directory: Option<PathBuf>,
 }
 
 impl Default for SaveManager {
+    /// uses the data folder of the app.
     fn default() -> Self {
-        let split_merges: LevelsMergeSplits = Self::get_directory()
-            .map(|v| v.join("merge_data.bin"))
-            .map(|path| {
-                std::fs::read(&path)
-                    .map(|data| bincode::deserialize(&data).ok())
-                    .ok()
-                    .flatten()
-            })
-            .flatten()
+        Self::from_directory(Self::get_directory())
+    }
+}
+
+impl SaveManager {
+    /// keeps everything inside the folder instead of the data folder of the app.
+    pub fn with_directory(directory: PathBuf) -> Self {
+        Self::from_directory(Some(directory))
+    }
+
+    fn from_directory(directory: Option<PathBuf>) -> Self {
+        let split_merges = directory
+            .as_deref()
+            .map(LevelsMergeSplits::load_from)
             .unwrap_or_default();
+        let trash = directory.as_deref().map(Trash::load).unwrap_or_default();
 
         Self {
             loaded_runs: Default::default(),
-            best_splits: Default::default(),
-            split_names: Default::default(),
             automatic_saving: false,
+            backup_count: DEFAULT_BACKUP_COUNT,
+            save_type: SaveType::default(),
+            storage: Self::open_storage(directory.as_deref(), SaveType::default()).ok(),
             split_merges,
+            trash,
+            directory,
         }
     }
-}
 
-impl SaveManager {
-    fn remove_duplicates(&mut self, objective: &String) {
-        if let Some(vec) = self.loaded_runs.remove(objective) {
-            let set: HashSet<RunEnum> = HashSet::from_iter(vec);
+    fn get_save_directory(&self) -> Result<&Path, String> {
+        self.directory
+            .as_deref()
+            .ok_or("No save directory found".to_owned())
+    }
 
-            self.loaded_runs
-                .insert(objective.clone(), set.into_iter().collect());
+    fn load_objective(storage: Option<&dyn RunStorage>, objective: &str) -> ObjectiveRuns {
+        let mut result = ObjectiveRuns::default();
+        match storage.map(|v| v.load(objective)) {
+            Some(Ok(runs)) => result.runs = remove_duplicates(runs),
+            Some(Err(e)) => {
+                eprintln!("Failed to load objective: {objective}: {e}");
+                result.load_error = Some(e);
+            }
+            None => {}
+        }
+
+        result
+    }
+
+    /// the runs of the objective, read from storage if they were not yet.
+    fn get_objective(&self, objective: &str) -> Option<&ObjectiveRuns> {
+        let cell = self.loaded_runs.get(objective)?;
+
+        Some(cell.get_or_init(|| {
+            Self::load_objective(self.storage.as_deref(), objective)
+        }))
+    }
+
+    fn get_objective_mut(&mut self, objective: &str) -> Option<&mut ObjectiveRuns> {
+        let cell = self.loaded_runs.get_mut(objective)?;
+        if cell.get().is_none() {
+            let loaded = Self::load_objective(self.storage.as_deref(), objective);
+            let _ = cell.set(loaded);
+        }
+
+        cell.get_mut()
+    }
+
+    fn remove_duplicates(&mut self, objective: &str) {
+        if let Some(loaded) = self.get_objective_mut(objective) {
+            loaded.runs = remove_duplicates(std::mem::take(&mut loaded.runs));
         }
     }
 
@@ -87,17 +225,11 @@ impl SaveManager {
 
         let objective = timed_run.get_objective().to_string();
 
-        match self.loaded_runs.get_mut(&objective) {
-            Some(vec) => {
-                vec.push(timed_run);
-            }
-            None => {
-                self.loaded_runs
-                    .insert(objective.to_string(), vec![timed_run]);
-            }
-        };
-
-        self.calculate_best_splits(&objective);
+        // the stored runs are loaded first so saving does not replace them
+        self.loaded_runs.entry(objective.clone()).or_default();
+        let loaded = self.get_objective_mut(&objective)?;
+        loaded.runs.push(timed_run);
+        loaded.dirty = true;
 
         return Some(objective);
     }
@@ -106,6 +238,72 @@ impl SaveManager {
         self.automatic_saving = automatic_saving;
     }
 
+    /// number of older generations kept for each save file.
+    pub fn set_backup_count(&mut self, backup_count: usize) {
+        self.backup_count = backup_count;
+        if let Some(storage) = &mut self.storage {
+            storage.set_backup_count(backup_count);
+        }
+    }
+
+    fn open_storage(
+        directory: Option<&Path>,
+        save_type: SaveType,
+    ) -> Result<Box<dyn RunStorage>, String> {
+        let directory = directory.ok_or("No save directory found".to_owned())?;
+
+        Ok(match save_type {
+            SaveType::Binary | SaveType::Json => {
+                Box::new(FileStorage::new(directory.to_path_buf(), save_type))
+            }
+            SaveType::Sqlite => {
+                std::fs::create_dir_all(directory).map_err(|e| e.to_string())?;
+                Box::new(SqliteStorage::open(&directory.join("runs.sqlite"))?)
+            }
+        })
+    }
+
+    /// switches where runs are loaded from and saved to.
+    ///
+    /// runs already in memory are kept, they are written to the new
+    /// storage on the next save.
+    pub fn set_save_type(&mut self, save_type: SaveType) -> Result<(), String> {
+        if self.save_type == save_type && self.storage.is_some() {
+            return Ok(());
+        }
+
+        let mut storage = Self::open_storage(self.directory.as_deref(), save_type)?;
+        storage.set_backup_count(self.backup_count);
+
+        // the runs in memory move to the new storage, the others belong to the old one
+        self.loaded_runs
+            .retain(|_, v| v.get().is_some_and(|v| v.load_error.is_none()));
+        for loaded in self.loaded_runs.values_mut().filter_map(|v| v.get_mut()) {
+            loaded.dirty = true;
+        }
+
+        self.storage = Some(storage);
+        self.save_type = save_type;
+
+        Ok(())
+    }
+
+    pub fn get_save_type(&self) -> SaveType {
+        self.save_type
+    }
+
+    fn get_storage(&self) -> Result<&dyn RunStorage, String> {
+        self.storage
+            .as_deref()
+            .ok_or("No save directory found".to_owned())
+    }
+
+    fn get_storage_mut(&mut self) -> Result<&mut Box<dyn RunStorage>, String> {
+        self.storage
+            .as_mut()
+            .ok_or("No save directory found".to_owned())
+    }
+
     pub fn get_split_merge(&self, objective: &String, split_name: &str) -> Option<&String> {
         self.split_merges
             .get_level(objective)
@@ -113,8 +311,14 @@ impl SaveManager {
             .flatten()
     }
 
-    pub fn get_split_names(&self, objective: &String) -> Option<&Vec<String>> {
-        self.split_names.get(objective)
+    fn get_objective_best_splits(&self, objective: &str) -> Option<&BestSplits> {
+        let loaded = self.get_objective(objective)?;
+
+        Some(loaded.get_best_splits(self.split_merges.get_level(objective)))
+    }
+
+    pub fn get_split_names(&self, objective: &str) -> Option<&Vec<String>> {
+        self.get_objective_best_splits(objective).map(|v| &v.split_names)
     }
 
     pub fn get_directory() -> Option<PathBuf> {
@@ -151,73 +355,15 @@ impl SaveManager {
     pub fn save(&mut self, timed_run: RunEnum) {
         if let Some(name) = self.save_no_remove_duplicates(timed_run) {
             self.remove_duplicates(&name);
+            self.calculate_best_splits(&name);
         }
     }
 
+    /// the best splits are worked out again the next time they are needed.
     pub fn calculate_best_splits(&mut self, objective_id: &String) {
-        let empty = Vec::new();
-        let runs = self.loaded_runs.get(objective_id).unwrap_or(&empty);
-        let merge_splits = self.split_merges.get_level(objective_id);
-
-        let mut build_vec: Vec<String> = Vec::new();
-        let mut build_hash: HashMap<String, (Time, u32)> = HashMap::new();
-        let mut set: HashSet<String> = HashSet::new();
-
-        for run in runs {
-            let mut times = HashMap::new();
-
-            for split in run.get_splits() {
-                let name = split.get_name();
-
-                let name = merge_splits
-                    .map(|ms| ms.get_split(name))
-                    .flatten()
-                    .map_or(name, |v| v);
-
-                if name != "LOSS" && name != "STOP" && !set.contains(name) {
-                    //println!("SET: {:?}", set);
-                    build_vec.push(name.to_owned());
-                    set.insert(name.to_owned());
-                }
-
-                match times.get_mut(name) {
-                    Some((time, count)) => {
-                        *time += split.get_time();
-                        *count += 1;
-                    }
-                    None => {
-                        times.insert(name, (split.get_time(), 1));
-                    }
-                };
-            }
-
-            for (name, (time, count)) in times {
-                match build_hash.get(name) {
-                    Some((b_time, b_count)) => {
-                        if count < *b_count {
-                            continue;
-                        }
-
-                        if time < *b_time || count > *b_count {
-                            build_hash.insert(name.to_owned(), (time, count));
-                        }
-                    }
-                    None => {
-                        build_hash.insert(name.to_owned(), (time, count));
-                    }
-                }
-            }
+        if let Some(loaded) = self.loaded_runs.get_mut(objective_id).and_then(|v| v.get_mut()) {
+            loaded.calculate_best_splits();
         }
-
-        let mut bh = HashMap::new();
-        for (key, (value, _)) in build_hash {
-            bh.insert(key, value);
-        }
-
-        // println!("Inserted: {:?}", build_vec);
-        // println!("Hashed: {:?}", build_hash);
-        self.split_names.insert(objective_id.clone(), build_vec);
-        self.best_splits.insert(objective_id.clone(), bh);
     }
 
     /// save multiple runs into the RAM memory.
@@ -242,13 +388,13 @@ impl SaveManager {
     }
 
     // returns the world record run for a level
-    pub fn get_best_run(&self, objective_data: &String) -> Option<&RunEnum> {
-        match self.loaded_runs.get(objective_data) {
-            Some(runs) => {
+    pub fn get_best_run(&self, objective_data: &str) -> Option<&RunEnum> {
+        match self.get_objective(objective_data) {
+            Some(loaded) => {
                 let mut best_run = None;
                 let mut best_time = Time::max();
 
-                for timed_run in runs {
+                for timed_run in &loaded.runs {
                     if timed_run.get_time() < best_time && timed_run.is_win() {
                         best_run = Some(timed_run);
                         best_time = timed_run.get_time();
@@ -261,20 +407,71 @@ impl SaveManager {
         }
     }
 
-    pub fn get_runs(&self, objective_data: &String) -> Option<&Vec<RunEnum>> {
-        self.loaded_runs.get(objective_data)
+    /// the best time of every segment of the loaded runs and the run it comes from.
+    pub fn get_sum_of_best(&self, objective: &str) -> Option<&SumOfBest> {
+        self.get_objective_best_splits(objective).map(|v| &v.sum_of_best)
     }
 
-    /// returns all runs for the objective.
-    pub fn get_runs_mut(&mut self, objective_data: &String) -> Option<&mut Vec<RunEnum>> {
-        self.loaded_runs.get_mut(objective_data)
+    /// sum of the best loaded level times for every level of the game objective.
+    ///
+    /// returns none while a level has no finished run.
+    ///
+    /// only the objectives of those levels are loaded.
+    pub fn get_theoretical_best_game(&self, objective: &GameObjective) -> Option<Time> {
+        objective.get_levels().iter().try_fold(Time::new(), |total, level| {
+            self.loaded_runs
+                .keys()
+                .filter(|key| {
+                    RunObjective::try_from(key.as_str()).is_ok_and(|v| {
+                        v.get_player_count() == level.get_player_count()
+                            && v.level_name.eq_ignore_ascii_case(&level.level_name)
+                    })
+                })
+                .filter_map(|key| self.get_objective(key))
+                .flat_map(|v| &v.runs)
+                .filter_map(|run| match run {
+                    RunEnum::Level(run) => Some(run),
+                    RunEnum::Game(_) => None,
+                })
+                .filter(|run| run.get_objective().get_player_count() == level.get_player_count())
+                .filter(|run| GameObjective::completes_level(level, run))
+                .map(|run| run.get_time())
+                .min()
+                .map(|best| total + best)
+        })
     }
 
-    pub fn get_best_split(&self, objective: &String, name: &str) -> Option<&Time> {
-        self.best_splits
-            .get(objective)
-            .map(|h| h.get(name))
-            .flatten()
+    pub fn get_runs(&self, objective_data: &str) -> Option<&Vec<RunEnum>> {
+        self.get_objective(objective_data).map(|v| &v.runs)
+    }
+
+    /// changes the runs of the objective.
+    ///
+    /// the objective is saved again on the next save and its best splits are worked out again,
+    /// so only call it when the runs really change.
+    pub fn edit_runs<T>(
+        &mut self,
+        objective_data: &str,
+        edit: impl FnOnce(&mut Vec<RunEnum>) -> T,
+    ) -> Option<T> {
+        let loaded = self.get_objective_mut(objective_data)?;
+        let result = edit(&mut loaded.runs);
+        loaded.dirty = true;
+        loaded.calculate_best_splits();
+
+        Some(result)
+    }
+
+    fn get_runs_mut(&mut self, objective_data: &str) -> Option<&mut Vec<RunEnum>> {
+        let loaded = self.get_objective_mut(objective_data)?;
+        loaded.dirty = true;
+        loaded.calculate_best_splits();
+
+        Some(&mut loaded.runs)
+    }
+
+    pub fn get_best_split(&self, objective: &str, name: &str) -> Option<&Time> {
+        self.get_objective_best_splits(objective)?.best_splits.get(name)
     }
 
     pub fn get_best_split_with_merge(&self, objective: &String, name: &str) -> Option<&Time> {
@@ -289,170 +486,307 @@ impl SaveManager {
         self.get_best_split(objective, new_name)
     }
 
+    /// the time of a split name of the merge splits, the sum of every split
+    /// merged into it.
+    ///
+    /// returns none if the run is missing one of those splits.
+    pub fn get_merged_split_time<R: Run>(
+        &self,
+        objective: &String,
+        run: &R,
+        split_name: &str,
+    ) -> Option<Time> {
+        match self.get_splits_req(objective, split_name) {
+            Some(splits) => {
+                let mut result = Time::default();
+                for split in splits {
+                    result += run.get_time_for_split(split)?;
+                }
+
+                Some(result)
+            }
+            None => run.get_time_for_split(split_name),
+        }
+    }
+
     /// returns all best splits for the objective.
-    pub fn get_best_splits(&self, objective_data: &String) -> Option<&HashMap<String, Time>> {
-        self.best_splits.get(objective_data)
+    pub fn get_best_splits(&self, objective_data: &str) -> Option<&HashMap<String, Time>> {
+        self.get_objective_best_splits(objective_data).map(|v| &v.best_splits)
     }
 
-    /// load all runs that were saved to folder
+    /// load all runs that were saved
+    ///
+    /// objectives not yet in memory are only listed, their runs are read
+    /// the first time they are needed.
     pub fn load_all_runs(&mut self) {
-        let file_path = Self::get_directory();
-        let paths = match file_path {
-            Some(file_path) => {
-                if !file_path.exists() {
-                    let _ = fs::create_dir_all(&file_path);
-                }
-
-                Some(fs::read_dir(file_path).unwrap())
+        let objectives = match self.get_storage().and_then(|v| v.get_objectives()) {
+            Ok(objectives) => objectives,
+            Err(e) => {
+                eprintln!("Failed to list objectives: {e}");
+                return;
             }
-            None => None,
         };
 
-        if let Some(paths) = paths {
-            for path in paths {
-                if let Ok(entry) = path {
-                    if entry.file_name().into_string().unwrap().contains(".save") ||
-                        entry.file_name().into_string().unwrap().contains(".rsave") {
-                        
-                        self.load_advanced(&entry.file_name().into_string().unwrap());
-                    }
+        for objective in objectives {
+            match self.loaded_runs.get(&objective).is_some_and(|v| v.get().is_some()) {
+                true => self.load_advanced(&objective),
+                false => {
+                    self.loaded_runs.entry(objective).or_default();
                 }
             }
         }
     }
-    
+
+    /// loads the objective from storage, whatever save format version it
+    /// was written with.
+    ///
+    /// the stored runs are added to the ones already in memory.
     pub fn load_advanced(&mut self, objective: &String) {
-        if let Ok(_) = self.load(
-            objective, 
-            |e| bincode::deserialize(e).map_err(|e| e.to_string())
-        ) {
-            return;
-        }
-        
-        if let Ok(_) = self.load(
-            objective,
-            |e| serde_json::from_slice(e).map_err(|e| e.to_string())
-        ) {
+        let Some(loaded) = self.loaded_runs.get_mut(objective).and_then(|v| v.get_mut()) else {
+            self.loaded_runs.insert(objective.clone(), OnceCell::new());
+            if self
+                .get_objective(objective)
+                .is_some_and(|v| v.runs.is_empty() && v.load_error.is_none())
+            {
+                self.loaded_runs.remove(objective);
+            }
+
             return;
+        };
+
+        match self.storage.as_deref().map(|v| v.load(objective)) {
+            Some(Ok(runs)) => {
+                loaded.runs.extend(runs);
+                loaded.runs = remove_duplicates(std::mem::take(&mut loaded.runs));
+                loaded.load_error = None;
+                loaded.calculate_best_splits();
+            }
+            Some(Err(e)) => eprintln!("Failed to load objective: {objective}: {e}"),
+            None => eprintln!("Failed to load objective: {objective}: No save directory found"),
         }
-        
-        eprintln!("Failed to load objective: {objective}");
     }
 
     /// optimize these runs by removing all that do not hold
     /// important information
     ///
-    /// if the run is not world record or has a best split it is removed
-    pub fn optimize_obj(&mut self, objective_data: &String) {
+    /// if the run is not world record or does not hold the gold of a
+    /// segment it is removed
+    ///
+    /// returns the removed runs with their index, in the order they were removed.
+    pub fn optimize_obj(&mut self, objective_data: &String) -> Vec<(usize, RunEnum)> {
         let best_time = match self.get_best_run(objective_data) {
             Some(run) => Some(run.get_time()),
             None => None,
         };
         let mut for_deletions = Vec::new();
 
-        if let Some(runs) = self.loaded_runs.get(objective_data) {
-            for (r_id, run) in runs.into_iter().enumerate() {
-                let mut is_valid = false;
+        if let Some(loaded) = self.get_objective(objective_data) {
+            let gold_runs: HashSet<usize> = loaded
+                .get_best_splits(self.split_merges.get_level(objective_data))
+                .sum_of_best
+                .get_golds()
+                .iter()
+                .filter_map(|v| v.run)
+                .collect();
+
+            for (r_id, run) in loaded.runs.iter().enumerate() {
                 if best_time.is_some_and(|t| run.get_time() == t) {
                     continue;
                 }
 
-                for split in run.get_splits() {
-                    if self
-                        .get_best_split(objective_data, split.get_name())
-                        .is_some_and(|t| split.get_time() == *t)
-                    {
-                        is_valid = true;
-                        break;
-                    }
-                }
-
-                if !is_valid {
+                if !gold_runs.contains(&r_id) {
                     for_deletions.push(r_id);
                 }
             }
         }
 
-        let runs = self.get_runs_mut(objective_data).unwrap();
-        for elem in for_deletions.iter().rev() {
-            runs.remove(*elem);
+        self.move_to_trash(objective_data, for_deletions, TrashReason::Optimized)
+    }
+
+    /// removes the runs at the indexes and keeps them in the trash.
+    ///
+    /// returns the removed runs with their index, in the order they were removed.
+    pub fn move_to_trash(
+        &mut self,
+        objective_data: &String,
+        mut ids: Vec<usize>,
+        reason: TrashReason,
+    ) -> Vec<(usize, RunEnum)> {
+        let Some(loaded) = self.get_objective_mut(objective_data) else {
+            return Vec::new();
+        };
+
+        ids.sort_unstable();
+        ids.dedup();
+        ids.retain(|id| *id < loaded.runs.len());
+        let removed: Vec<(usize, RunEnum)> = ids
+            .into_iter()
+            .rev()
+            .map(|id| (id, loaded.runs.remove(id)))
+            .collect();
+        loaded.dirty |= !removed.is_empty();
+
+        for (_, run) in &removed {
+            self.trash.add(objective_data.clone(), run.clone(), reason);
         }
+        self.calculate_best_splits(objective_data);
+
+        removed
     }
 
-    /// load from file the objective data.
-    pub fn load<F>(&mut self, objective_data: &String, func: F) -> Result<(), String>
-    where
-        F: Fn(&[u8]) -> Result<Vec<RunEnum>, String>, {
-        
-        let file_path = Self::get_directory().map(|path| path.join(objective_data.clone()));
+    pub fn get_trash(&self) -> &Trash {
+        &self.trash
+    }
 
-        if let Some(file_path) = file_path {
-            return match std::fs::read(file_path) {
-                Ok(binary_data) => {
-                    let mut vec: Vec<RunEnum> = match func(&binary_data) {
-                        Ok(vec) => vec,
-                        Err(e) => {
-                            return Err(e)
-                        },
-                    };
+    pub fn get_trash_mut(&mut self) -> &mut Trash {
+        &mut self.trash
+    }
 
-                    for it in &mut vec {
-                        it.set_objective_str(objective_data);
-                    }
+    /// puts the trashed run back with the other runs of its objective.
+    pub fn restore_from_trash(&mut self, id: usize) -> Result<String, String> {
+        let objective = match self.trash.get_runs().get(id) {
+            Some(trashed) => trashed.objective.clone(),
+            None => return Err("The run is no longer in the trash".to_owned()),
+        };
 
-                    //println!("Added vec with size for obj: {}, {}, {}", vec.len(), binary_data.len(), objective_data);
-                    self.save_multiple(vec);
-                    
-                    Ok(())
-                }
-                Err(e) => {
-                    Err(e.to_string())
-                }
-            };
+        // the run stays in the trash while its objective can not be saved
+        self.loaded_runs.entry(objective.clone()).or_default();
+        if let Some(e) = self.get_objective_mut(&objective).and_then(|v| v.load_error.as_ref()) {
+            return Err(format!("The stored runs could not be read, the run is left in the trash: {e}"));
         }
-        
-        return Err("File not found".to_owned())
+
+        let trashed = self.trash.take(id).ok_or("The run is no longer in the trash")?;
+        let loaded = self.get_objective_mut(&objective).ok_or("The objective could not be loaded")?;
+        loaded.runs.push(trashed.run);
+        loaded.dirty = true;
+        self.remove_duplicates(&objective);
+        self.calculate_best_splits(&objective);
+
+        Ok(objective)
     }
 
-    pub fn save_to_file(&self, save_type: SaveType, objective_data: &String) {
-        let file_path = Self::get_directory();
+    /// runs trashed more than `days` days ago are removed for good, 0 keeps them.
+    pub fn set_trash_max_age(&mut self, days: u32) {
+        if days == 0 {
+            return;
+        }
 
-        if let Some(file_path) = file_path.as_ref() {
-            if !file_path.exists() {
-                let _ = std::fs::create_dir(&file_path);
-            }
+        self.trash
+            .purge_older_than(days, chrono::Local::now().naive_local());
+    }
+
+    fn save_objective(
+        storage: &mut dyn RunStorage,
+        objective: &str,
+        loaded: &mut ObjectiveRuns,
+    ) -> Result<(), String> {
+        if let Some(e) = &loaded.load_error {
+            return Err(format!("The stored runs could not be read, not saving over them: {e}"));
         }
 
-        let file_path =
-            file_path.map(|path| path.join(Into::<String>::into(objective_data.to_string())));
+        storage.save(objective, &loaded.runs)?;
+        loaded.dirty = false;
 
-        let empty = Vec::new();
-        let to_save_vec = self.loaded_runs.get(objective_data).unwrap_or(&empty);
-        if let Ok(bin_data) =
-            match save_type {
-                SaveType::Binary => bincode::serialize(to_save_vec).map_err(|_| {}),
-                SaveType::Json => serde_json::to_string(to_save_vec).map(|v| v.into_bytes()).map_err(|_| {}),
+        Ok(())
+    }
+
+    pub fn save_to_file(&mut self, objective_data: &String) -> Result<(), String> {
+        self.loaded_runs.entry(objective_data.clone()).or_default();
+        self.get_objective_mut(objective_data);
+
+        let storage = self
+            .storage
+            .as_deref_mut()
+            .ok_or("No save directory found".to_owned())?;
+        if let Some(loaded) = self.loaded_runs.get_mut(objective_data).and_then(|v| v.get_mut()) {
+            Self::save_objective(storage, objective_data, loaded)?;
+        }
+
+        self.save_trash()
+    }
+
+    /// the trash is saved with the runs, a run is never only in one of them.
+    fn save_trash(&mut self) -> Result<(), String> {
+        match (&self.directory, self.trash.is_changed()) {
+            (Some(directory), true) => self.trash.save(directory),
+            _ => Ok(()),
+        }
+    }
+
+    /// save all loaded runs that changed to storage
+    ///
+    /// a failed objective does not stop the others from being saved,
+    /// all errors are returned together.
+    pub fn save_to_files(&mut self) -> Result<(), String> {
+        let storage = match self.storage.as_deref_mut() {
+            Some(storage) => storage,
+            None => return Err("No save directory found".to_owned()),
+        };
+        let mut errors = Vec::new();
+
+        for (key, cell) in &mut self.loaded_runs {
+            let Some(loaded) = cell.get_mut().filter(|v| v.dirty) else {
+                continue;
+            };
+
+            if let Err(e) = Self::save_objective(storage, key, loaded) {
+                errors.push(format!("{key}: {e}"));
             }
+        }
+
+        if errors.is_empty()
+            && let Err(e) = self.save_trash()
         {
-            if let Some(file_path) = file_path {
-                let _ = std::fs::write(file_path, &bin_data);
-            }
+            errors.push(format!("trash: {e}"));
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors.join("\n")),
         }
     }
 
-    /// save all loaded runs to files
-    pub fn save_to_files(&self) {
-        for (key, vec) in &self.loaded_runs {
-            let file_path =
-                Self::get_directory().map(|path| path.join(Into::<String>::into(key.to_string())));
+    /// copies every `.save`/`.rsave` file into the current storage.
+    ///
+    /// returns the number of runs that were not already stored.
+    pub fn import_save_files(&mut self) -> Result<usize, String> {
+        if self.save_type != SaveType::Sqlite {
+            return Err("Runs are already stored in save files".to_owned());
+        }
+
+        let files = FileStorage::new(self.get_save_directory()?.to_path_buf(), SaveType::Binary);
 
-            if let Ok(bin_data) = serde_json::to_string(&vec) {
-                //println!("Saved vec with size: {}: {}", vec.len(), bin_data.len());
-                if let Some(file_path) = file_path {
-                    let _ = std::fs::write(file_path, &bin_data);
-                }
-            }
+        storage::copy_all(&files, self.get_storage_mut()?.as_mut())
+    }
+
+    /// writes every objective of the current storage into save files.
+    pub fn export_save_files(&self, save_type: SaveType) -> Result<usize, String> {
+        if save_type == SaveType::Sqlite {
+            return Err("Sqlite is not a save file type".to_owned());
         }
+
+        let mut files = FileStorage::new(self.get_save_directory()?.to_path_buf(), save_type);
+        files.set_backup_count(self.backup_count);
+
+        storage::copy_all(self.get_storage()?, &mut files)
+    }
+
+    /// returns all backup generations for the objective, newest first.
+    pub fn get_backups(&self, objective_data: &String) -> Vec<(usize, PathBuf)> {
+        self.get_storage()
+            .map(|v| v.get_backups(objective_data))
+            .unwrap_or_default()
+    }
+
+    /// replaces the stored runs with the backup generation and reloads
+    /// the runs of that objective from it.
+    pub fn restore_backup(&mut self, objective_data: &str, generation: usize) -> Result<(), String> {
+        self.get_storage_mut()?.restore_backup(objective_data, generation)?;
+
+        // read again from the restored runs the next time they are needed
+        self.loaded_runs.insert(objective_data.to_owned(), OnceCell::new());
+
+        Ok(())
     }
 
     pub fn get_all_objectives(&self) -> Vec<String> {
@@ -461,12 +795,30 @@ impl SaveManager {
         v
     }
 
-    pub fn set_merge_splits(&mut self, objective: &String, data: &str) {
-        let merged: MergeSplits = data.into();
-
-        self.split_merges.add_level(objective, merged);
+    /// replaces the merges of the objective and saves every merge right away.
+    pub fn set_merge_splits(&mut self, objective: &String, merge_splits: MergeSplits) -> Result<(), String> {
+        self.split_merges.add_level(objective, merge_splits);
 
         self.calculate_best_splits(objective);
+
+        let directory = self.directory.as_deref().ok_or("No save directory found".to_owned())?;
+        self.split_merges.save_to(directory)
+    }
+
+    /// every split name the loaded runs of the objective have, before merging.
+    pub fn get_run_split_names(&self, objective: &str) -> HashSet<String> {
+        self.get_objective(objective)
+            .into_iter()
+            .flat_map(|v| &v.runs)
+            .flat_map(|run| run.get_splits().map(|v| v.get_name().to_owned()).collect::<Vec<_>>())
+            .collect()
+    }
+
+    pub fn get_split_display_name<'a>(&'a self, objective: &str, split_name: &'a str) -> &'a str {
+        self.split_merges
+            .get_level(objective)
+            .map(|v| v.get_display_name(split_name))
+            .unwrap_or(split_name)
     }
 
     pub fn get_splits_req(&self, objective: &String, split_name: &str) -> Option<&Vec<String>> {
@@ -479,10 +831,6 @@ impl SaveManager {
             )
     }
 
-    pub fn get_level_merge_split_str(&self, objective: &String) -> Option<String> {
-        self.split_merges.get_level(objective).map(|v| v.into())
-    }
-
     pub fn get_level_merge(&self, objective: &str) -> Option<&MergeSplits> {
         self.split_merges.get_level(objective)
     }
@@ -490,20 +838,136 @@ impl SaveManager {
 
 impl Sortable<RunEnum> for SaveManager {
     fn get_vec(&mut self, objective: &String) -> Option<&mut Vec<RunEnum>> {
-        self.loaded_runs.get_mut(objective)
+        self.get_runs_mut(objective)
     }
 }
 
 impl Drop for SaveManager {
-    // save the merge splits automatically
+    // the trash is saved with the runs, merge splits are saved when changed
     fn drop(&mut self) {
-        if let Some(file_path) = Self::get_directory().map(|v| v.join("merge_data.bin")) {
-            if let Ok(bin) = bincode::serialize(&self.split_merges) {
-                let _ = std::fs::write(file_path, bin);
-            }
-        }
         if self.automatic_saving {
-            self.save_to_files();
+            if let Err(e) = self.save_to_files() {
+                eprintln!("Failed to save runs: {e}");
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::{OBJECTIVE, run, time},
+        trash::TrashReason,
+    };
+
+    use super::SaveManager;
+
+    #[test]
+    pub fn test_lazy_loading() {
+        let dir = tempfile::tempdir().unwrap();
+        let objective = OBJECTIVE.to_owned();
+        let first = run(&[("door", "00:01:00.000"), ("WIN", "00:02:00.000")], true);
+        let second = run(&[("door", "00:00:50.000"), ("WIN", "00:02:10.000")], true);
+
+        let mut save_manager = SaveManager::with_directory(dir.path().to_path_buf());
+        save_manager.save(first.clone());
+        save_manager.save_to_files().unwrap();
+        drop(save_manager);
+
+        let mut save_manager = SaveManager::with_directory(dir.path().to_path_buf());
+        save_manager.load_all_runs();
+        assert_eq!(save_manager.get_all_objectives(), vec![objective.clone()]);
+        assert!(save_manager.loaded_runs[&objective].get().is_none());
+
+        assert_eq!(save_manager.get_runs(&objective), Some(&vec![first.clone()]));
+        assert!(!save_manager.loaded_runs[&objective].get().unwrap().dirty);
+
+        save_manager.save(second.clone());
+        assert!(save_manager.loaded_runs[&objective].get().unwrap().dirty);
+        save_manager.save_to_files().unwrap();
+        assert!(!save_manager.loaded_runs[&objective].get().unwrap().dirty);
+        drop(save_manager);
+
+        let save_manager = SaveManager::with_directory(dir.path().to_path_buf());
+        assert_eq!(save_manager.get_runs(&objective), None);
+    }
+
+    #[test]
+    pub fn test_unreadable_objective_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(OBJECTIVE);
+        std::fs::write(&path, b"not a save file").unwrap();
+
+        let mut save_manager = SaveManager::with_directory(dir.path().to_path_buf());
+        save_manager.save(run(&[("WIN", "00:02:00.000")], true));
+
+        assert!(save_manager.save_to_files().is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"not a save file");
+
+        // a run is not restored into runs that can not be saved
+        let trashed = run(&[("WIN", "00:02:10.000")], true);
+        save_manager
+            .get_trash_mut()
+            .add(OBJECTIVE.to_owned(), trashed, TrashReason::Deleted);
+        assert!(save_manager.restore_from_trash(0).is_err());
+        assert_eq!(save_manager.get_trash().len(), 1);
+    }
+
+    #[test]
+    pub fn test_best_splits_from_sum_of_best() {
+        let dir = tempfile::tempdir().unwrap();
+        let objective = OBJECTIVE.to_owned();
+        let mut save_manager = SaveManager::with_directory(dir.path().to_path_buf());
+        save_manager.save_multiple(vec![
+            run(&[("door", "00:01:00.000"), ("WIN", "00:02:00.000")], true),
+            // doing the door again does not make its first time the gold
+            run(&[("door", "00:01:10.000"), ("door", "00:00:05.000"), ("WIN", "00:01:50.000")], true),
+            run(&[("door", "00:00:50.000"), ("LOSS", "00:00:10.000")], false),
+            run(&[("door", "00:01:20.000"), ("WIN", "00:02:30.000")], true),
+        ]);
+
+        assert_eq!(save_manager.get_best_split(&objective, "door"), Some(&time("00:00:50.000")));
+        assert_eq!(save_manager.get_best_split(&objective, "WIN"), Some(&time("00:01:50.000")));
+        assert_eq!(save_manager.get_split_names(&objective).unwrap(), &vec!["door", "WIN"]);
+        assert_eq!(
+            save_manager.get_sum_of_best(&objective).unwrap().get_total(),
+            time("00:02:45.000")
+        );
+
+        // only the run without any gold is removed
+        let removed = save_manager.optimize_obj(&objective);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].0, 3);
+
+        // the golds follow the runs left
+        save_manager.edit_runs(&objective, |runs| runs.remove(2));
+        assert_eq!(save_manager.get_best_split(&objective, "door"), Some(&time("00:01:00.000")));
+    }
+
+    #[test]
+    pub fn test_restore_from_trash() {
+        let dir = tempfile::tempdir().unwrap();
+        let objective = OBJECTIVE.to_owned();
+        let mut save_manager = SaveManager::with_directory(dir.path().to_path_buf());
+        save_manager.save_multiple(vec![
+            run(&[("door", "00:01:00.000"), ("WIN", "00:02:00.000")], true),
+            run(&[("door", "00:00:50.000"), ("WIN", "00:02:10.000")], true),
+        ]);
+
+        save_manager.move_to_trash(&objective, vec![1], TrashReason::Deleted);
+        assert_eq!(save_manager.get_best_split(&objective, "door"), Some(&time("00:01:00.000")));
+
+        assert_eq!(save_manager.restore_from_trash(0), Ok(objective.clone()));
+        assert_eq!(save_manager.get_runs(&objective).unwrap().len(), 2);
+        assert_eq!(save_manager.get_best_split(&objective, "door"), Some(&time("00:00:50.000")));
+        assert!(save_manager.get_trash().is_empty());
+
+        // the trash is only saved together with the runs
+        save_manager.save_to_files().unwrap();
+        drop(save_manager);
+        let mut save_manager = SaveManager::with_directory(dir.path().to_path_buf());
+        save_manager.load_all_runs();
+        assert!(save_manager.get_trash().is_empty());
+        assert_eq!(save_manager.get_runs(&objective).unwrap().len(), 2);
+    }
+}
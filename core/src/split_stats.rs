@@ -0,0 +1,266 @@
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::{comparison::format_millis, export::Export, save_manager::SaveManager};
+
+/// percentiles computed for every split.
+pub const PERCENTILES: [u8; 4] = [10, 25, 75, 90];
+
+/// the value under which `percent` percent of the sorted times are,
+/// interpolated between the two closest times.
+fn percentile(sorted: &[u64], percent: f64) -> u64 {
+    let Some(last) = sorted.len().checked_sub(1) else {
+        return 0;
+    };
+
+    let rank = percent.clamp(0.0, 100.0) / 100.0 * last as f64;
+    let (low, high) = (sorted[rank.floor() as usize], sorted[rank.ceil() as usize]);
+
+    (low as f64 + (high as f64 - low as f64) * rank.fract()).round() as u64
+}
+
+/// how the times of a split are spread, every time is in milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SplitTimes {
+    pub best: u64,
+    pub mean: u64,
+    pub median: u64,
+    pub std_dev: u64,
+    /// the time for every percent of [PERCENTILES].
+    pub percentiles: Vec<(u8, u64)>,
+    /// the median minus the gold, what can usually be saved on the split.
+    pub time_save: u64,
+}
+
+impl SplitTimes {
+    /// returns none if there are no times.
+    pub fn new(mut times: Vec<u64>, gold: Option<u64>) -> Option<Self> {
+        if times.is_empty() {
+            return None;
+        }
+        times.sort_unstable();
+
+        let count = times.len() as f64;
+        let mean = times.iter().sum::<u64>() as f64 / count;
+        let variance = times
+            .iter()
+            .map(|v| (*v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        let median = percentile(&times, 50.0);
+
+        Some(Self {
+            best: times[0],
+            mean: mean.round() as u64,
+            median,
+            std_dev: variance.sqrt().round() as u64,
+            percentiles: PERCENTILES
+                .iter()
+                .map(|v| (*v, percentile(&times, *v as f64)))
+                .collect(),
+            time_save: median.saturating_sub(gold.unwrap_or(times[0])),
+        })
+    }
+
+    /// the standard deviation as a percent of the mean, lower is more consistent.
+    pub fn get_variation(&self) -> f64 {
+        match self.mean {
+            0 => 0.0,
+            mean => self.std_dev as f64 / mean as f64 * 100.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SplitStats {
+    pub name: String,
+    /// runs that got to the start of the split.
+    pub attempts: usize,
+    /// runs that have a time for the split.
+    pub completions: usize,
+    pub times: Option<SplitTimes>,
+}
+
+impl SplitStats {
+    /// the percent of attempts that completed the split.
+    pub fn get_completion_rate(&self) -> f64 {
+        match self.attempts {
+            0 => 0.0,
+            attempts => self.completions as f64 / attempts as f64 * 100.0,
+        }
+    }
+}
+
+/// statistics of every merged split of an objective.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ObjectiveStats {
+    pub objective: String,
+    pub runs: usize,
+    pub splits: Vec<SplitStats>,
+}
+
+impl ObjectiveStats {
+    /// `runs` has the time of every split in milliseconds for each run,
+    /// in the order of `split_names`.
+    ///
+    /// a run attempts a split once it got as far as the split before it,
+    /// so a reset counts against the split that was being done.
+    pub fn new(
+        objective: String,
+        split_names: &[String],
+        runs: &[Vec<Option<u64>>],
+        golds: &[Option<u64>],
+    ) -> Self {
+        let reached: Vec<Option<usize>> = runs
+            .iter()
+            .map(|run| run.iter().rposition(|v| v.is_some()))
+            .collect();
+
+        let splits = split_names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| {
+                let times: Vec<u64> = runs
+                    .iter()
+                    .filter_map(|run| run.get(id).cloned().flatten())
+                    .collect();
+                let attempts = match id {
+                    0 => runs.len(),
+                    _ => reached.iter().filter(|v| v.is_some_and(|v| v + 1 >= id)).count(),
+                };
+
+                SplitStats {
+                    name: name.clone(),
+                    attempts: attempts.max(times.len()),
+                    completions: times.len(),
+                    times: SplitTimes::new(times, golds.get(id).cloned().flatten()),
+                }
+            })
+            .collect();
+
+        Self {
+            objective,
+            runs: runs.len(),
+            splits,
+        }
+    }
+
+    /// statistics of the loaded runs of the objective with its merged splits.
+    pub fn from_save_manager(save_manager: &SaveManager, objective: &String) -> Option<Self> {
+        let split_names = save_manager.get_split_names(objective)?;
+        let runs: Vec<Vec<Option<u64>>> = save_manager
+            .get_runs(objective)?
+            .iter()
+            .map(|run| {
+                split_names
+                    .iter()
+                    .map(|name| {
+                        save_manager
+                            .get_merged_split_time(objective, run, name)
+                            .map(|v| v.get_stamp())
+                    })
+                    .collect()
+            })
+            .collect();
+        let golds: Vec<Option<u64>> = split_names
+            .iter()
+            .map(|name| save_manager.get_best_split(objective, name).map(|v| v.get_stamp()))
+            .collect();
+
+        Some(Self::new(objective.clone(), split_names, &runs, &golds))
+    }
+
+    /// sum of the time that can usually be saved on every split.
+    pub fn get_total_time_save(&self) -> u64 {
+        self.splits
+            .iter()
+            .filter_map(|v| v.times.as_ref())
+            .map(|v| v.time_save)
+            .sum()
+    }
+
+    pub fn export_csv(&self, writer: impl Write) -> Result<(), String> {
+        let mut writer = std::io::BufWriter::new(writer);
+        let mut write = |text: String| writer.write_all(text.as_bytes()).map_err(|e| e.to_string());
+
+        let percentiles: Vec<String> = PERCENTILES.iter().map(|v| format!("P{v}")).collect();
+        write(format!(
+            "Split,Attempts,Completions,Best,Mean,Median,StdDev,{},TimeSave\n",
+            percentiles.join(",")
+        ))?;
+
+        for split in &self.splits {
+            let times = match &split.times {
+                Some(times) => {
+                    let mut columns = vec![times.best, times.mean, times.median, times.std_dev];
+                    columns.extend(times.percentiles.iter().map(|(_, v)| *v));
+                    columns.push(times.time_save);
+
+                    columns.into_iter().map(format_millis).collect::<Vec<_>>().join(",")
+                }
+                None => ",".repeat(PERCENTILES.len() + 4),
+            };
+
+            write(format!(
+                "{},{},{},{times}\n",
+                Export::escape_csv(&split.name),
+                split.attempts,
+                split.completions,
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn export_json(&self, writer: impl Write) -> Result<(), String> {
+        serde_json::to_writer_pretty(writer, self).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ObjectiveStats, SplitTimes, percentile};
+
+    #[test]
+    pub fn test_split_times() {
+        assert_eq!(percentile(&[10, 20, 30, 40], 50.0), 25);
+        assert_eq!(percentile(&[10, 20, 30, 40], 100.0), 40);
+        assert_eq!(percentile(&[], 50.0), 0);
+
+        let times = vec![4000, 2000, 6000, 4000, 5000, 7000, 4000, 9000];
+        let times = SplitTimes::new(times, Some(1000)).unwrap();
+        assert_eq!(times.best, 2000);
+        assert_eq!(times.mean, 5125);
+        assert_eq!(times.median, 4500);
+        assert_eq!(times.std_dev, 2027);
+        assert_eq!(times.percentiles[0], (10, 3400));
+        assert_eq!(times.time_save, 3500);
+        assert!(SplitTimes::new(Vec::new(), None).is_none());
+    }
+
+    #[test]
+    pub fn test_objective_stats() {
+        let names = ["door".to_owned(), "bulk".to_owned(), "WIN".to_owned()];
+        let runs = vec![
+            vec![Some(1000), Some(2000), Some(3000)],
+            vec![Some(1500), Some(2500), None],
+            vec![Some(2000), None, None],
+            vec![None, None, None],
+        ];
+        let golds = [Some(900), None, None];
+        let stats = ObjectiveStats::new("R1A1_1.save".to_owned(), &names, &runs, &golds);
+
+        let attempts: Vec<(usize, usize)> =
+            stats.splits.iter().map(|v| (v.attempts, v.completions)).collect();
+        assert_eq!(attempts, vec![(4, 3), (3, 2), (2, 1)]);
+        assert_eq!(stats.splits[0].times.as_ref().unwrap().time_save, 600);
+        assert_eq!(stats.get_total_time_save(), 600 + 250);
+
+        let mut csv = Vec::new();
+        stats.export_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.lines().nth(1).unwrap().starts_with("door,4,3,00:01.000,00:01.500,00:01.500"));
+    }
+}
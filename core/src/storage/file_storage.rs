@@ -0,0 +1,129 @@
+use std::{fs, path::PathBuf};
+
+use crate::{
+    file_backup,
+    run::{timed_run::RunEnum, traits::Run},
+    save_format,
+    save_manager::SaveType,
+    storage::RunStorage,
+};
+
+/// one `.save`/`.rsave` file for each objective, named after it.
+pub struct FileStorage {
+    directory: PathBuf,
+    backup_directory: PathBuf,
+
+    save_type: SaveType,
+    backup_count: usize,
+}
+
+impl FileStorage {
+    pub fn new(directory: PathBuf, save_type: SaveType) -> Self {
+        Self {
+            backup_directory: directory.join("backups"),
+            directory,
+            save_type,
+            backup_count: file_backup::DEFAULT_BACKUP_COUNT,
+        }
+    }
+}
+
+impl RunStorage for FileStorage {
+    fn get_objectives(&self) -> Result<Vec<String>, String> {
+        if !self.directory.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut result = Vec::new();
+        for entry in fs::read_dir(&self.directory).map_err(|e| e.to_string())? {
+            let name = match entry.map(|v| v.file_name().into_string()) {
+                Ok(Ok(name)) => name,
+                _ => continue,
+            };
+
+            if name.contains(".save") || name.contains(".rsave") {
+                result.push(name);
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn load(&self, objective: &str) -> Result<Vec<RunEnum>, String> {
+        let path = self.directory.join(objective);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let data = fs::read(path).map_err(|e| e.to_string())?;
+        let mut runs = save_format::decode_runs(&data)?;
+
+        for run in &mut runs {
+            run.set_objective_str(objective);
+        }
+
+        Ok(runs)
+    }
+
+    /// the old file is first rotated into the backups folder and
+    /// the new one is written atomically.
+    ///
+    /// nothing is done if the file already holds these runs, otherwise
+    /// every launch would push a real older generation out of the backups.
+    fn save(&mut self, objective: &str, runs: &[RunEnum]) -> Result<(), String> {
+        let path = self.directory.join(objective);
+        let data = save_format::encode_runs(runs, self.save_type)?;
+
+        if fs::read(&path).is_ok_and(|v| v == data) {
+            return Ok(());
+        }
+
+        file_backup::rotate_backups(&path, &self.backup_directory, self.backup_count)?;
+        file_backup::write_atomic(&path, &data)
+    }
+
+    fn set_backup_count(&mut self, backup_count: usize) {
+        self.backup_count = backup_count;
+    }
+
+    fn get_backups(&self, objective: &str) -> Vec<(usize, PathBuf)> {
+        file_backup::list_backups(&self.backup_directory, objective)
+    }
+
+    fn restore_backup(&mut self, objective: &str, generation: usize) -> Result<(), String> {
+        file_backup::restore_backup(
+            &self.directory.join(objective),
+            &self.backup_directory,
+            generation,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        save_manager::SaveType,
+        storage::RunStorage,
+        test_utils::{OBJECTIVE, run},
+    };
+
+    use super::FileStorage;
+
+    #[test]
+    pub fn test_unchanged_save_keeps_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = FileStorage::new(dir.path().to_path_buf(), SaveType::Binary);
+        let first = vec![run(&[("WIN", "00:02:00.000")], true)];
+        let second = vec![run(&[("WIN", "00:01:50.000")], true)];
+
+        storage.save(OBJECTIVE, &first).unwrap();
+        storage.save(OBJECTIVE, &second).unwrap();
+        for _ in 0..5 {
+            storage.save(OBJECTIVE, &second).unwrap();
+        }
+
+        let backups = storage.get_backups(OBJECTIVE);
+        assert_eq!(backups.len(), 1);
+        assert_eq!(storage.load(OBJECTIVE).unwrap(), second);
+    }
+}
@@ -0,0 +1,53 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use crate::run::timed_run::RunEnum;
+
+pub mod file_storage;
+pub mod sqlite_storage;
+
+/// place where the runs of every objective are persisted.
+pub trait RunStorage {
+    /// every objective that has runs stored.
+    fn get_objectives(&self) -> Result<Vec<String>, String>;
+
+    fn load(&self, objective: &str) -> Result<Vec<RunEnum>, String>;
+
+    /// replaces all stored runs of the objective with these.
+    fn save(&mut self, objective: &str, runs: &[RunEnum]) -> Result<(), String>;
+
+    fn set_backup_count(&mut self, _backup_count: usize) {}
+
+    /// backup generations for the objective, newest first.
+    fn get_backups(&self, _objective: &str) -> Vec<(usize, PathBuf)> {
+        Vec::new()
+    }
+
+    fn restore_backup(&mut self, _objective: &str, _generation: usize) -> Result<(), String> {
+        Err("Backups are not supported by this storage".to_owned())
+    }
+}
+
+/// copies every objective from one storage into the other, keeping
+/// the runs that were already in the target.
+///
+/// returns the number of runs that were copied.
+pub fn copy_all(from: &dyn RunStorage, to: &mut dyn RunStorage) -> Result<usize, String> {
+    let mut copied = 0;
+
+    for objective in from.get_objectives()? {
+        let runs = from.load(&objective)?;
+        let mut merged = to.load(&objective)?;
+        let known: HashSet<RunEnum> = merged.iter().cloned().collect();
+
+        for run in runs {
+            if !known.contains(&run) {
+                merged.push(run);
+                copied += 1;
+            }
+        }
+
+        to.save(&objective, &merged)?;
+    }
+
+    Ok(copied)
+}
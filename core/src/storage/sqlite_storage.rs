@@ -0,0 +1,354 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::Path,
+};
+
+use glr_core::split::Split;
+use rusqlite::{Connection, Transaction, params};
+
+use crate::{
+    run::{timed_run::RunEnum, traits::Run},
+    save_format,
+    save_manager::SaveType,
+    storage::RunStorage,
+};
+
+const SCHEMA: &str = "
+    PRAGMA foreign_keys = ON;
+
+    CREATE TABLE IF NOT EXISTS objectives (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL UNIQUE
+    );
+
+    CREATE TABLE IF NOT EXISTS runs (
+        id INTEGER PRIMARY KEY,
+        objective_id INTEGER NOT NULL REFERENCES objectives(id) ON DELETE CASCADE,
+        position INTEGER NOT NULL DEFAULT 0,
+        total_time INTEGER NOT NULL,
+        is_win INTEGER NOT NULL,
+        data BLOB NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS runs_objective ON runs(objective_id, total_time);
+
+    CREATE TABLE IF NOT EXISTS splits (
+        run_id INTEGER NOT NULL REFERENCES runs(id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        time INTEGER NOT NULL,
+        PRIMARY KEY (run_id, position)
+    );
+    CREATE INDEX IF NOT EXISTS splits_name ON splits(name, time);
+";
+
+/// all runs inside a single sqlite database.
+///
+/// every run is kept as a save format blob so it can be migrated like
+/// the files, the other columns only exist for indexing.
+pub struct SqliteStorage {
+    connection: Connection,
+
+    /// row ids of the stored runs of every loaded or saved objective by
+    /// fingerprint, so a save only writes the runs that changed.
+    stored: RefCell<HashMap<String, RunIds>>,
+}
+
+/// row ids of the stored runs by their fingerprint.
+type RunIds = HashMap<u64, Vec<i64>>;
+
+/// tells runs apart, notes and metadata included.
+fn fingerprint(run: &RunEnum) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    run.hash(&mut hasher);
+    run.get_metadata().hash(&mut hasher);
+    run.get_notes().hash(&mut hasher);
+
+    hasher.finish()
+}
+
+impl SqliteStorage {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        Self::with_connection(Connection::open(path).map_err(|e| e.to_string())?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, String> {
+        Self::with_connection(Connection::open_in_memory().map_err(|e| e.to_string())?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, String> {
+        connection
+            .execute_batch(SCHEMA)
+            .map_err(|e| e.to_string())?;
+        Self::add_position(&connection).map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            connection,
+            stored: Default::default(),
+        })
+    }
+
+    /// databases made before runs kept their order get the column, in the order they were added.
+    fn add_position(connection: &Connection) -> rusqlite::Result<()> {
+        let exists: bool = connection.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('runs') WHERE name = 'position'",
+            [],
+            |row| row.get(0),
+        )?;
+        if exists {
+            return Ok(());
+        }
+
+        connection.execute_batch(
+            "ALTER TABLE runs ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+            UPDATE runs SET position = id;",
+        )
+    }
+
+    /// number of runs stored for the objective, without decoding them.
+    pub fn count_runs(&self, objective: &str) -> Result<usize, String> {
+        self.connection
+            .query_row(
+                "SELECT COUNT(*) FROM runs r
+                JOIN objectives o ON o.id = r.objective_id
+                WHERE o.name = ?1",
+                params![objective],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|v| v as usize)
+            .map_err(|e| e.to_string())
+    }
+
+    fn get_objective_id(tx: &Transaction, objective: &str) -> rusqlite::Result<i64> {
+        tx.execute(
+            "INSERT OR IGNORE INTO objectives (name) VALUES (?1)",
+            params![objective],
+        )?;
+
+        tx.query_row(
+            "SELECT id FROM objectives WHERE name = ?1",
+            params![objective],
+            |row| row.get(0),
+        )
+    }
+
+    fn insert_run(
+        tx: &Transaction,
+        objective_id: i64,
+        position: usize,
+        run: &RunEnum,
+        data: &[u8],
+    ) -> rusqlite::Result<i64> {
+        tx.execute(
+            "INSERT INTO runs (objective_id, position, total_time, is_win, data)
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                objective_id,
+                position as i64,
+                run.get_time().get_stamp() as i64,
+                run.is_win(),
+                data
+            ],
+        )?;
+        let run_id = tx.last_insert_rowid();
+
+        let mut statement = tx.prepare_cached(
+            "INSERT INTO splits (run_id, position, name, time) VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for (position, split) in run.get_splits().enumerate() {
+            statement.execute(params![
+                run_id,
+                position as i64,
+                split.get_name(),
+                split.get_time().get_stamp() as i64
+            ])?;
+        }
+
+        Ok(run_id)
+    }
+
+    /// the runs of the objective with the row ids of their fingerprints.
+    fn load_stored(&self, objective: &str) -> Result<(Vec<RunEnum>, RunIds), String> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT r.id, r.data FROM runs r
+                JOIN objectives o ON o.id = r.objective_id
+                WHERE o.name = ?1
+                ORDER BY r.position, r.id",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let rows: Vec<(i64, Vec<u8>)> = statement
+            .query_map(params![objective], |row| Ok((row.get(0)?, row.get(1)?)))
+            .and_then(|rows| rows.collect())
+            .map_err(|e| e.to_string())?;
+
+        let mut runs = Vec::with_capacity(rows.len());
+        let mut ids: RunIds = HashMap::with_capacity(rows.len());
+        for (id, blob) in rows {
+            for mut run in save_format::decode_runs(&blob)? {
+                run.set_objective_str(objective);
+                ids.entry(fingerprint(&run)).or_default().push(id);
+                runs.push(run);
+            }
+        }
+
+        Ok((runs, ids))
+    }
+}
+
+impl RunStorage for SqliteStorage {
+    fn get_objectives(&self) -> Result<Vec<String>, String> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT name FROM objectives o
+                WHERE EXISTS (SELECT 1 FROM runs r WHERE r.objective_id = o.id)
+                ORDER BY name",
+            )
+            .map_err(|e| e.to_string())?;
+
+        statement
+            .query_map([], |row| row.get(0))
+            .and_then(|rows| rows.collect())
+            .map_err(|e| e.to_string())
+    }
+
+    fn load(&self, objective: &str) -> Result<Vec<RunEnum>, String> {
+        let (runs, ids) = self.load_stored(objective)?;
+        self.stored.borrow_mut().insert(objective.to_owned(), ids);
+
+        Ok(runs)
+    }
+
+    /// only the runs that changed are written, the rest only get their new position.
+    fn save(&mut self, objective: &str, runs: &[RunEnum]) -> Result<(), String> {
+        let mut stale = match self.stored.get_mut().remove(objective) {
+            Some(ids) => ids,
+            None => self.load_stored(objective)?.1,
+        };
+
+        let tx = self.connection.transaction().map_err(|e| e.to_string())?;
+        let mut kept: RunIds = HashMap::with_capacity(runs.len());
+        let result = (|| -> Result<(), String> {
+            let objective_id = Self::get_objective_id(&tx, objective).map_err(|e| e.to_string())?;
+
+            for (position, run) in runs.iter().enumerate() {
+                let fingerprint = fingerprint(run);
+                if kept.contains_key(&fingerprint) {
+                    continue;
+                }
+
+                let id = match stale.get_mut(&fingerprint).and_then(|v| v.pop()) {
+                    Some(id) => {
+                        tx.prepare_cached("UPDATE runs SET position = ?2 WHERE id = ?1")
+                            .and_then(|mut v| v.execute(params![id, position as i64]))
+                            .map_err(|e| e.to_string())?;
+                        id
+                    }
+                    None => {
+                        let data = save_format::encode_runs(std::slice::from_ref(run), SaveType::Binary)?;
+                        Self::insert_run(&tx, objective_id, position, run, &data)
+                            .map_err(|e| e.to_string())?
+                    }
+                };
+                kept.insert(fingerprint, vec![id]);
+            }
+
+            for id in stale.values().flatten() {
+                tx.execute("DELETE FROM runs WHERE id = ?1", params![id])
+                    .map_err(|e| e.to_string())?;
+            }
+
+            if runs.is_empty() {
+                tx.execute("DELETE FROM objectives WHERE id = ?1", params![objective_id])
+                    .map_err(|e| e.to_string())?;
+            }
+
+            Ok(())
+        })();
+
+        result.and_then(|_| tx.commit().map_err(|e| e.to_string()))?;
+        self.stored.get_mut().insert(objective.to_owned(), kept);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        run::traits::Run,
+        save_manager::SaveType,
+        storage::{RunStorage, copy_all, file_storage::FileStorage},
+        test_utils::{OBJECTIVE, run},
+    };
+
+    use super::SqliteStorage;
+
+    fn run_ids(storage: &SqliteStorage) -> Vec<i64> {
+        storage
+            .connection
+            .prepare("SELECT id FROM runs ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    pub fn test_sqlite_storage() {
+        let mut storage = SqliteStorage::open_in_memory().unwrap();
+        let objective = OBJECTIVE;
+        let first = run(&[("door", "00:01:00.000"), ("WIN", "00:02:00.000")], true);
+        let second = run(&[("door", "00:00:50.000"), ("WIN", "00:02:10.000")], true);
+        let third = run(&[("door", "00:00:40.000"), ("WIN", "00:02:20.000")], true);
+
+        storage.save(objective, &[first.clone(), second.clone()]).unwrap();
+        assert_eq!(storage.get_objectives().unwrap(), vec![objective.to_owned()]);
+        assert_eq!(storage.load(objective).unwrap(), vec![first.clone(), second.clone()]);
+
+        let ids = run_ids(&storage);
+        storage.save(objective, &[second.clone(), third.clone()]).unwrap();
+        // the second run is not written again
+        assert_eq!(run_ids(&storage)[0], ids[1]);
+
+        let loaded = storage.load(objective).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert!(loaded.contains(&second) && loaded.contains(&third));
+        assert_eq!(storage.count_runs(objective).unwrap(), 2);
+        assert_eq!(loaded[0].get_objective().to_string(), objective);
+
+        // a sorted objective keeps its new order without writing the runs again
+        storage.save(objective, &[third.clone(), second.clone()]).unwrap();
+        assert_eq!(storage.load(objective).unwrap(), vec![third.clone(), second.clone()]);
+        assert_eq!(run_ids(&storage).len(), 2);
+
+        storage.save(objective, &[]).unwrap();
+        assert!(storage.get_objectives().unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn test_import_export_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files = FileStorage::new(dir.path().to_path_buf(), SaveType::Binary);
+        let mut sqlite = SqliteStorage::open_in_memory().unwrap();
+        let runs = vec![
+            run(&[("door", "00:01:00.000"), ("WIN", "00:02:00.000")], true),
+            run(&[("door", "00:00:50.000"), ("WIN", "00:02:10.000")], true),
+        ];
+
+        files.save(OBJECTIVE, &runs).unwrap();
+        assert_eq!(copy_all(&files, &mut sqlite).unwrap(), 2);
+        assert_eq!(copy_all(&files, &mut sqlite).unwrap(), 0);
+        assert_eq!(sqlite.load(OBJECTIVE).unwrap(), runs);
+
+        let export_dir = tempfile::tempdir().unwrap();
+        let mut exported = FileStorage::new(export_dir.path().to_path_buf(), SaveType::Json);
+        assert_eq!(copy_all(&sqlite, &mut exported).unwrap(), 2);
+        assert_eq!(exported.load(OBJECTIVE).unwrap(), runs);
+    }
+}
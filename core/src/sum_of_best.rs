@@ -0,0 +1,277 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use glr_core::time::Time;
+
+use crate::run::{merge_splits::MergeSplits, timed_run::RunEnum, traits::Run};
+
+/// splits that are not part of the route.
+const IGNORED_SPLITS: [&str; 2] = ["LOSS", "STOP"];
+
+/// one part of the route, a split name done for the n-th time in a run.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Segment {
+    /// the name after merging.
+    pub name: String,
+    /// starts at 1, above 1 when the same split is done again.
+    pub occurrence: usize,
+}
+
+impl Display for Segment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.occurrence {
+            1 => write!(f, "{}", self.name),
+            occurrence => write!(f, "{} ({occurrence})", self.name),
+        }
+    }
+}
+
+/// the time of a segment in one run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentTime {
+    pub segment: Segment,
+    pub time: Time,
+    /// false for a merge group the run did not get through.
+    pub complete: bool,
+}
+
+impl SegmentTime {
+    /// splits the run into segments in the order they were done.
+    ///
+    /// splits of a merge group are added into one segment until every split
+    /// of the group was done, doing a split of the group again starts the
+    /// next occurrence. any other repeated split is a new occurrence.
+    pub fn from_run<R: Run>(run: &R, merge_splits: Option<&MergeSplits>) -> Vec<SegmentTime> {
+        let mut result: Vec<SegmentTime> = Vec::new();
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        // groups still missing a split, with the index of their segment.
+        let mut open: HashMap<String, (usize, HashSet<String>)> = HashMap::new();
+
+        for split in run.get_splits() {
+            let split_name = split.get_name();
+            if IGNORED_SPLITS.contains(&split_name) {
+                continue;
+            }
+
+            let name = merge_splits
+                .and_then(|v| v.get_split(split_name))
+                .map(|v| v.as_str())
+                .unwrap_or(split_name);
+            let group_len = merge_splits
+                .and_then(|v| v.get_req_splits(name))
+                .map(|v| v.len())
+                .unwrap_or(1);
+
+            if let Some((id, done)) = open.get_mut(name)
+                && done.insert(split_name.to_owned())
+            {
+                result[*id].time += split.get_time();
+                if done.len() == group_len {
+                    result[*id].complete = true;
+                    open.remove(name);
+                }
+                continue;
+            }
+
+            let occurrence = occurrences.entry(name.to_owned()).or_default();
+            *occurrence += 1;
+            result.push(SegmentTime {
+                segment: Segment {
+                    name: name.to_owned(),
+                    occurrence: *occurrence,
+                },
+                time: split.get_time(),
+                complete: group_len <= 1,
+            });
+
+            match group_len > 1 {
+                true => open.insert(
+                    name.to_owned(),
+                    (result.len() - 1, HashSet::from([split_name.to_owned()])),
+                ),
+                false => open.remove(name),
+            };
+        }
+
+        result
+    }
+}
+
+/// the best time of a segment and the run it comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gold {
+    pub segment: Segment,
+    /// none if no run completed the segment.
+    pub time: Option<Time>,
+    /// index of the run in the runs given.
+    pub run: Option<usize>,
+}
+
+/// the sum of the best time of every segment of the route.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SumOfBest {
+    golds: Vec<Gold>,
+}
+
+impl SumOfBest {
+    /// segments are kept in the order of the merge splits and then in the
+    /// order they are first seen, like the split names of the save manager.
+    /// only complete segments can be golds and the earlier run keeps the
+    /// gold on a tie.
+    pub fn new(runs: &[RunEnum], merge_splits: Option<&MergeSplits>) -> Self {
+        let mut golds: Vec<Gold> = Vec::new();
+        let mut positions: HashMap<Segment, usize> = HashMap::new();
+
+        for (run_id, run) in runs.iter().enumerate() {
+            for segment in SegmentTime::from_run(run, merge_splits) {
+                let id = *positions.entry(segment.segment.clone()).or_insert_with(|| {
+                    golds.push(Gold {
+                        segment: segment.segment.clone(),
+                        time: None,
+                        run: None,
+                    });
+                    golds.len() - 1
+                });
+
+                let gold = &mut golds[id];
+                if segment.complete && gold.time.is_none_or(|v| segment.time < v) {
+                    gold.time = Some(segment.time);
+                    gold.run = Some(run_id);
+                }
+            }
+        }
+
+        if let Some(merge_splits) = merge_splits {
+            golds.sort_by_key(|v| {
+                merge_splits
+                    .get_order_position(&v.segment.name)
+                    .unwrap_or(usize::MAX)
+            });
+        }
+
+        Self { golds }
+    }
+
+    pub fn get_golds(&self) -> &Vec<Gold> {
+        &self.golds
+    }
+
+    pub fn get_total(&self) -> Time {
+        let mut result = Time::new();
+        for time in self.golds.iter().filter_map(|v| v.time) {
+            result += time;
+        }
+
+        result
+    }
+
+    /// the segments that no run completed, the total is missing their time.
+    pub fn get_missing(&self) -> impl Iterator<Item = &Segment> {
+        self.golds
+            .iter()
+            .filter(|v| v.time.is_none())
+            .map(|v| &v.segment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        run::merge_splits::{MergeConfig, MergeGroup, MergeSplits},
+        test_utils::{run, time},
+    };
+
+    use super::{Segment, SegmentTime, SumOfBest};
+
+    fn bulk_merge() -> MergeSplits {
+        MergeConfig {
+            groups: vec![MergeGroup {
+                name: "bulk".to_owned(),
+                display_name: None,
+                splits: vec!["bulk_a".to_owned(), "bulk_b".to_owned()],
+            }],
+            order: Vec::new(),
+        }
+        .into()
+    }
+
+    fn segment(name: &str, occurrence: usize) -> Segment {
+        Segment {
+            name: name.to_owned(),
+            occurrence,
+        }
+    }
+
+    #[test]
+    pub fn test_segments() {
+        let merge = bulk_merge();
+        let run = run(
+            &[
+                ("door", "00:00:10.000"),
+                ("bulk_a", "00:00:20.000"),
+                ("door", "00:00:05.000"),
+                ("bulk_b", "00:00:30.000"),
+                ("bulk_a", "00:00:01.000"),
+                ("LOSS", "00:00:01.000"),
+            ],
+            false,
+        );
+
+        let segments: Vec<_> = SegmentTime::from_run(&run, Some(&merge))
+            .into_iter()
+            .map(|v| (v.segment.to_string(), v.time, v.complete))
+            .collect();
+        assert_eq!(
+            segments,
+            vec![
+                ("door".to_owned(), time("00:00:10.000"), true),
+                ("bulk".to_owned(), time("00:00:50.000"), true),
+                ("door (2)".to_owned(), time("00:00:05.000"), true),
+                ("bulk (2)".to_owned(), time("00:00:01.000"), false),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_sum_of_best() {
+        let merge = bulk_merge();
+        let runs = vec![
+            run(
+                &[
+                    ("door", "00:00:10.000"),
+                    ("door", "00:00:10.000"),
+                    ("bulk_a", "00:00:20.000"),
+                    ("bulk_b", "00:00:20.000"),
+                    ("WIN", "00:00:10.000"),
+                ],
+                false,
+            ),
+            // faster on the first door, dies during the bulk.
+            run(
+                &[("door", "00:00:08.000"), ("door", "00:00:12.000"), ("bulk_a", "00:00:01.000")],
+                false,
+            ),
+            run(&[("door", "00:00:09.000"), ("door", "00:00:09.000")], false),
+        ];
+
+        let sum = SumOfBest::new(&runs, Some(&merge));
+        let golds: Vec<_> = sum.get_golds().iter().map(|v| (v.segment.clone(), v.run)).collect();
+        assert_eq!(
+            golds,
+            vec![
+                (segment("door", 1), Some(1)),
+                (segment("door", 2), Some(2)),
+                (segment("bulk", 1), Some(0)),
+                (segment("WIN", 1), Some(0)),
+            ]
+        );
+        assert_eq!(sum.get_total(), time("00:01:07.000"));
+        assert_eq!(sum.get_missing().count(), 0);
+
+        let sum = SumOfBest::new(&runs[1..], Some(&merge));
+        assert_eq!(sum.get_missing().collect::<Vec<_>>(), vec![&segment("bulk", 1)]);
+        assert_eq!(sum.get_total(), time("00:00:17.000"));
+    }
+}
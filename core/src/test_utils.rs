@@ -0,0 +1,33 @@
+//! runs and times shared by the tests of every module.
+
+use glr_core::{split::NamedSplit, time::Time};
+
+use crate::run::{
+    objectives::{objective_enum::ObjectiveEnum, run_objective::RunObjective},
+    timed_run::{LevelRun, RunEnum},
+    traits::Run,
+};
+
+pub const OBJECTIVE: &str = "R1A1_1.save";
+
+pub fn time(text: &str) -> Time {
+    Time::from(text).unwrap()
+}
+
+/// a run of the objective with one split for each name and time.
+pub fn level_run(objective: RunObjective, splits: &[(&str, &str)], is_win: bool) -> LevelRun {
+    let mut run = LevelRun::new(ObjectiveEnum::Run(objective));
+    for (name, t) in splits {
+        run.add_split(NamedSplit::new(time(t), name.to_string()));
+    }
+    run.set_win(is_win);
+
+    run
+}
+
+/// a solo R1A1 run, saved under [OBJECTIVE].
+pub fn run(splits: &[(&str, &str)], is_win: bool) -> RunEnum {
+    let objective = RunObjective::from_name("R1A1".to_owned()).with_player_count(1);
+
+    RunEnum::Level(level_run(objective, splits, is_win))
+}
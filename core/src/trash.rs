@@ -0,0 +1,302 @@
+use std::{fmt::Display, path::Path};
+
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    file_backup,
+    run::timed_run::RunEnum,
+    save_format,
+    save_manager::SaveType,
+};
+
+const TRASH_FILE_NAME: &str = "trash.bin";
+
+/// written before the runs so a newer layout is never read as this one.
+const TRASH_MAGIC: &[u8; 4] = b"GLTR";
+/// version of the layout of [StoredRun], the files written before the
+/// layout had a version start with the runs right away.
+const TRASH_VERSION: u16 = 1;
+
+/// how long runs stay in the trash by default, in days.
+pub const DEFAULT_TRASH_DAYS: u32 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrashReason {
+    Deleted,
+    Optimized,
+}
+
+impl Display for TrashReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrashReason::Deleted => write!(f, "Deleted"),
+            TrashReason::Optimized => write!(f, "Removed as useless"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrashedRun {
+    pub objective: String,
+    pub run: RunEnum,
+    pub reason: TrashReason,
+    pub deleted_at: NaiveDateTime,
+}
+
+/// layout on disk, the run goes through the save format so trash
+/// written by older versions can still be read.
+#[derive(Serialize, Deserialize)]
+struct StoredRun {
+    objective: String,
+    reason: TrashReason,
+    deleted_at: NaiveDateTime,
+    run: Vec<u8>,
+}
+
+fn decode_stored(data: &[u8]) -> Result<Vec<StoredRun>, String> {
+    let Some(data) = data.strip_prefix(TRASH_MAGIC) else {
+        return bincode::deserialize(data).map_err(|e| e.to_string());
+    };
+
+    let Some((version, payload)) = data.split_first_chunk::<2>() else {
+        return Err("Trash file is too short".to_owned());
+    };
+    let version = u16::from_le_bytes(*version);
+    if version > TRASH_VERSION {
+        return Err(format!("Trash version {version} is newer than supported {TRASH_VERSION}"));
+    }
+
+    bincode::deserialize(payload).map_err(|e| e.to_string())
+}
+
+/// runs removed from the save manager that can still be restored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trash {
+    runs: Vec<TrashedRun>,
+
+    /// the file held runs that could not be read, it is moved aside
+    /// before it is saved over.
+    unreadable: bool,
+    /// runs were added or removed since it was loaded or saved.
+    changed: bool,
+}
+
+impl Trash {
+    /// the trash stored next to the save data, empty if there is none.
+    pub fn load(directory: &Path) -> Self {
+        Self::load_from(&directory.join(TRASH_FILE_NAME))
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        let Ok(data) = std::fs::read(path) else {
+            return Self::default();
+        };
+
+        let stored = match decode_stored(&data) {
+            Ok(stored) => stored,
+            Err(e) => {
+                eprintln!("Failed to read trash {}: {e}", path.display());
+                return Self {
+                    unreadable: true,
+                    ..Default::default()
+                };
+            }
+        };
+
+        let mut unreadable = false;
+        let runs = stored
+            .into_iter()
+            .filter_map(|v| {
+                let run = save_format::decode_runs(&v.run)
+                    .inspect_err(|e| {
+                        eprintln!("Failed to read trashed run: {e}");
+                        unreadable = true;
+                    })
+                    .ok()?
+                    .pop()?;
+
+                Some(TrashedRun {
+                    objective: v.objective,
+                    run,
+                    reason: v.reason,
+                    deleted_at: v.deleted_at,
+                })
+            })
+            .collect();
+
+        Self {
+            runs,
+            unreadable,
+            changed: false,
+        }
+    }
+
+    pub fn save(&mut self, directory: &Path) -> Result<(), String> {
+        self.save_to(&directory.join(TRASH_FILE_NAME))
+    }
+
+    /// a file that could not be fully read is first moved aside.
+    pub fn save_to(&mut self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+
+        if self.unreadable && path.exists() {
+            let kept = file_backup::keep_unreadable(path)?;
+            eprintln!("Kept the unreadable trash as {}", kept.display());
+        }
+
+        let stored = self
+            .runs
+            .iter()
+            .map(|v| {
+                Ok(StoredRun {
+                    objective: v.objective.clone(),
+                    reason: v.reason,
+                    deleted_at: v.deleted_at,
+                    run: save_format::encode_runs(std::slice::from_ref(&v.run), SaveType::Binary)?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let mut data = TRASH_MAGIC.to_vec();
+        data.extend_from_slice(&TRASH_VERSION.to_le_bytes());
+        bincode::serialize_into(&mut data, &stored).map_err(|e| e.to_string())?;
+
+        file_backup::write_atomic(path, &data)?;
+        self.unreadable = false;
+        self.changed = false;
+
+        Ok(())
+    }
+
+    /// false while there is nothing new to save.
+    pub fn is_changed(&self) -> bool {
+        self.changed
+    }
+
+    pub fn get_runs(&self) -> &[TrashedRun] {
+        &self.runs
+    }
+
+    pub fn len(&self) -> usize {
+        self.runs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    pub fn add(&mut self, objective: String, run: RunEnum, reason: TrashReason) {
+        self.runs.push(TrashedRun {
+            objective,
+            run,
+            reason,
+            deleted_at: Local::now().naive_local(),
+        });
+        self.changed = true;
+    }
+
+    /// removes the run at the index, used to restore or purge it.
+    pub fn take(&mut self, id: usize) -> Option<TrashedRun> {
+        let run = (id < self.runs.len()).then(|| self.runs.remove(id))?;
+        self.changed = true;
+
+        Some(run)
+    }
+
+    /// removes the newest copy of the run, used when a delete is undone.
+    pub fn take_run(&mut self, objective: &str, run: &RunEnum) -> Option<TrashedRun> {
+        let id = self
+            .runs
+            .iter()
+            .rposition(|v| v.objective == objective && v.run == *run)?;
+
+        self.take(id)
+    }
+
+    pub fn clear(&mut self) {
+        self.changed |= !self.runs.is_empty();
+        self.runs.clear();
+    }
+
+    /// removes the runs deleted more than `days` days before `now`.
+    ///
+    /// returns how many runs were removed.
+    pub fn purge_older_than(&mut self, days: u32, now: NaiveDateTime) -> usize {
+        let limit = now - TimeDelta::days(days as i64);
+        let len = self.runs.len();
+        self.runs.retain(|v| v.deleted_at >= limit);
+        self.changed |= self.runs.len() < len;
+
+        len - self.runs.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, TimeDelta};
+    use glr_core::split::Split;
+
+    use crate::{
+        run::timed_run::RunEnum,
+        test_utils::{self, time},
+    };
+
+    use super::{TRASH_MAGIC, Trash, TrashReason};
+
+    fn run(t: &str) -> RunEnum {
+        test_utils::run(&[("WIN", t)], true)
+    }
+
+    #[test]
+    pub fn test_trash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trash.bin");
+        let objective = "R1A1_1.save".to_owned();
+
+        let mut trash = Trash::default();
+        trash.add(objective.clone(), run("00:10:00.000"), TrashReason::Deleted);
+        trash.add(objective.clone(), run("00:11:00.000"), TrashReason::Optimized);
+        trash.save_to(&path).unwrap();
+
+        let mut loaded = Trash::load_from(&path);
+        assert_eq!(loaded, trash);
+        assert_eq!(loaded.get_runs()[1].reason, TrashReason::Optimized);
+        assert!(!loaded.is_changed());
+
+        let taken = loaded.take_run(&objective, &run("00:10:00.000")).unwrap();
+        assert!(loaded.is_changed());
+        assert_eq!(taken.run.get_time(), time("00:10:00.000"));
+        assert!(loaded.take_run(&objective, &run("00:10:00.000")).is_none());
+        assert!(loaded.take(1).is_none());
+
+        let now = Local::now().naive_local();
+        assert_eq!(trash.purge_older_than(30, now), 0);
+        assert_eq!(trash.purge_older_than(30, now + TimeDelta::days(31)), 2);
+        assert!(trash.is_empty());
+    }
+
+    #[test]
+    pub fn test_unreadable_trash_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trash.bin");
+        let newer = [TRASH_MAGIC.as_slice(), &u16::MAX.to_le_bytes(), b"runs"].concat();
+        std::fs::write(&path, &newer).unwrap();
+
+        let mut trash = Trash::load_from(&path);
+        assert!(trash.is_empty());
+
+        trash.add("R1A1_1.save".to_owned(), run("00:10:00.000"), TrashReason::Deleted);
+        trash.save_to(&path).unwrap();
+        assert_eq!(Trash::load_from(&path).len(), 1);
+
+        let kept: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|v| v.unwrap().path())
+            .filter(|v| v != &path)
+            .collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(std::fs::read(&kept[0]).unwrap(), newer);
+    }
+}
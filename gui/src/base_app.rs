@@ -1,4 +1,5 @@
 use core::{
+    import_ledger::ImportLedger,
     run::timed_run::LevelRun,
     save_manager::SaveManager,
     version::{get_latest_version, is_there_new_version},
@@ -14,7 +15,7 @@ use opener::open;
 use crate::{
     render::Render,
     windows::{
-        await_parse_files::AwaitParseFiles, live_window::{live_window::LiveWindow, objective_reader::LevelObjectiveReader},
+        auto_import::AutoImporter, await_parse_files::AwaitParseFiles, live_window::{live_window::LiveWindow, objective_reader::LevelObjectiveReader},
         log_parser_window::LogParserWindow, run_manager_window::RunManagerWindow,
         settings_window::SettingsWindow, stats_window::StatsWindow,
     },
@@ -40,6 +41,7 @@ pub struct BaseApp {
     settings_window: SettingsWindow,
     save_manager: SaveManager,
     obj_reader: Option<LevelObjectiveReader>,
+    auto_importer: Option<AutoImporter>,
     limiter: CpuLimiter,
 
     latest_version: Option<String>,
@@ -77,8 +79,14 @@ impl BaseApp {
     pub fn new(cc: &CreationContext) -> Self {
         let mut fonts = FontDefinitions::default();
         let settings_window = SettingsWindow::default();
+        let settings = settings_window.get_settings();
         let mut save_manager = SaveManager::default();
-        save_manager.set_automatic_saving(settings_window.get_def("automatic_saving"));
+        save_manager.set_automatic_saving(settings.general.automatic_saving);
+        save_manager.set_backup_count(settings.general.backup_count as usize);
+        save_manager.set_trash_max_age(settings.general.trash_days);
+        if let Err(e) = save_manager.set_save_type(settings.general.save_type) {
+            eprintln!("Failed to open run storage: {e}");
+        }
 
         fonts.font_data.insert(
             "jetbrains_mono".to_owned(),
@@ -117,14 +125,16 @@ impl BaseApp {
         cc.egui_ctx.set_fonts(fonts);
         cc.egui_ctx.set_theme(egui::Theme::Dark);
 
-        let size = settings_window.get("text_size").unwrap_or(12f32);
-        let font_name = settings_window.get_font_name();
+        let size = settings.general.text_size;
+        let font_name = settings.get_font_name();
         Self::update_font_data(&cc.egui_ctx, &font_name, size);
 
-        if settings_window.get_def("automatic_loading") {
+        if settings.general.automatic_loading {
             save_manager.load_all_runs();
         }
 
+        let auto_importer = AutoImporter::new(settings);
+
         let limiter = CpuLimiter::new(Duration::from_micros(16667));
 
         let latest_version = get_latest_version();
@@ -138,6 +148,7 @@ impl BaseApp {
             live_window_size: None,
             app_state: AppState::None,
             obj_reader: Some(LevelObjectiveReader::default()),
+            auto_importer,
 
             save_manager,
             settings_window,
@@ -147,6 +158,26 @@ impl BaseApp {
     }
 }
 
+impl BaseApp {
+    /// pushes the settings that are not read on every frame to the rest of the app.
+    fn apply_settings(&mut self, ctx: &egui::Context) {
+        let settings = self.settings_window.get_settings();
+        self.save_manager.set_automatic_saving(settings.general.automatic_saving);
+        self.save_manager.set_backup_count(settings.general.backup_count as usize);
+        self.save_manager.set_trash_max_age(settings.general.trash_days);
+        if let Err(e) = self.save_manager.set_save_type(settings.general.save_type) {
+            eprintln!("Failed to open run storage: {e}");
+        }
+
+        let font_name = settings.get_font_name();
+        Self::update_font_data(ctx, &font_name, settings.general.text_size);
+
+        if !self.auto_importer.as_ref().is_some_and(|v| v.matches(settings)) {
+            self.auto_importer = AutoImporter::new(settings);
+        }
+    }
+}
+
 impl eframe::App for BaseApp {
     fn clear_color(&self, _visuals: &egui::Visuals) -> [f32; 4] {
         match self.app_state {
@@ -154,7 +185,7 @@ impl eframe::App for BaseApp {
                 0.0,
                 0.0,
                 0.0,
-                self.settings_window.get_def("window_transparency"),
+                self.settings_window.get_settings().splitter.window_transparency,
             ],
             _ => [0.0, 0.0, 0.0, 1.0],
         }
@@ -163,6 +194,16 @@ impl eframe::App for BaseApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         ctx.request_repaint_after(Duration::from_millis(50));
 
+        if let Some(auto_importer) = &mut self.auto_importer {
+            let idle = !matches!(
+                self.app_state,
+                AppState::LiveWindow(_)
+                    | AppState::AwaitParseLogWindow(_)
+                    | AppState::AwaitParseStatWindow(_)
+            );
+            auto_importer.update(idle);
+        }
+
         let frame = Frame::none().fill(Color32::TRANSPARENT);
 
         egui::TopBottomPanel::top("TopPanel")
@@ -193,14 +234,7 @@ impl eframe::App for BaseApp {
                             self.app_state = AppState::None;
 
                             self.settings_window.save_settings();
-                            self.save_manager.set_automatic_saving(
-                                self.settings_window.get_def("automatic_saving"),
-                            );
-
-                            let font_name = self.settings_window.get_font_name();
-                            let font_size = self.settings_window.get("text_size")
-                                .unwrap_or(12f32);
-                            Self::update_font_data(ctx, &font_name, font_size);
+                            self.apply_settings(ctx);
                         }
 
                         return;
@@ -210,43 +244,89 @@ impl eframe::App for BaseApp {
                         ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(
                             egui::WindowLevel::AlwaysOnTop,
                         ));
-                        let x = self.settings_window.get_def("x_position");
-                        let y = self.settings_window.get_def("y_position");
+                        let settings = self.settings_window.get_settings();
+                        let x = settings.splitter.x_position;
+                        let y = settings.splitter.y_position;
                         ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::Pos2 {
                             x,
                             y,
                         }));
                         ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::Vec2 {
-                            x: self.settings_window.get_def("x_size"),
+                            x: settings.splitter.x_size,
                             y: 80f32,
                         }));
                         self.live_window_size = Some(80);
                         self.app_state =
-                            AppState::LiveWindow(LiveWindow::new(self.obj_reader.take(), &self.settings_window));
+                            AppState::LiveWindow(LiveWindow::new(self.obj_reader.take(), settings, &self.save_manager));
                     }
 
                     if ui.button("Input Speedrun Logs...").clicked() {
                         if let Some(paths) = rfd::FileDialog::new().pick_files() {
                             self.app_state =
-                                AppState::AwaitParseLogWindow(Some(AwaitParseFiles::new(paths)));
+                                AppState::AwaitParseLogWindow(Some(AwaitParseFiles::with_ledger(
+                                    paths,
+                                    self.settings_window.get_settings().general.import_workers as usize,
+                                    ImportLedger::load(),
+                                )));
                         }
                     }
 
                     if ui.button("Grab stats from Logs...").clicked() {
                         if let Some(paths) = rfd::FileDialog::new().pick_files() {
                             self.app_state =
-                                AppState::AwaitParseStatWindow(Some(AwaitParseFiles::new(paths)));
+                                AppState::AwaitParseStatWindow(Some(AwaitParseFiles::new(
+                                    paths,
+                                    self.settings_window.get_settings().general.import_workers as usize,
+                                )));
                         }
                     }
 
                     if ui.button("Check Saved Runs").clicked() {
-                        self.app_state = AppState::ManagingRuns(RunManagerWindow::new(&self.settings_window));
+                        self.app_state = AppState::ManagingRuns(RunManagerWindow::new());
+                    }
+
+                    if let Some(auto_importer) = &mut self.auto_importer {
+                        let inbox_len = auto_importer.get_inbox_len();
+                        if inbox_len > 0 && ui.button(format!("Inbox ({inbox_len})")).clicked() {
+                            self.app_state = AppState::LogParserWindow(
+                                LogParserWindow::new(auto_importer.get_inbox()).from_inbox(),
+                            );
+                        } else if auto_importer.is_scanning() {
+                            ui.label("Importing logs...");
+                        }
+
+                        if let Some(error) = auto_importer.get_error() {
+                            ui.colored_label(Color32::RED, error);
+                        }
                     }
 
                     if ui.button("Settings").clicked() {
                         self.app_state = AppState::SettingsWindow;
                     }
 
+                    if !self.settings_window.get_profiles().is_empty() {
+                        let mut selected = None;
+                        egui::ComboBox::from_id_salt("profile_selector")
+                            .selected_text(
+                                self.settings_window.get_active_profile().unwrap_or("Profile"),
+                            )
+                            .show_ui(ui, |ui| {
+                                for profile in self.settings_window.get_profiles() {
+                                    let active = self.settings_window.get_active_profile()
+                                        == Some(profile.as_str());
+                                    if ui.selectable_label(active, profile).clicked() {
+                                        selected = Some(profile.clone());
+                                    }
+                                }
+                            });
+
+                        if let Some(profile) = selected
+                            && self.settings_window.switch_profile(&profile)
+                        {
+                            self.apply_settings(ctx);
+                        }
+                    }
+
                     if self.new_version_warning {
                         if let Some(version) = &self.latest_version {
                             if ui
@@ -272,27 +352,32 @@ impl eframe::App for BaseApp {
             .show(ctx, |ui| match &mut self.app_state {
                 AppState::None => {}
                 AppState::LogParserWindow(log_parser_window) => {
-                    log_parser_window.render(ui, &mut self.save_manager)
+                    log_parser_window.render(ui, &mut self.save_manager);
+
+                    if let Some(runs) = log_parser_window.take_inbox_changes()
+                        && let Some(auto_importer) = &mut self.auto_importer
+                    {
+                        auto_importer.set_inbox(runs);
+                    }
                 }
                 AppState::ManagingRuns(run_manager_window) => {
                     run_manager_window.render(ui, &mut self.save_manager)
                 }
                 AppState::SettingsWindow => {
                     if self.settings_window.render(ui) {
-                        let font_name = self.settings_window.get_font_name();
-                        let font_size = self.settings_window.get("text_size")
-                            .unwrap_or(12f32);
-                        Self::update_font_data(ctx, &font_name, font_size);
+                        let settings = self.settings_window.get_settings();
+                        let font_name = settings.get_font_name();
+                        Self::update_font_data(ctx, &font_name, settings.general.text_size);
                     }
                 }
                 AppState::LiveWindow(live_window) => {
-                    let size =
-                        live_window.render(ui, &mut self.save_manager, &self.settings_window);
+                    let settings = self.settings_window.get_settings();
+                    let size = live_window.render(ui, &mut self.save_manager, settings);
 
                     if self.live_window_size.is_none_or(|v| v != size) {
-                        let multi = self.settings_window.get_def::<f32>("text_size") / 12f32;
+                        let multi = settings.general.text_size / 12f32;
                         ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(Vec2 {
-                            x: self.settings_window.get_def("x_size"),
+                            x: settings.splitter.x_size,
                             y: size as f32 * multi,
                         }));
                         self.live_window_size = Some(size);
@@ -303,9 +388,16 @@ impl eframe::App for BaseApp {
                 }
                 AppState::AwaitParseLogWindow(awaiter) => {
                     if awaiter.render(ui).is_some_and(|v| v == true) {
-                        let awaiter = awaiter.take().unwrap();
-                        self.app_state =
-                            AppState::LogParserWindow(LogParserWindow::new(awaiter.into()));
+                        let mut awaiter = awaiter.take().unwrap();
+                        let summary = awaiter.get_summary();
+                        let report = awaiter.get_report().clone();
+                        let ledger = awaiter.take_ledger();
+                        self.app_state = AppState::LogParserWindow(
+                            LogParserWindow::new(awaiter.into())
+                                .with_import_summary(summary)
+                                .with_import_report(report)
+                                .with_pending_ledger(ledger),
+                        );
                     }
                 }
                 AppState::AwaitParseStatWindow(awaiter) => {
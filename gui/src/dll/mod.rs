@@ -1,2 +1 @@
 pub mod parse_continously;
-pub mod parse_files;
@@ -0,0 +1,43 @@
+use core::edit_history::{Edit, EditHistory};
+
+use egui::{Key, KeyboardShortcut, Modifiers, Ui};
+
+const UNDO: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+const REDO: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Y);
+const REDO_SHIFT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::Z);
+
+/// undo and redo buttons, also bound to ctrl+z and ctrl+y/ctrl+shift+z.
+///
+/// returns true if anything was undone or done again.
+pub fn render_history_buttons<E: Edit>(
+    history: &mut EditHistory<E>,
+    target: &mut E::Target,
+    ui: &mut Ui,
+) -> bool {
+    // shortcuts are ignored while typing so text fields keep their own undo.
+    let typing = ui.ctx().wants_keyboard_input();
+    // the shift version is checked first since ctrl+z would consume it too.
+    let (redo_key, undo_key) = ui.input_mut(|i| match typing {
+        true => (false, false),
+        false => (
+            i.consume_shortcut(&REDO_SHIFT) || i.consume_shortcut(&REDO),
+            i.consume_shortcut(&UNDO),
+        ),
+    });
+
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        let undo = ui.add_enabled(history.can_undo(), egui::Button::new("Undo"));
+        if undo.on_hover_text(ui.ctx().format_shortcut(&UNDO)).clicked() || undo_key {
+            changed |= history.undo(target);
+        }
+
+        let redo = ui.add_enabled(history.can_redo(), egui::Button::new("Redo"));
+        if redo.on_hover_text(ui.ctx().format_shortcut(&REDO)).clicked() || redo_key {
+            changed |= history.redo(target);
+        }
+    });
+
+    changed
+}
@@ -4,10 +4,12 @@ pub mod dll;
 pub mod windows;
 
 pub mod render;
+pub mod history_buttons;
 pub mod sorter_buttons;
 
 pub mod base_app;
 pub mod run;
+pub mod settings;
 
 use base_app::BaseApp;
 use eframe::egui;
@@ -1,3 +1,7 @@
+use core::run::objectives::{
+    category::Categories, game_objective::GameObjective, game_run_objective::GameRunObjective,
+    game_run_rundown::GameRunRundown,
+};
 use std::ops::DerefMut;
 
 use egui::Ui;
@@ -46,3 +50,55 @@ where
         self.deref_mut().update();
     }
 }
+
+/// picker for the rundown, category and player count of a game run.
+///
+/// user categories fix their own levels and player count.
+impl Render for GameObjective {
+    type Response = bool;
+
+    fn render(&mut self, ui: &mut Ui) -> Self::Response {
+        let old = self.clone();
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("game_objective_objective")
+                .selected_text(self.objective.to_string())
+                .show_ui(ui, |ui| {
+                    for objective in GameRunObjective::all() {
+                        let text = objective.to_string();
+                        ui.selectable_value(&mut self.objective, objective, text);
+                    }
+                });
+
+            if let GameRunObjective::Category(name) = &self.objective {
+                if self.objective != old.objective
+                    && let Some(category) = Categories::get().get_category(name)
+                {
+                    *self = GameObjective::from_category(category);
+                }
+
+                ui.label(format!("{}p", self.player_count));
+                return;
+            }
+
+            egui::ComboBox::from_id_salt("game_objective_rundown")
+                .selected_text(self.rundown.to_string())
+                .show_ui(ui, |ui| {
+                    for rundown in GameRunRundown::all() {
+                        let text = rundown.to_string();
+                        ui.selectable_value(&mut self.rundown, rundown, text);
+                    }
+                });
+
+            egui::ComboBox::from_id_salt("game_objective_players")
+                .selected_text(format!("{}p", self.player_count))
+                .show_ui(ui, |ui| {
+                    for player_count in 1..=4 {
+                        ui.selectable_value(&mut self.player_count, player_count, format!("{player_count}p"));
+                    }
+                });
+        });
+
+        old != *self
+    }
+}
@@ -13,6 +13,7 @@ pub struct RenderResult {
     pub save: bool,
     pub compare_first: Option<bool>,
     pub compare_second: Option<bool>,
+    pub notes: bool,
 }
 
 pub trait RenderRun {
@@ -23,6 +24,7 @@ pub trait RenderRun {
         save_manager: &SaveManager,
         ui: &mut Ui,
         show_split_times: bool,
+        show_run_info: bool,
         compare_run: Option<&RunEnum>,
         compare_first: bool,
         compare_second: bool,
@@ -40,6 +42,7 @@ where
         save_manager: &SaveManager,
         ui: &mut egui::Ui,
         show_split_times: bool,
+        show_run_info: bool,
         compare_run: Option<&RunEnum>,
         mut compare_first: bool,
         mut compare_second: bool,
@@ -61,15 +64,43 @@ where
             };
 
             ui.label("RUN:");
-            ui.colored_label(color, time.to_string());
+            let time_label = ui.colored_label(color, time.to_string());
+            if let Some(metadata) = self.get_metadata() {
+                time_label.on_hover_text(metadata.to_string());
+            }
 
             ui.label(objective.get_player_count().to_string());
 
             if ui.button(format!("DELETE RUN")).clicked() {
                 result.delete = true;
             }
+
+            let notes_button = match self.get_notes() {
+                Some(notes) => ui.button("NOTES*").on_hover_text(notes.to_string()),
+                None => ui.button("NOTES"),
+            };
+            if notes_button.clicked() {
+                result.notes = true;
+            }
             ui.label(format!("{:03}", self.len()));
 
+            if show_run_info {
+                ui.label(format!(
+                    "{: <19}",
+                    self.get_metadata()
+                        .and_then(|v| v.date)
+                        .map(|v| v.format("%Y-%m-%d %H:%M:%S").to_string())
+                        .unwrap_or_default()
+                ));
+                ui.label(format!(
+                    "{: >10}",
+                    self.get_metadata()
+                        .and_then(|v| v.session_seed)
+                        .map(|v| v.to_string())
+                        .unwrap_or_default()
+                ));
+            }
+
             if !show_split_times {
                 if ui.checkbox(&mut compare_first, "")
                     .clicked() {
@@ -166,24 +197,5 @@ fn grab_time<R: Run>(
     split_name: &String,
     save_manager: &SaveManager,
 ) -> Option<Time> {
-    let splits_vec = save_manager.get_splits_req(objective_str, split_name);
-    match splits_vec {
-        Some(splits_vec) => {
-            let mut result = Time::default();
-            for it in splits_vec.iter().map(|v| run.get_time_for_split(v)) {
-                if let Some(it) = it {
-                    result += it;
-                } else {
-                    return None;
-                }
-            }
-            return Some(result);
-        }
-        None => {
-            if let Some(time) = run.get_time_for_split(split_name) {
-                return Some(time);
-            }
-        }
-    };
-    None
+    save_manager.get_merged_split_time(objective_str, run, split_name)
 }
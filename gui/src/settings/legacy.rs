@@ -0,0 +1,197 @@
+use core::{run::objectives::game_objective::GameObjective, save_manager::SaveType};
+use std::{collections::HashMap, path::PathBuf};
+
+use serde::{Deserialize, de::IgnoredAny};
+
+use super::{LoggerFontEnum, Settings};
+
+/// the string keyed `app.properties` layout used before the settings
+/// were versioned, only read to migrate it.
+#[derive(Deserialize)]
+enum FieldValue {
+    Boolean(bool),
+    Integer(i32, IgnoredAny),
+    Float(f32, IgnoredAny),
+    String(String),
+    Path(PathBuf, IgnoredAny),
+}
+
+#[derive(Deserialize)]
+struct Field {
+    value: FieldValue,
+}
+
+#[derive(Deserialize)]
+pub struct LegacySettings {
+    #[serde(default)]
+    setting_hash: HashMap<String, Field>,
+
+    #[serde(default)]
+    font_used: LoggerFontEnum,
+    #[serde(default)]
+    save_type: SaveType,
+    #[serde(default)]
+    game_objective: Option<GameObjective>,
+}
+
+impl LegacySettings {
+    pub fn from_value(value: serde_yaml::Value) -> Result<Self, String> {
+        serde_yaml::from_value(value).map_err(|e| e.to_string())
+    }
+
+    fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.setting_hash.get(key)?.value {
+            FieldValue::Boolean(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// negative values were accepted by the old text fields, they end up as 0.
+    fn get_u32(&self, key: &str) -> Option<u32> {
+        match self.setting_hash.get(key)?.value {
+            FieldValue::Integer(i, _) => Some(i.max(0) as u32),
+            _ => None,
+        }
+    }
+
+    fn get_f32(&self, key: &str) -> Option<f32> {
+        match self.setting_hash.get(key)?.value {
+            FieldValue::Float(f, _) => Some(f),
+            _ => None,
+        }
+    }
+
+    fn get_string(&self, key: &str) -> Option<String> {
+        match &self.setting_hash.get(key)?.value {
+            FieldValue::String(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    fn get_path(&self, key: &str) -> Option<PathBuf> {
+        match &self.setting_hash.get(key)?.value {
+            FieldValue::Path(p, _) => Some(p.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl From<LegacySettings> for Settings {
+    fn from(legacy: LegacySettings) -> Self {
+        let mut result = Settings::default();
+
+        macro_rules! migrate {
+            ($getter:ident, $($key:literal => $field:expr),+ $(,)?) => {
+                $(if let Some(v) = legacy.$getter($key) { $field = v; })+
+            };
+        }
+
+        let general = &mut result.general;
+        migrate!(get_bool,
+            "automatic_loading" => general.automatic_loading,
+            "automatic_saving" => general.automatic_saving,
+            "show_real_timer" => general.show_real_timer,
+            "show_game_splitter" => general.show_game_splitter,
+            "show_run_splitter" => general.show_run_splitter,
+            "show_run_counter" => general.show_run_counter,
+        );
+        migrate!(get_u32, "backup_count" => general.backup_count);
+        migrate!(get_f32, "text_size" => general.text_size);
+        general.font = legacy.font_used;
+        general.save_type = legacy.save_type;
+
+        let splitter = &mut result.splitter;
+        migrate!(get_bool,
+            "compare_record" => splitter.compare_record,
+            "compare_best_splits" => splitter.compare_best_splits,
+            "show_split_name" => splitter.show_split_name,
+            "overlay_server" => splitter.overlay_server,
+            "livesplit_server" => splitter.livesplit_server,
+        );
+        migrate!(get_u32,
+            "overlay_port" => splitter.overlay_port,
+            "run_splitter_length" => splitter.run_splitter_length,
+            "game_splitter_length" => splitter.game_splitter_length,
+        );
+        migrate!(get_f32,
+            "window_transparency" => splitter.window_transparency,
+            "x_position" => splitter.x_position,
+            "y_position" => splitter.y_position,
+            "x_size" => splitter.x_size,
+        );
+        migrate!(get_string, "livesplit_server_address" => splitter.livesplit_server_address);
+        migrate!(get_path, "logs_path" => splitter.logs_path);
+        if let Some(game_objective) = legacy.game_objective.clone() {
+            splitter.game_objective = game_objective;
+        }
+
+        let mapper = &mut result.mapper;
+        migrate!(get_bool,
+            "show_mapper" => mapper.show_mapper,
+            "show_objectives" => mapper.show_objectives,
+            "show_code_guess" => mapper.show_code_guess,
+        );
+        migrate!(get_u32,
+            "code_guess_line_count" => mapper.code_guess_line_count,
+            "code_guess_word_count" => mapper.code_guess_word_count,
+        );
+
+        let foresight = &mut result.foresight;
+        migrate!(get_bool,
+            "show_foresight" => foresight.show_foresight,
+            "seed_indexer_show_overflow" => foresight.show_overflow,
+            "seed_indexer_show_overflow_hash" => foresight.show_overflow_hash,
+            "seed_indexer_show_resources" => foresight.show_resources,
+            "seed_indexer_show_consumables" => foresight.show_consumables,
+            "seed_indexer_show_artifacts" => foresight.show_artifacts,
+            "seed_indexer_show_gather_small_items" => foresight.show_gather_small_items,
+            "seed_indexer_show_fog_turbine" => foresight.show_fog_turbine,
+            "seed_indexer_show_cell" => foresight.show_cell,
+            "seed_indexer_show_colored_key" => foresight.show_colored_key,
+            "seed_indexer_show_bulkhead_key" => foresight.show_bulkhead_key,
+            "seed_indexer_show_terminal_uplink" => foresight.show_terminal_uplink,
+            "seed_indexer_show_retrieve_big_items" => foresight.show_retrieve_big_items,
+            "seed_indexer_show_special_terminal_command" => foresight.show_special_terminal_command,
+            "seed_indexer_show_hsu" => foresight.show_hsu,
+            "seed_indexer_show_power_cell_distribution" => foresight.show_power_cell_distribution,
+        );
+        migrate!(get_u32,
+            "seed_indexer_overflow_hash_size" => foresight.overflow_hash_size,
+            "seed_indexer_length" => foresight.length,
+        );
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use core::save_manager::SaveType;
+
+    use super::super::{LoggerFontEnum, SETTINGS_VERSION, Settings};
+
+    #[test]
+    pub fn test_migrate_app_properties() {
+        // written by the settings window of the last build without versioned settings.
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../examples/settings/app.properties");
+        let text = std::fs::read_to_string(path).unwrap();
+
+        let settings = Settings::parse(&text).unwrap();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert!(settings.general.automatic_saving);
+        assert_eq!(settings.general.text_size, 12.0);
+        assert_eq!(settings.general.font, LoggerFontEnum::Ubuntu);
+        assert_eq!(settings.general.save_type, SaveType::Json);
+        assert_eq!(settings.splitter.run_splitter_length, 7);
+        assert_eq!(settings.splitter.x_position, 100.5);
+        assert_eq!(settings.splitter.logs_path, PathBuf::from("/home/runner/GTFO logs"));
+        assert!(!settings.foresight.show_hsu);
+        assert!(settings.foresight.show_special_terminal_command);
+
+        let saved = Settings::parse(&settings.to_text().unwrap()).unwrap();
+        assert_eq!(saved, settings);
+    }
+}
@@ -0,0 +1,354 @@
+use core::{
+    file_backup, livesplit_server, logs_folder,
+    run::{
+        default_dirs::get_config_directory,
+        objectives::{Objective, game_objective::GameObjective},
+    },
+    save_manager::SaveType,
+    trash::DEFAULT_TRASH_DAYS,
+};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+mod legacy;
+pub mod profiles;
+pub mod schema;
+
+/// bumped every time a field is renamed or changes meaning.
+///
+/// version 0 is the old string keyed `app.properties` file.
+pub const SETTINGS_VERSION: u32 = 1;
+
+const SETTINGS_FILE_NAME: &str = "settings.yaml";
+const LEGACY_FILE_NAME: &str = "app.properties";
+
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[repr(u8)]
+pub enum LoggerFontEnum {
+    #[default] JetBrainsMono,
+    ShareTechMono,
+    Ubuntu
+}
+
+pub static LOGGER_FONT_ENUM_ITER: &[LoggerFontEnum] = &[
+    LoggerFontEnum::JetBrainsMono,
+    LoggerFontEnum::ShareTechMono,
+    LoggerFontEnum::Ubuntu,
+];
+
+pub static LOGGER_SAVE_TYPE_ENUM_ITER: &[SaveType] = &[
+    SaveType::Binary,
+    SaveType::Json,
+    SaveType::Sqlite,
+];
+
+impl ToString for LoggerFontEnum {
+    fn to_string(&self) -> String {
+        match self {
+            LoggerFontEnum::JetBrainsMono => "jetbrains_mono".to_owned(),
+            LoggerFontEnum::ShareTechMono => "share_tech_mono".to_owned(),
+            LoggerFontEnum::Ubuntu => "ubuntu".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct GeneralSettings {
+    pub automatic_loading: bool,
+    pub automatic_saving: bool,
+    pub backup_count: u32,
+    /// days deleted runs stay in the trash, 0 keeps them until purged by hand.
+    pub trash_days: u32,
+    pub auto_import: bool,
+    /// minutes between two scans of the logs folder.
+    pub auto_import_interval: u32,
+    /// threads used to parse log files.
+    pub import_workers: u32,
+    pub show_real_timer: bool,
+    pub show_game_splitter: bool,
+    pub show_run_splitter: bool,
+    pub show_run_counter: bool,
+    pub text_size: f32,
+    pub font: LoggerFontEnum,
+    pub save_type: SaveType,
+}
+
+impl Default for GeneralSettings {
+    fn default() -> Self {
+        Self {
+            automatic_loading: true,
+            automatic_saving: false,
+            backup_count: 3,
+            trash_days: DEFAULT_TRASH_DAYS,
+            auto_import: false,
+            auto_import_interval: 5,
+            import_workers: 8,
+            show_real_timer: false,
+            show_game_splitter: false,
+            show_run_splitter: true,
+            show_run_counter: true,
+            text_size: 12.0,
+            font: LoggerFontEnum::default(),
+            save_type: SaveType::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SplitterSettings {
+    pub window_transparency: f32,
+    pub logs_path: PathBuf,
+    pub compare_record: bool,
+    pub compare_best_splits: bool,
+    pub show_split_name: bool,
+    pub overlay_server: bool,
+    pub overlay_port: u32,
+    pub livesplit_server: bool,
+    pub livesplit_server_address: String,
+    pub run_splitter_length: u32,
+    pub game_splitter_length: u32,
+    pub game_objective: GameObjective,
+    pub x_position: f32,
+    pub y_position: f32,
+    pub x_size: f32,
+}
+
+impl Default for SplitterSettings {
+    fn default() -> Self {
+        Self {
+            window_transparency: 0.6,
+            logs_path: PathBuf::new(),
+            compare_record: true,
+            compare_best_splits: true,
+            show_split_name: false,
+            overlay_server: false,
+            overlay_port: 8085,
+            livesplit_server: false,
+            livesplit_server_address: livesplit_server::DEFAULT_ADDRESS.to_owned(),
+            run_splitter_length: 100,
+            game_splitter_length: 5,
+            game_objective: GameObjective::default().with_player_count(4),
+            x_position: 0.0,
+            y_position: 250.0,
+            x_size: 250.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct MapperSettings {
+    pub show_mapper: bool,
+    pub show_objectives: bool,
+    pub show_code_guess: bool,
+    pub code_guess_line_count: u32,
+    pub code_guess_word_count: u32,
+}
+
+impl Default for MapperSettings {
+    fn default() -> Self {
+        Self {
+            show_mapper: true,
+            show_objectives: true,
+            show_code_guess: false,
+            code_guess_line_count: 3,
+            code_guess_word_count: 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ForesightSettings {
+    pub show_foresight: bool,
+    pub show_overflow: bool,
+    pub show_overflow_hash: bool,
+    pub overflow_hash_size: u32,
+    pub show_resources: bool,
+    pub show_consumables: bool,
+    pub show_artifacts: bool,
+    pub show_gather_small_items: bool,
+    pub show_fog_turbine: bool,
+    pub show_cell: bool,
+    pub show_colored_key: bool,
+    pub show_bulkhead_key: bool,
+    pub show_terminal_uplink: bool,
+    pub show_retrieve_big_items: bool,
+    pub show_special_terminal_command: bool,
+    pub show_hsu: bool,
+    pub show_power_cell_distribution: bool,
+    pub length: u32,
+}
+
+impl Default for ForesightSettings {
+    fn default() -> Self {
+        Self {
+            show_foresight: true,
+            show_overflow: true,
+            show_overflow_hash: false,
+            overflow_hash_size: 8,
+            show_resources: true,
+            show_consumables: true,
+            show_artifacts: false,
+            show_gather_small_items: true,
+            show_fog_turbine: true,
+            show_cell: true,
+            show_colored_key: true,
+            show_bulkhead_key: true,
+            show_terminal_uplink: true,
+            show_retrieve_big_items: true,
+            show_special_terminal_command: true,
+            show_hsu: true,
+            show_power_cell_distribution: true,
+            length: 10,
+        }
+    }
+}
+
+/// every setting of the app.
+///
+/// defaults live in the `Default` impls of the sections, the allowed
+/// ranges and descriptions in the schema.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    /// the profile these settings were loaded from, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+
+    pub general: GeneralSettings,
+    pub splitter: SplitterSettings,
+    pub mapper: MapperSettings,
+    pub foresight: ForesightSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            profile: None,
+            general: Default::default(),
+            splitter: Default::default(),
+            mapper: Default::default(),
+            foresight: Default::default(),
+        }
+    }
+}
+
+/// the most likely GTFO logs folder, the windows one if none is found.
+///
+/// this scans the steam libraries, so it only runs when no folder was ever set.
+fn detect_logs_path() -> Option<PathBuf> {
+    if let Some(candidate) = logs_folder::find_logs_folders().into_iter().next() {
+        return Some(candidate.path);
+    }
+
+    if let Some(dirs) = directories::UserDirs::new() {
+        return Some(
+            dirs.home_dir()
+                .to_path_buf()
+                .join("AppData\\LocalLow\\10 Chambers Collective\\GTFO"),
+        );
+    }
+
+    None
+}
+
+impl Settings {
+    /// reads any settings version, files without a version are the old
+    /// string keyed layout.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let value: serde_yaml::Value = serde_yaml::from_str(text).map_err(|e| e.to_string())?;
+        let version = value
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .unwrap_or_default();
+
+        let mut settings: Settings = match version {
+            0 => legacy::LegacySettings::from_value(value)?.into(),
+            1 => serde_yaml::from_value(value).map_err(|e| e.to_string())?,
+            _ => {
+                return Err(format!(
+                    "Settings version {version} is newer than the supported {SETTINGS_VERSION}"
+                ));
+            }
+        };
+
+        settings.version = SETTINGS_VERSION;
+        settings.validate();
+
+        Ok(settings)
+    }
+
+    /// fills in the detected logs folder if none is set.
+    pub fn with_detected_logs_path(mut self) -> Self {
+        if self.splitter.logs_path.as_os_str().is_empty() {
+            self.splitter.logs_path = detect_logs_path().unwrap_or_default();
+        }
+
+        self
+    }
+
+    /// the saved settings, migrated from `app.properties` if they were never saved in this version.
+    ///
+    /// the logs folder is detected for new and migrated settings.
+    pub fn load() -> Self {
+        let Some(directory) = get_config_directory() else {
+            return Self::default().with_detected_logs_path();
+        };
+
+        for name in [SETTINGS_FILE_NAME, LEGACY_FILE_NAME] {
+            let Ok(text) = std::fs::read_to_string(directory.join(name)) else {
+                continue;
+            };
+
+            match Self::parse(&text) {
+                Ok(settings) if name == LEGACY_FILE_NAME => {
+                    let settings = settings.with_detected_logs_path();
+                    if let Err(e) = settings.save() {
+                        eprintln!("Failed to save migrated settings: {e}");
+                    }
+
+                    return settings;
+                }
+                Ok(settings) => return settings,
+                Err(e) => eprintln!("Failed to read {name}: {e}"),
+            }
+        }
+
+        Self::default().with_detected_logs_path()
+    }
+
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    pub fn to_text(&self) -> Result<String, String> {
+        serde_yaml::to_string(self).map_err(|e| e.to_string())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let directory = get_config_directory().ok_or("No config directory found".to_owned())?;
+
+        let text = self.to_text()?;
+        file_backup::write_atomic(&directory.join(SETTINGS_FILE_NAME), text.as_bytes())
+    }
+
+    /// pulls every value back into the range the schema allows.
+    pub fn validate(&mut self) {
+        for section in schema::sections(self) {
+            for mut field in section.fields {
+                field.value.clamp();
+            }
+        }
+    }
+
+    pub fn get_font_name(&self) -> String {
+        self.general.font.to_string()
+    }
+}
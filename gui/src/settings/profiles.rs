@@ -0,0 +1,90 @@
+use core::run::default_dirs::get_config_directory;
+use std::path::{Path, PathBuf};
+
+use super::Settings;
+
+const PROFILES_FOLDER: &str = "profiles";
+const PROFILE_EXTENSION: &str = "yaml";
+
+/// the name is the file name of the profile, so it has to stay a plain name.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.trim().is_empty()
+        && !name
+            .chars()
+            .any(|c| matches!(c, '.' | '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+}
+
+fn get_profiles_directory() -> Option<PathBuf> {
+    get_config_directory().map(|v| v.join(PROFILES_FOLDER))
+}
+
+fn get_profile_path(name: &str) -> Result<PathBuf, String> {
+    if !is_valid_name(name) {
+        return Err(format!("Invalid profile name: {name}"));
+    }
+
+    get_profiles_directory()
+        .map(|v| v.join(format!("{name}.{PROFILE_EXTENSION}")))
+        .ok_or("No config directory found".to_owned())
+}
+
+/// names of every saved profile, sorted.
+pub fn list_profiles() -> Vec<String> {
+    let Some(Ok(entries)) = get_profiles_directory().map(std::fs::read_dir) else {
+        return Vec::new();
+    };
+
+    let mut result: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|v| v == PROFILE_EXTENSION))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_owned()))
+        .collect();
+    result.sort();
+
+    result
+}
+
+pub fn load_profile(name: &str) -> Result<Settings, String> {
+    let text = std::fs::read_to_string(get_profile_path(name)?).map_err(|e| e.to_string())?;
+
+    Settings::parse(&text).map(|settings| settings.with_profile(Some(name.to_owned())))
+}
+
+pub fn save_profile(name: &str, settings: &Settings) -> Result<(), String> {
+    let path = get_profile_path(name)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    export_profile(settings, &path)
+}
+
+pub fn delete_profile(name: &str) -> Result<(), String> {
+    std::fs::remove_file(get_profile_path(name)?).map_err(|e| e.to_string())
+}
+
+/// writes a single file that can be shared and imported by someone else.
+pub fn export_profile(settings: &Settings, path: &Path) -> Result<(), String> {
+    let text = settings.clone().with_profile(None).to_text()?;
+
+    std::fs::write(path, text).map_err(|e| e.to_string())
+}
+
+/// copies a shared profile into the profiles folder, named after the file.
+///
+/// returns the name of the imported profile.
+pub fn import_profile(path: &Path) -> Result<String, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let settings = Settings::parse(&text)?;
+
+    let name = path
+        .file_stem()
+        .and_then(|v| v.to_str())
+        .ok_or("Invalid profile file name".to_owned())?
+        .to_owned();
+
+    save_profile(&name, &settings)?;
+
+    Ok(name)
+}
@@ -0,0 +1,147 @@
+use core::{run::objectives::game_objective::GameObjective, save_manager::SaveType};
+use std::{ops::RangeInclusive, path::PathBuf};
+
+use super::{LoggerFontEnum, Settings};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionKind {
+    General,
+    Splitter,
+    Mapper,
+    Foresight,
+}
+
+/// a typed reference to one setting together with the values it may take.
+pub enum SettingValue<'a> {
+    Bool(&'a mut bool),
+    Integer(&'a mut u32, RangeInclusive<u32>),
+    Float(&'a mut f32, RangeInclusive<f32>),
+    Text(&'a mut String),
+    Path(&'a mut PathBuf),
+    Font(&'a mut LoggerFontEnum),
+    SaveType(&'a mut SaveType),
+    GameObjective(&'a mut GameObjective),
+}
+
+impl SettingValue<'_> {
+    pub fn clamp(&mut self) {
+        match self {
+            SettingValue::Integer(v, range) => **v = (**v).clamp(*range.start(), *range.end()),
+            SettingValue::Float(v, range) => {
+                **v = match v.is_nan() {
+                    true => *range.start(),
+                    false => v.clamp(*range.start(), *range.end()),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct SettingField<'a> {
+    pub description: &'static str,
+    pub value: SettingValue<'a>,
+}
+
+pub struct Section<'a> {
+    pub kind: SectionKind,
+    pub title: &'static str,
+    pub fields: Vec<SettingField<'a>>,
+}
+
+fn field<'a>(description: &'static str, value: SettingValue<'a>) -> SettingField<'a> {
+    SettingField { description, value }
+}
+
+/// every setting in the order it is shown, used both by the settings
+/// window and by `Settings::validate`.
+pub fn sections(settings: &mut Settings) -> Vec<Section<'_>> {
+    use SettingValue as V;
+
+    let Settings { general, splitter, mapper, foresight, .. } = settings;
+
+    vec![
+        Section {
+            kind: SectionKind::General,
+            title: "General settings: ",
+            fields: vec![
+                field("Automatic Loading of Runs", V::Bool(&mut general.automatic_loading)),
+                field("Automatic Saving of Runs", V::Bool(&mut general.automatic_saving)),
+                field("Backups kept for each save file", V::Integer(&mut general.backup_count, 0..=100)),
+                field("Days deleted runs stay in the trash (0 keeps them)", V::Integer(&mut general.trash_days, 0..=365)),
+                field("Import new logs in the background", V::Bool(&mut general.auto_import)),
+                field("Minutes between background imports", V::Integer(&mut general.auto_import_interval, 1..=120)),
+                field("Threads used to parse logs", V::Integer(&mut general.import_workers, 1..=64)),
+                field(
+                    "Show real timer   Warning: this timer may not be accurate. Use the in game timer for that.",
+                    V::Bool(&mut general.show_real_timer),
+                ),
+                field("Show game splitter", V::Bool(&mut general.show_game_splitter)),
+                field("Show run splitter", V::Bool(&mut general.show_run_splitter)),
+                field("Show run counter", V::Bool(&mut general.show_run_counter)),
+                field("Text size", V::Float(&mut general.text_size, 6.0..=48.0)),
+                field("Select font", V::Font(&mut general.font)),
+                field("Select SaveType", V::SaveType(&mut general.save_type)),
+            ],
+        },
+        Section {
+            kind: SectionKind::Splitter,
+            title: "LiveSplitter settings: ",
+            fields: vec![
+                field("LiveWindow Transparency", V::Float(&mut splitter.window_transparency, 0.0..=1.0)),
+                field("Path to logs folder: ", V::Path(&mut splitter.logs_path)),
+                field("Compare to saved record", V::Bool(&mut splitter.compare_record)),
+                field("Compare to best splits", V::Bool(&mut splitter.compare_best_splits)),
+                field("Show the split name", V::Bool(&mut splitter.show_split_name)),
+                field("Local overlay server", V::Bool(&mut splitter.overlay_server)),
+                field("Overlay server port", V::Integer(&mut splitter.overlay_port, 1..=65535)),
+                field("Drive LiveSplit through LiveSplit Server", V::Bool(&mut splitter.livesplit_server)),
+                field("LiveSplit Server address", V::Text(&mut splitter.livesplit_server_address)),
+                field("Run Splitter max length", V::Integer(&mut splitter.run_splitter_length, 1..=1000)),
+                field("Game Splitter max length", V::Integer(&mut splitter.game_splitter_length, 1..=1000)),
+                field("X position", V::Float(&mut splitter.x_position, -10000.0..=10000.0)),
+                field("Y position", V::Float(&mut splitter.y_position, -10000.0..=10000.0)),
+                field("X size    ", V::Float(&mut splitter.x_size, 50.0..=4000.0)),
+                field("Game splitter run", V::GameObjective(&mut splitter.game_objective)),
+            ],
+        },
+        Section {
+            kind: SectionKind::Mapper,
+            title: "Mapper settings: ",
+            fields: vec![
+                field("Show mapper in live splitter", V::Bool(&mut mapper.show_mapper)),
+                field("Show objectives items in live splitter", V::Bool(&mut mapper.show_objectives)),
+                field("Show code guess", V::Bool(&mut mapper.show_code_guess)),
+                field("Code guess number of lines: ", V::Integer(&mut mapper.code_guess_line_count, 1..=20)),
+                field("Code guess number of words per line: ", V::Integer(&mut mapper.code_guess_word_count, 1..=20)),
+            ],
+        },
+        Section {
+            kind: SectionKind::Foresight,
+            title: "Foresight settings: ",
+            fields: vec![
+                field("Show Foresight in live splitter", V::Bool(&mut foresight.show_foresight)),
+                field("Show the Marker Set in Foresight", V::Bool(&mut foresight.show_overflow)),
+                field(
+                    "Show the Marker Set Hash in Foresight. This is for specific zone spawns",
+                    V::Bool(&mut foresight.show_overflow_hash),
+                ),
+                field("How many characters to show in the hash size", V::Integer(&mut foresight.overflow_hash_size, 1..=64)),
+                field("Show resources in foresight", V::Bool(&mut foresight.show_resources)),
+                field("Show consumables in foresight", V::Bool(&mut foresight.show_consumables)),
+                field("Show artifacts in foresight", V::Bool(&mut foresight.show_artifacts)),
+                field("Show GatherSmallItem in foresight", V::Bool(&mut foresight.show_gather_small_items)),
+                field("Show FogTurbine in foresight", V::Bool(&mut foresight.show_fog_turbine)),
+                field("Show Cell in foresight", V::Bool(&mut foresight.show_cell)),
+                field("Show ColoredKey in foresight", V::Bool(&mut foresight.show_colored_key)),
+                field("Show BulkKey in foresight", V::Bool(&mut foresight.show_bulkhead_key)),
+                field("Show TerminalUplink in foresight", V::Bool(&mut foresight.show_terminal_uplink)),
+                field("Show RetrieveBigItems in foresight", V::Bool(&mut foresight.show_retrieve_big_items)),
+                field("Show SpecialTerminalCommand in foresight", V::Bool(&mut foresight.show_special_terminal_command)),
+                field("Show HSU_FindTakeSample in foresight", V::Bool(&mut foresight.show_hsu)),
+                field("Show PowerCellDistribution in foresight", V::Bool(&mut foresight.show_power_cell_distribution)),
+                field("Size of Foresight", V::Integer(&mut foresight.length, 1..=100)),
+            ],
+        },
+    ]
+}
@@ -13,7 +13,33 @@ fn get_total_times<T: Split>(timed_runs: &Vec<T>) -> Time {
     total
 }
 
-pub fn render_buttons<T: Run>(timed_runs: &mut Vec<T>, ui: &mut Ui) {
+/// the sort picked with one of the sorter buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Win,
+    Objective,
+    Time,
+    Stamps,
+}
+
+impl SortBy {
+    pub fn apply<T: Run>(self, timed_runs: &mut [T]) {
+        match self {
+            SortBy::Win => timed_runs.sort_by(|d, e| d.is_win().cmp(&e.is_win()).reverse()),
+            SortBy::Objective => {
+                timed_runs.sort_by(|d, e| d.get_objective().cmp(&e.get_objective()))
+            }
+            SortBy::Time => timed_runs
+                .sort_by(|d, e| d.get_time().get_stamp().cmp(&e.get_time().get_stamp())),
+            SortBy::Stamps => timed_runs.sort_by(|a, b| a.len().cmp(&b.len()).reverse()),
+        }
+    }
+}
+
+/// draws the sorter buttons without touching the runs, returns the sort that was clicked.
+pub fn render_buttons<T: Run>(timed_runs: &Vec<T>, ui: &mut Ui) -> Option<SortBy> {
+    let mut sort = None;
+
     ui.horizontal(|ui| {
         ui.label(format!(
             "Total times added: {}",
@@ -21,15 +47,15 @@ pub fn render_buttons<T: Run>(timed_runs: &mut Vec<T>, ui: &mut Ui) {
         ));
 
         if ui.button("Sort by Win").clicked() {
-            timed_runs.sort_by(|d, e| d.is_win().cmp(&e.is_win()).reverse());
+            sort = Some(SortBy::Win);
         }
 
         if ui.button("Sort by objective").clicked() {
-            timed_runs.sort_by(|d, e| d.get_objective().cmp(&e.get_objective()));
+            sort = Some(SortBy::Objective);
         }
 
         if ui.button("Sort by time").clicked() {
-            timed_runs.sort_by(|d, e| d.get_time().get_stamp().cmp(&e.get_time().get_stamp()));
+            sort = Some(SortBy::Time);
         }
 
         // if ui.button(super::create_text("Sort by Players")).clicked() {
@@ -37,9 +63,11 @@ pub fn render_buttons<T: Run>(timed_runs: &mut Vec<T>, ui: &mut Ui) {
         // }
 
         if ui.button("Sort by Stamps").clicked() {
-            timed_runs.sort_by(|a, b| a.len().cmp(&b.len()).reverse());
+            sort = Some(SortBy::Stamps);
         }
     });
+
+    sort
 }
 
 pub trait OptionalVisualSorterButtons<T: Run> {
@@ -48,8 +76,10 @@ pub trait OptionalVisualSorterButtons<T: Run> {
     fn render_buttons(&mut self, ui: &mut Ui) {
         let timed_runs = self.get_vec();
 
-        if let Some(timed_runs) = timed_runs {
-            render_buttons(timed_runs, ui);
+        if let Some(timed_runs) = timed_runs
+            && let Some(sort) = render_buttons(timed_runs, ui)
+        {
+            sort.apply(timed_runs);
         }
     }
 }
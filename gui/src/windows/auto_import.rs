@@ -0,0 +1,220 @@
+use core::{
+    file_backup,
+    import_ledger::ImportLedger,
+    logs_folder,
+    run::timed_run::{LevelRun, RunEnum},
+    save_format,
+    save_manager::{SaveManager, SaveType},
+};
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use crate::{settings::Settings, windows::await_parse_files::AwaitParseFiles};
+
+const INBOX_FILE_NAME: &str = "import_inbox.runs";
+const INBOX_LEDGER_FILE_NAME: &str = "import_inbox_ledger.json";
+
+/// scans the logs folder at startup and then on a timer, new runs wait in
+/// an inbox until they are reviewed.
+pub struct AutoImporter {
+    logs_path: PathBuf,
+    interval: Duration,
+    workers: usize,
+    last_scan: Option<Instant>,
+
+    job: Option<AwaitParseFiles>,
+    inbox: Vec<LevelRun>,
+    /// the log files of the inbox, saved in the ledger once the runs are saved.
+    pending_ledger: ImportLedger,
+    /// how many runs of the inbox are being reviewed.
+    reviewing: usize,
+    /// the inbox file could not be read, it is moved aside before saving.
+    unreadable: bool,
+    error: Option<String>,
+}
+
+impl AutoImporter {
+    /// `None` if background imports are turned off.
+    pub fn new(settings: &Settings) -> Option<Self> {
+        if !settings.general.auto_import {
+            return None;
+        }
+
+        let (inbox, pending_ledger, error) = match Self::load_inbox() {
+            Ok((inbox, ledger)) => (inbox, ledger, None),
+            Err(e) => {
+                eprintln!("{e}");
+                (Vec::new(), ImportLedger::default(), Some(e))
+            }
+        };
+
+        Some(Self {
+            logs_path: settings.splitter.logs_path.clone(),
+            interval: Self::get_interval(settings),
+            workers: settings.general.import_workers as usize,
+            last_scan: None,
+            job: None,
+            inbox,
+            pending_ledger,
+            reviewing: 0,
+            unreadable: error.is_some(),
+            error,
+        })
+    }
+
+    fn get_interval(settings: &Settings) -> Duration {
+        Duration::from_secs(settings.general.auto_import_interval as u64 * 60)
+    }
+
+    /// true if the settings would create the same importer.
+    pub fn matches(&self, settings: &Settings) -> bool {
+        settings.general.auto_import
+            && self.logs_path == settings.splitter.logs_path
+            && self.interval == Self::get_interval(settings)
+            && self.workers == settings.general.import_workers as usize
+    }
+
+    fn get_inbox_path(file_name: &str) -> Result<PathBuf, String> {
+        SaveManager::get_config_directory()
+            .map(|v| v.join(file_name))
+            .ok_or("No config directory found".to_owned())
+    }
+
+    /// the runs of the inbox and the ledger of their log files.
+    fn load_inbox() -> Result<(Vec<LevelRun>, ImportLedger), String> {
+        let path = Self::get_inbox_path(INBOX_FILE_NAME)?;
+        let ledger = ImportLedger::load_from(&Self::get_inbox_path(INBOX_LEDGER_FILE_NAME)?);
+        if !path.exists() {
+            return Ok((Vec::new(), ledger));
+        }
+
+        let data = std::fs::read(&path).map_err(|e| e.to_string())?;
+        let runs = save_format::decode_runs(&data)
+            .map_err(|e| format!("Failed to read import inbox {}: {e}", path.display()))?
+            .into_iter()
+            .filter_map(|run| match run {
+                RunEnum::Level(run) => Some(run),
+                RunEnum::Game(_) => None,
+            })
+            .collect();
+
+        Ok((runs, ledger))
+    }
+
+    /// the log files are only in the ledger once their runs are saved, the
+    /// inbox is kept on disk so the runs are not parsed again after a restart.
+    ///
+    /// an inbox that could not be read is moved aside instead of saved over.
+    fn save_inbox(&mut self) -> Result<(), String> {
+        let path = Self::get_inbox_path(INBOX_FILE_NAME)?;
+        let ledger_path = Self::get_inbox_path(INBOX_LEDGER_FILE_NAME)?;
+
+        if self.unreadable && path.exists() {
+            file_backup::keep_unreadable(&path)?;
+        }
+        self.unreadable = false;
+
+        if self.inbox.is_empty() {
+            let _ = std::fs::remove_file(&path);
+            let _ = std::fs::remove_file(&ledger_path);
+            return Ok(());
+        }
+
+        let runs: Vec<RunEnum> = self.inbox.iter().cloned().map(RunEnum::Level).collect();
+        let data = save_format::encode_runs(&runs, SaveType::Binary)?;
+
+        file_backup::write_atomic(&path, &data)?;
+        self.pending_ledger.save_to(&ledger_path)
+    }
+
+    fn set_result(&mut self, result: Result<(), String>) {
+        if let Err(e) = &result {
+            eprintln!("Failed to save import inbox: {e}");
+        }
+        self.error = result.err();
+    }
+
+    /// why the inbox could not be read or saved.
+    pub fn get_error(&self) -> Option<&String> {
+        self.error.as_ref()
+    }
+
+    pub fn is_scanning(&self) -> bool {
+        self.job.is_some()
+    }
+
+    pub fn get_inbox_len(&self) -> usize {
+        self.inbox.len()
+    }
+
+    /// the runs to review, they stay in the inbox until they are saved or deleted.
+    pub fn get_inbox(&mut self) -> Vec<LevelRun> {
+        self.reviewing = self.inbox.len();
+
+        self.inbox.clone()
+    }
+
+    /// replaces the runs given for review with the ones still left, runs
+    /// found by a scan since are kept.
+    ///
+    /// once every run is saved or deleted the log files go in the ledger.
+    pub fn set_inbox(&mut self, runs: Vec<LevelRun>) {
+        let reviewing = self.reviewing.min(self.inbox.len());
+        self.reviewing = runs.len();
+        self.inbox.splice(..reviewing, runs);
+        if self.inbox.is_empty()
+            && let Err(e) = std::mem::take(&mut self.pending_ledger).commit()
+        {
+            eprintln!("Failed to save import ledger: {e}");
+        }
+
+        let result = self.save_inbox();
+        self.set_result(result);
+    }
+
+    /// finishes a running scan and starts a new one once the interval
+    /// passed, new scans only start while the app is idle.
+    pub fn update(&mut self, idle: bool) {
+        if let Some(job) = &mut self.job {
+            if !job.poll() {
+                return;
+            }
+
+            let mut job = self.job.take().unwrap();
+            if let Some(ledger) = job.take_ledger() {
+                self.pending_ledger.merge(ledger);
+            }
+
+            let runs: Vec<LevelRun> = job.into();
+            let count = self.inbox.len();
+            for run in runs {
+                if !self.inbox.contains(&run) {
+                    self.inbox.push(run);
+                }
+            }
+
+            if self.inbox.len() != count {
+                let result = self.save_inbox();
+                self.set_result(result);
+            }
+
+            return;
+        }
+
+        if !idle || self.last_scan.is_some_and(|v| v.elapsed() < self.interval) {
+            return;
+        }
+
+        self.last_scan = Some(Instant::now());
+        let files = logs_folder::list_log_files(&self.logs_path);
+        if !files.is_empty() {
+            // the files of the inbox are not parsed again.
+            let mut ledger = ImportLedger::load();
+            ledger.merge(self.pending_ledger.clone());
+
+            self.job = Some(AwaitParseFiles::with_ledger(files, self.workers, ledger));
+        }
+    }
+}
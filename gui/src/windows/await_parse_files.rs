@@ -1,25 +1,43 @@
-use core::run::timed_run::LevelRun;
+use core::{
+    import_ledger::{FileFingerprint, FileState, ImportLedger, ImportSummary, LedgerEntry},
+    import_report::{FileStatus, ImportReport},
+    log_parser,
+    run::timed_run::LevelRun,
+};
 use std::{
     path::PathBuf,
     sync::{
         Arc, Mutex,
-        atomic::AtomicUsize,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         mpsc::{self, Receiver},
     },
     thread::{self, JoinHandle}, time::{Duration, Instant},
 };
 
-use egui::ProgressBar;
+use egui::{Color32, ProgressBar};
 
-use crate::{dll::parse_files::parse_runs, render::Render};
+use crate::render::Render;
 
-const MAX_THREAD: usize = 8;
+struct ParsedFile {
+    path: PathBuf,
+    state: Option<FileState>,
+    fingerprint: Option<FileFingerprint>,
+    /// an unchanged file that was touched, recorded again so it is not hashed next time.
+    refreshed: Option<LedgerEntry>,
+    status: FileStatus,
+}
 
 pub struct AwaitParseFiles {
     runs_collected: Vec<LevelRun>,
     receiver: Receiver<LevelRun>,
+    file_receiver: Receiver<ParsedFile>,
+
+    ledger: Option<ImportLedger>,
+    summary: ImportSummary,
+    report: ImportReport,
 
     left: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
     len: usize,
     started_instance: Instant,
 
@@ -27,22 +45,78 @@ pub struct AwaitParseFiles {
 }
 
 impl AwaitParseFiles {
-    pub fn new(mut paths: Vec<PathBuf>) -> Self {
+    pub fn new(paths: Vec<PathBuf>, workers: usize) -> Self {
+        Self::start(paths, workers, None)
+    }
+
+    /// files already in the ledger and not changed since are skipped, the
+    /// files parsed are kept in a pending ledger until their runs are saved.
+    pub fn with_ledger(paths: Vec<PathBuf>, workers: usize, ledger: ImportLedger) -> Self {
+        Self::start(paths, workers, Some(ledger))
+    }
+
+    fn parse_file(ledger: Option<&ImportLedger>, path: PathBuf, sender: &mpsc::Sender<LevelRun>) -> ParsedFile {
+        let mut file = ParsedFile {
+            state: None,
+            fingerprint: None,
+            refreshed: None,
+            status: FileStatus::Parsed(0),
+            path,
+        };
+
+        if let Err(status) = ImportReport::check_log_file(&file.path) {
+            file.status = status;
+            return file;
+        }
+
+        match ledger.map(|v| v.check(&file.path)) {
+            Some(Ok((FileState::Unchanged, fingerprint))) => {
+                file.refreshed = ledger.and_then(|v| v.refreshed(&file.path, fingerprint));
+                file.state = Some(FileState::Unchanged);
+                file.status = FileStatus::Skipped("already imported".to_owned());
+                return file;
+            }
+            Some(Ok((state, fingerprint))) => {
+                file.state = Some(state);
+                file.fingerprint = Some(fingerprint);
+            }
+            Some(Err(e)) => {
+                file.status = FileStatus::Failed(e);
+                return file;
+            }
+            None => file.state = Some(FileState::New),
+        }
+
+        let runs = log_parser::parse_file(&file.path);
+        file.status = FileStatus::from_runs(runs.len());
+        for run in runs {
+            let _ = sender.send(run);
+        }
+
+        file
+    }
+
+    fn start(paths: Vec<PathBuf>, workers: usize, ledger: Option<ImportLedger>) -> Self {
         let (sender, recv) = mpsc::channel();
-        paths.retain(|path| 
-            path.extension().is_some_and(|e| e == "txt")
-        );
-        
+        let (file_sender, file_recv) = mpsc::channel();
+        let pending = ledger.as_ref().map(|_| ImportLedger::default());
+        let shared_ledger = Arc::new(ledger);
+        let cancelled = Arc::new(AtomicBool::new(false));
+
         let len = paths.len();
         let left = Arc::new(AtomicUsize::new(len));
 
         let paths_arc = Arc::new(Mutex::new(paths.into_iter()));
-        let mut threads = Vec::with_capacity(MAX_THREAD);
+        let workers = workers.max(1);
+        let mut threads = Vec::with_capacity(workers);
 
-        for _ in 0..MAX_THREAD {
+        for _ in 0..workers {
             let paths_clone = paths_arc.clone();
             let sender_clone = sender.clone();
+            let file_sender_clone = file_sender.clone();
             let left_clone = left.clone();
+            let ledger_clone = shared_ledger.clone();
+            let cancelled_clone = cancelled.clone();
 
             threads.push(thread::spawn(move || {
                 loop {
@@ -66,17 +140,35 @@ impl AwaitParseFiles {
                         return;
                     }
 
-                    let len_parsed = files.len();
-                    parse_runs(files, &sender_clone);
-                    left_clone.fetch_sub(len_parsed, std::sync::atomic::Ordering::Relaxed);
+                    for path in files {
+                        // the files left are still reported so the progress reaches the end.
+                        let file = match cancelled_clone.load(Ordering::Relaxed) {
+                            true => ParsedFile {
+                                path,
+                                state: None,
+                                fingerprint: None,
+                                refreshed: None,
+                                status: FileStatus::Cancelled,
+                            },
+                            false => Self::parse_file(ledger_clone.as_ref().as_ref(), path, &sender_clone),
+                        };
+
+                        let _ = file_sender_clone.send(file);
+                        left_clone.fetch_sub(1, Ordering::Relaxed);
+                    }
                 }
             }));
         }
 
         Self {
             receiver: recv,
+            file_receiver: file_recv,
+            ledger: pending,
+            summary: ImportSummary::default(),
+            report: ImportReport::default(),
             runs_collected: Vec::new(),
             left,
+            cancelled,
             len,
             started_instance: Instant::now(),
             join_handle: threads,
@@ -84,7 +176,7 @@ impl AwaitParseFiles {
     }
 
     pub fn get_left(&self) -> usize {
-        self.left.load(std::sync::atomic::Ordering::Acquire)
+        self.left.load(Ordering::Acquire)
     }
 
     pub fn get_len(&self) -> usize {
@@ -99,10 +191,67 @@ impl AwaitParseFiles {
         self.get_left() == 0
     }
 
+    /// files not started yet are reported as cancelled, the runs found so far are kept.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// collects what the workers found, returns true once every file is parsed.
+    pub fn poll(&mut self) -> bool {
+        self.collect();
+        if !self.is_done() {
+            return false;
+        }
+
+        for jh in self.join_handle.drain(0..self.join_handle.len()) {
+            let _ = jh.join();
+        }
+        self.collect();
+
+        true
+    }
+
+    /// the files parsed by this import, to commit once their runs are saved.
+    pub fn take_ledger(&mut self) -> Option<ImportLedger> {
+        self.ledger.take()
+    }
+
+    pub fn get_summary(&self) -> ImportSummary {
+        self.summary
+    }
+
+    pub fn get_report(&self) -> &ImportReport {
+        &self.report
+    }
+
     pub fn collect(&mut self) {
         while let Ok(r) = self.receiver.try_recv() {
             self.runs_collected.push(r);
         }
+
+        while let Ok(file) = self.file_receiver.try_recv() {
+            if let Some(state) = file.state {
+                self.summary.add(state);
+            }
+
+            if let Some(ledger) = &mut self.ledger
+                && let Some(fingerprint) = file.fingerprint
+                && let FileStatus::Parsed(runs) = file.status
+            {
+                ledger.record(&file.path, fingerprint, runs);
+            }
+            if let Some(ledger) = &mut self.ledger
+                && let Some(entry) = file.refreshed
+            {
+                ledger.record(&file.path, entry.fingerprint, entry.runs);
+            }
+
+            self.report.add(file.path, file.status);
+        }
     }
 }
 
@@ -128,19 +277,25 @@ impl Render for AwaitParseFiles {
             ui.add(ProgressBar::new(
                 (self.get_len() - self.get_left()) as f32 / self.get_len() as f32,
             ));
-        });
 
-        self.collect();
-        if self.is_done() {
-            for jh in self.join_handle.drain(0..self.join_handle.len()) {
-                let _ = jh.join();
+            if self.ledger.is_some() {
+                ui.label(format!("Files: {}", self.summary));
             }
-            self.collect();
 
-            return true;
-        }
+            let failed = self.report.count(|v| v.is_failed());
+            if failed > 0 {
+                ui.colored_label(Color32::RED, format!("Failed files: {failed}"));
+            }
+
+            match self.is_cancelled() {
+                true => { ui.label("Cancelling..."); }
+                false => if ui.button("Cancel").clicked() {
+                    self.cancel();
+                },
+            }
+        });
 
-        false
+        self.poll()
     }
 }
 
@@ -0,0 +1,231 @@
+use core::{
+    comparison::{Baseline, ComparedRun, Comparison, format_delta},
+    save_manager::SaveManager,
+};
+
+use egui::{Align2, Color32, FontId, Pos2, Sense, Shape, Stroke, Ui, vec2};
+use glr_core::split::Split;
+
+const GRAPH_HEIGHT: f32 = 240.0;
+const GRAPH_MARGIN: f32 = 48.0;
+
+/// colors of the runs in the graph, repeated when there are more runs.
+const RUN_COLORS: [Color32; 8] = [
+    Color32::LIGHT_BLUE,
+    Color32::ORANGE,
+    Color32::LIGHT_GREEN,
+    Color32::from_rgb(230, 120, 230),
+    Color32::YELLOW,
+    Color32::LIGHT_RED,
+    Color32::from_rgb(120, 230, 230),
+    Color32::WHITE,
+];
+
+fn run_color(id: usize) -> Color32 {
+    RUN_COLORS[id % RUN_COLORS.len()]
+}
+
+fn delta_color(delta: i64) -> Color32 {
+    match delta {
+        ..0 => Color32::GREEN,
+        0 => Color32::GRAY,
+        _ => Color32::RED,
+    }
+}
+
+/// split by split tables and a cumulative delta graph of the selected runs.
+#[derive(Default)]
+pub struct ComparisonView {
+    baseline: Baseline,
+    show_cumulative: bool,
+}
+
+impl ComparisonView {
+    /// `selected` are indexes into the runs of the objective.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        save_manager: &SaveManager,
+        objective: &String,
+        selected: &[usize],
+    ) {
+        let Some(all_runs) = save_manager.get_runs(objective) else {
+            return;
+        };
+        let runs: Vec<_> = selected
+            .iter()
+            .filter_map(|id| all_runs.get(*id).map(|run| (*id, run)))
+            .enumerate()
+            .map(|(position, (id, run))| {
+                (format!("Run {} (#{}, {})", position + 1, id + 1, run.get_time()), run)
+            })
+            .collect();
+
+        if runs.is_empty() {
+            ui.label("Select runs to compare with the checkboxes of the table.");
+            return;
+        }
+
+        if let Baseline::Run(id) = self.baseline
+            && id >= runs.len()
+        {
+            self.baseline = Baseline::default();
+        }
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Compare against")
+                .selected_text(self.baseline.to_string())
+                .show_ui(ui, |ui| {
+                    for baseline in [Baseline::PersonalBest, Baseline::Gold] {
+                        ui.selectable_value(&mut self.baseline, baseline, baseline.to_string());
+                    }
+                    for (id, (name, _)) in runs.iter().enumerate() {
+                        ui.selectable_value(&mut self.baseline, Baseline::Run(id), name);
+                    }
+                });
+
+            ui.checkbox(&mut self.show_cumulative, "Show cumulative times");
+        });
+
+        let Some(comparison) = Comparison::from_runs(save_manager, objective, &runs, self.baseline)
+        else {
+            ui.colored_label(Color32::RED, format!("No {} to compare against.", self.baseline));
+            return;
+        };
+
+        egui::ScrollArea::both().id_salt("comparison").show(ui, |ui| {
+            self.render_table(ui, &comparison, save_manager, objective);
+            ui.separator();
+            Self::render_graph(ui, &comparison);
+        });
+    }
+
+    fn render_table(
+        &self,
+        ui: &mut Ui,
+        comparison: &Comparison,
+        save_manager: &SaveManager,
+        objective: &str,
+    ) {
+        let baseline = comparison.get_baseline();
+        let times = |run: &ComparedRun| match self.show_cumulative {
+            true => run.cumulative.clone(),
+            false => run.splits.clone(),
+        };
+
+        egui::Grid::new("comparison_table").striped(true).show(ui, |ui| {
+            ui.label("");
+            for name in comparison.get_split_names() {
+                ui.monospace(save_manager.get_split_display_name(objective, name));
+            }
+            ui.end_row();
+
+            ui.colored_label(Color32::GOLD, &baseline.name);
+            for time in times(baseline) {
+                ui.monospace(time.map(|v| v.to_string()).unwrap_or_default());
+            }
+            ui.end_row();
+
+            for (id, run) in comparison.get_runs().iter().enumerate() {
+                let deltas = match self.show_cumulative {
+                    true => comparison.get_deltas(id),
+                    false => comparison.get_split_deltas(id),
+                };
+
+                ui.colored_label(run_color(id), &run.name);
+                for (time, delta) in times(run).into_iter().zip(deltas) {
+                    ui.vertical(|ui| {
+                        ui.monospace(time.map(|v| v.to_string()).unwrap_or_default());
+                        if let Some(delta) = delta {
+                            ui.colored_label(delta_color(delta), format_delta(delta));
+                        }
+                    });
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    fn render_graph(ui: &mut Ui, comparison: &Comparison) {
+        let split_names = comparison.get_split_names();
+        if split_names.is_empty() {
+            return;
+        }
+
+        ui.label(format!("Cumulative delta against {}", comparison.get_baseline().name));
+
+        let width = ui.available_width().max(GRAPH_MARGIN * 4.0);
+        let (response, painter) = ui.allocate_painter(vec2(width, GRAPH_HEIGHT), Sense::hover());
+        let rect = response.rect.shrink2(vec2(GRAPH_MARGIN, 16.0));
+
+        // the range always holds 0 so the baseline line is drawn.
+        let (min, max) = comparison.get_delta_range();
+        let span = (max - min).max(1) as f32;
+        let x_of = |split: usize| match split_names.len() {
+            1 => rect.center().x,
+            len => rect.left() + rect.width() * split as f32 / (len - 1) as f32,
+        };
+        let y_of = |delta: i64| rect.bottom() - rect.height() * (delta - min) as f32 / span;
+        let font = FontId::monospace(11.0);
+
+        painter.rect_stroke(rect, 0.0, Stroke::new(1.0, Color32::DARK_GRAY));
+        painter.line_segment(
+            [Pos2::new(rect.left(), y_of(0)), Pos2::new(rect.right(), y_of(0))],
+            Stroke::new(1.0, Color32::GOLD),
+        );
+        for value in [max, min] {
+            painter.text(
+                Pos2::new(rect.left() - 4.0, y_of(value)),
+                Align2::RIGHT_CENTER,
+                format_delta(value),
+                font.clone(),
+                delta_color(value),
+            );
+        }
+
+        for id in 0..comparison.get_runs().len() {
+            let stroke = Stroke::new(2.0, run_color(id));
+
+            // a missing split breaks the line.
+            let mut line = Vec::new();
+            for (split, delta) in comparison.get_deltas(id).into_iter().enumerate() {
+                match delta {
+                    Some(delta) => {
+                        let point = Pos2::new(x_of(split), y_of(delta));
+                        painter.circle_filled(point, 2.5, stroke.color);
+                        line.push(point);
+                    }
+                    None => {
+                        painter.add(Shape::line(std::mem::take(&mut line), stroke));
+                    }
+                }
+            }
+            painter.add(Shape::line(line, stroke));
+        }
+
+        let Some(hover) = response.hover_pos() else {
+            return;
+        };
+        let split = (0..split_names.len())
+            .min_by(|a, b| (x_of(*a) - hover.x).abs().total_cmp(&(x_of(*b) - hover.x).abs()))
+            .unwrap_or_default();
+
+        painter.line_segment(
+            [Pos2::new(x_of(split), rect.top()), Pos2::new(x_of(split), rect.bottom())],
+            Stroke::new(1.0, Color32::GRAY),
+        );
+        response.on_hover_ui_at_pointer(|ui| {
+            ui.label(&split_names[split]);
+            for (id, run) in comparison.get_runs().iter().enumerate() {
+                let delta = comparison.get_deltas(id)[split]
+                    .map(format_delta)
+                    .unwrap_or("-".to_owned());
+                let time = run.cumulative[split]
+                    .map(|v| v.to_string())
+                    .unwrap_or("-".to_owned());
+
+                ui.colored_label(run_color(id), format!("{}: {time} {delta}", run.name));
+            }
+        });
+    }
+}
@@ -1,6 +1,6 @@
 use egui::Color32;
 
-use crate::{render::Render, windows::settings_window::SettingsWindow};
+use crate::{render::Render, settings::Settings};
 
 static ALL_CODES: &'static [u8] = include_bytes!("..\\..\\..\\..\\resources\\keys.txt");
 
@@ -13,16 +13,12 @@ pub struct CodeGuess {
 }
 
 impl CodeGuess {
-    pub fn new(settings_window: &SettingsWindow) -> Self {
+    pub fn new(settings: &Settings) -> Self {
         Self {
             selected: String::new(),
             valid: [true; 733],
-            max_lines: settings_window
-                .get::<i32>("code_guess_line_count")
-                .unwrap_or(3) as usize,
-            codes_per_line: settings_window
-                .get::<i32>("code_guess_word_count")
-                .unwrap_or(3) as usize,
+            max_lines: settings.mapper.code_guess_line_count as usize,
+            codes_per_line: settings.mapper.code_guess_word_count as usize,
         }
     }
 
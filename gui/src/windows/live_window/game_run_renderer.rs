@@ -1,101 +1,209 @@
-use core::{run::{objectives::game_objective::GameObjective, timed_run::LevelRun}, save_manager::{SaveManager}};
+use core::{
+    run::{
+        objectives::{game_objective::GameObjective, objective_enum::ObjectiveEnum},
+        timed_run::{GameRun, LevelRun, RunEnum},
+    },
+    save_manager::SaveManager,
+};
 
 use egui::Ui;
-use glr_core::run_gen_result::RunGeneratorResult;
+use glr_core::{run_gen_result::RunGeneratorResult, split::Split, time::Time};
 use glr_lib::dll_exports::enums::SubscribeCode;
 
-use crate::{dll::parse_continously::ContinousParser, render::Render, windows::{live_window::run_renderer::RunRender, settings_window::SettingsWindow}};
+use crate::{
+    dll::parse_continously::ContinousParser,
+    render::Render,
+    settings::Settings,
+    windows::live_window::{
+        overlay_server::{OverlayEvent, OverlayServer, OverlaySplitter},
+        run_renderer::RunRender,
+    },
+};
 
 
 pub struct GameRunRenderer {
     render: RunRender<LevelRun>,
     run_buffer: Vec<LevelRun>,
     objective: GameObjective,
+    level_count: usize,
+    missing_levels: usize,
+    theoretical_best: Option<Time>,
 
     continous_parser: ContinousParser<RunGeneratorResult>,
     no_save_for_frames: usize,
 }
 
 impl GameRunRenderer {
-    pub fn new(settings: &SettingsWindow, save_manager: &SaveManager, objective: GameObjective) -> Self {
-        if let Some(result) = Self::load_data(
-            settings,
-            save_manager,
-            &objective
-        ) {
-            return result
-        }
-        
-        Self {
+    pub fn new(settings: &Settings, save_manager: &SaveManager, objective: GameObjective) -> Self {
+        let run_buffer = Self::load_data().unwrap_or_default();
+        let mut result = Self {
             render: RunRender::new(objective.to_string(), settings),
             run_buffer: Vec::new(),
+            level_count: objective.get_levels().len(),
+            missing_levels: 0,
+            theoretical_best: save_manager.get_theoretical_best_game(&objective),
             objective,
             continous_parser: ContinousParser::new(SubscribeCode::RunInfo),
             no_save_for_frames: 5,
+        };
+
+        for run in run_buffer {
+            result.add_level(run, save_manager);
         }
+        result.update_missing();
+
+        result
     }
 
-    fn load_data(
-        settings: &SettingsWindow, 
-        save_manager: &SaveManager, 
-        objective: &GameObjective
-    ) -> Option<Self> {
+    fn load_data() -> Option<Vec<LevelRun>> {
         let path = SaveManager::get_config_directory()?
             .join("game_run_buffer.json");
         let text_buf = std::fs::read_to_string(path)
             .ok()?;
-        let inner = serde_json::from_str::<Vec<LevelRun>>(&text_buf)
-            .ok()?;
-        let mut run_render = RunRender::new(objective.to_string(), settings);
-
-        for item in &inner {
-            run_render.add_split(item, save_manager);
-        }
 
-        Some(
-            Self {
-                render: run_render,
-                continous_parser: ContinousParser::new(SubscribeCode::RunInfo),
-                no_save_for_frames: 5,
-                run_buffer: inner,
-                objective: objective.clone(),
-            }
-        )
+        serde_json::from_str::<Vec<LevelRun>>(&text_buf).ok()
     }
 
+    /// keeps the levels already done so a restart of the app does not lose the run.
     pub fn save_data(&self) -> Option<()> {
-        let path = SaveManager::get_config_directory()?
-            .join("game_run_buffer.json");
+        let directory = SaveManager::get_config_directory()?;
+        let path = directory.join("game_run_buffer.json");
+
+        if self.run_buffer.is_empty() {
+            let _ = std::fs::remove_file(&path);
+            return Some(());
+        }
+
         let text = serde_json::to_string_pretty(&self.run_buffer)
             .ok()?;
 
-        let _ = std::fs::create_dir_all(&path);
+        let _ = std::fs::create_dir_all(&directory);
         let _ = std::fs::write(&path, text);
 
         Some(())
     }
 
+    fn add_level(&mut self, run: LevelRun, save_manager: &SaveManager) {
+        self.render.add_split(&run, save_manager);
+        self.run_buffer.push(run);
+    }
+
+    fn update_missing(&mut self) {
+        self.missing_levels = self
+            .objective
+            .get_missing_levels(self.run_buffer.iter())
+            .len();
+    }
+
+    fn publish(&self, overlay: Option<&OverlayServer>, event: Option<OverlayEvent>) {
+        if let Some(server) = overlay {
+            server.publish(OverlaySplitter::Game, event.as_ref(), (&self.render).into());
+        }
+    }
+
+    /// throws away the levels done so far.
+    pub fn reset(&mut self, settings: &Settings, overlay: Option<&OverlayServer>) {
+        self.render = RunRender::new(self.objective.to_string(), settings);
+        self.run_buffer.clear();
+        self.update_missing();
+        self.save_data();
+        self.publish(overlay, None);
+    }
+
+    /// saves the game run once every level of the objective is finished.
+    fn finish_if_complete(&mut self, save_manager: &mut SaveManager, overlay: Option<&OverlayServer>) {
+        if self.missing_levels > 0 {
+            return;
+        }
+
+        let mut game_run = GameRun::new(ObjectiveEnum::Game(self.objective.clone()));
+        for run in self.run_buffer.drain(..) {
+            game_run.add_split(run);
+        }
+
+        let is_win = game_run.validate();
+        self.publish(
+            overlay,
+            Some(OverlayEvent::RunEnded {
+                objective: self.objective.to_string(),
+                time: game_run.get_time().to_string(),
+                is_win,
+            }),
+        );
+
+        if is_win {
+            save_manager.save(RunEnum::Game(game_run));
+        }
+
+        self.update_missing();
+        self.save_data();
+    }
+
     pub fn render(
-        &mut self, 
+        &mut self,
         save_manager: &mut SaveManager,
-        settings: &SettingsWindow,
+        settings: &Settings,
+        overlay: Option<&OverlayServer>,
         ui: &mut Ui,
     ) -> usize {
         self.no_save_for_frames = self.no_save_for_frames.saturating_sub(1);
 
         while let Some(r) = self.continous_parser.try_recv() {
-            match r {
-                RunGeneratorResult::LevelRun(timed_run) => {
-                    let run = timed_run.into();
-                    
-                    self.render.add_split(&run, save_manager);
-                    self.run_buffer.push(run);
+            if let RunGeneratorResult::LevelRun(timed_run) = r {
+                let run: LevelRun = timed_run.into();
+                let is_part_of_game = self
+                    .objective
+                    .get_levels()
+                    .iter()
+                    .any(|level| GameObjective::completes_level(level, &run));
+
+                if self.no_save_for_frames > 0 || !is_part_of_game {
+                    continue;
                 }
-                _ => {}
+
+                // a finished run on screen is cleared by the first level of the next one.
+                if self.run_buffer.is_empty() {
+                    self.render = RunRender::new(self.objective.to_string(), settings);
+                    self.publish(
+                        overlay,
+                        Some(OverlayEvent::RunStarted {
+                            objective: self.objective.to_string(),
+                        }),
+                    );
+                }
+
+                let event = OverlayEvent::SplitAdded {
+                    name: run.get_name().to_owned(),
+                    time: run.get_time().to_string(),
+                };
+                self.add_level(run, save_manager);
+                self.update_missing();
+                self.theoretical_best = save_manager.get_theoretical_best_game(&self.objective);
+                self.save_data();
+                self.publish(overlay, Some(event));
+                self.finish_if_complete(save_manager, overlay);
             }
         }
 
-        self.render.render(ui)
+        let mut result = self.render.render(ui);
+
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "Levels: {}/{}",
+                self.level_count - self.missing_levels,
+                self.level_count,
+            ));
+
+            if let Some(theoretical_best) = self.theoretical_best {
+                ui.label(format!("Sum of best: {theoretical_best}"));
+            }
+
+            if ui.button("Reset").clicked() {
+                self.reset(settings, overlay);
+            }
+        });
+        result += 22;
+
+        result
     }
 }
-
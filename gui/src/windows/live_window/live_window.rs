@@ -1,19 +1,24 @@
-use core::save_manager::SaveManager;
+use core::{logs_folder, save_manager::SaveManager};
+use std::path::{Path, PathBuf};
+
+use egui::Color32;
 
 use crate::{
     render::Render,
     windows::{
         live_window::{
             code_guess::CodeGuess,
+            game_run_renderer::GameRunRenderer,
             mapper::Mapper,
+            overlay_server::OverlayServer,
             objective_reader::{LevelObjectiveReader, UpdateObjective},
             run_counter::RunCounter,
             run_renderer::LevelRunRenderer,
             seed_indexer::SeedIndexer,
             timer::Timer,
         },
-        settings_window::SettingsWindow,
     },
+    settings::Settings,
 };
 
 #[derive(Default)]
@@ -26,6 +31,11 @@ pub struct LiveWindow {
 
     objective_reader: Option<LevelObjectiveReader>,
     run_renderer: Option<LevelRunRenderer>,
+    game_renderer: Option<GameRunRenderer>,
+    overlay_server: Option<Box<OverlayServer>>,
+
+    logs_error: Option<String>,
+    logs_warning: Option<String>,
 }
 
 impl LiveWindow {
@@ -59,6 +69,12 @@ impl LiveWindow {
         self
     }
 
+    pub fn with_game_renderer(mut self, game_renderer: GameRunRenderer) -> Self {
+        self.game_renderer = Some(game_renderer);
+
+        self
+    }
+
     pub fn with_real_timer(mut self, timer: Timer) -> Self {
         self.real_timer = Some(Box::new(timer));
 
@@ -71,18 +87,22 @@ impl LiveWindow {
         self
     }
 
-    pub fn new(mut obj_reader: Option<LevelObjectiveReader>, settings: &SettingsWindow) -> Self {
+    pub fn new(
+        mut obj_reader: Option<LevelObjectiveReader>,
+        settings: &Settings,
+        save_manager: &SaveManager,
+    ) -> Self {
         let mut result = Self::default();
 
-        if settings.get_def("show_real_timer") {
+        if settings.general.show_real_timer {
             result = result.with_real_timer(Timer::default());
         }
 
-        if settings.get_def("show_run_counter") {
+        if settings.general.show_run_counter {
             result = result.with_run_counter(RunCounter::default());
         }
 
-        if settings.get_def("show_mapper") {
+        if settings.mapper.show_mapper {
             result = result
                 .with_mapper(Mapper::new(&settings, "".to_string()));
             if let Some(reader) = obj_reader.take() {
@@ -94,15 +114,15 @@ impl LiveWindow {
             }
         };
 
-        if settings.get_def("show_foresight") {
+        if settings.foresight.show_foresight {
             result = result.with_indexer(SeedIndexer::new(&settings));
         }
 
-        if settings.get_def("show_code_guess") {
+        if settings.mapper.show_code_guess {
             result = result.with_code_guesser(CodeGuess::new(&settings));
         }
 
-        if settings.get_def("show_run_splitter") {
+        if settings.general.show_run_splitter {
             result = result
                 .with_run_renderer(LevelRunRenderer::new(settings));
             if let Some(reader) = obj_reader.take() {
@@ -114,21 +134,80 @@ impl LiveWindow {
             }
         }
 
-        let file_path = settings.get_path("logs_path").unwrap().clone();
+        if settings.general.show_game_splitter {
+            result = result.with_game_renderer(GameRunRenderer::new(
+                settings,
+                save_manager,
+                settings.splitter.game_objective.clone(),
+            ));
+        }
 
-        glr_lib::dll_exports::functions::start_listener(file_path);
+        if result.run_renderer.is_some() || result.game_renderer.is_some() {
+            result.overlay_server = Self::start_overlay_server(settings);
+        }
+
+        match Self::find_logs_folder(&settings.splitter.logs_path) {
+            Ok((file_path, warning)) => {
+                result.logs_warning = warning;
+                glr_lib::dll_exports::functions::start_listener(file_path)
+            }
+            Err(e) => result.logs_error = Some(e),
+        }
 
         result
     }
 
+    /// shared by the level and the game splitter, each one sends its own snapshot.
+    fn start_overlay_server(settings: &Settings) -> Option<Box<OverlayServer>> {
+        if !settings.splitter.overlay_server {
+            return None;
+        }
+
+        let port = settings.splitter.overlay_port.min(u16::MAX as u32) as u16;
+        match OverlayServer::start(port) {
+            Ok(server) => Some(Box::new(server)),
+            Err(e) => {
+                eprintln!("Failed to start overlay server on port {port}: {e}");
+                None
+            }
+        }
+    }
+
+    /// the folder from the settings, or the best detected one if that one is not usable.
+    ///
+    /// the warning says why the folder from the settings is not the one used.
+    fn find_logs_folder(path: &Path) -> Result<(PathBuf, Option<String>), String> {
+        let error = match logs_folder::validate_logs_folder(path) {
+            Ok(_) => return Ok((path.to_path_buf(), None)),
+            Err(e) => e,
+        };
+
+        match logs_folder::find_logs_folders().into_iter().find(|v| v.is_valid()) {
+            Some(candidate) => {
+                let warning = format!("{error} Using {} instead.", candidate.path.display());
+                Ok((candidate.path, Some(warning)))
+            }
+            None => Err(format!("{error} No other GTFO logs folder was found.")),
+        }
+    }
+
     pub fn render(
         &mut self,
         ui: &mut egui::Ui,
         save_manager: &mut SaveManager,
-        settings: &SettingsWindow,
+        settings: &Settings,
     ) -> usize {
         let mut result = 20;
 
+        if let Some(error) = &self.logs_error {
+            ui.colored_label(Color32::RED, error);
+            result += 22;
+        }
+        if let Some(warning) = &self.logs_warning {
+            ui.colored_label(Color32::YELLOW, warning);
+            result += 22;
+        }
+
         result += self.run_counter.render(ui).unwrap_or_default();
         result += self.real_timer.render(ui).unwrap_or_default();
         result += self.seed_indexer.render(ui).unwrap_or_default();
@@ -149,7 +228,15 @@ impl LiveWindow {
         result += self
             .run_renderer
             .as_mut()
-            .map(|v| v.render(save_manager, settings, &self.objective_reader, ui))
+            .map(|v| {
+                let overlay = self.overlay_server.as_deref();
+                v.render(save_manager, settings, &self.objective_reader, overlay, ui)
+            })
+            .unwrap_or_default();
+        result += self
+            .game_renderer
+            .as_mut()
+            .map(|v| v.render(save_manager, settings, self.overlay_server.as_deref(), ui))
             .unwrap_or_default();
 
         result
@@ -12,10 +12,8 @@ use ron::de::SpannedError;
 use crate::{
     dll::parse_continously::ContinousParser,
     render::Render,
-    windows::{
-        live_window::objective_reader::{ObjectiveReader, UpdateObjective},
-        settings_window::SettingsWindow,
-    },
+    settings::Settings,
+    windows::live_window::objective_reader::{ObjectiveReader, UpdateObjective},
 };
 
 use super::mapper_view::{LevelView, LookUpColor, OptimizedLevelView};
@@ -195,14 +193,14 @@ pub struct Mapper {
 }
 
 impl Mapper {
-    pub fn new(settings_window: &SettingsWindow, objective: String) -> Self {
+    pub fn new(settings: &Settings, objective: String) -> Self {
         Self {
             continous_parser: ContinousParser::new(SubscribeCode::Mapper),
             level_objective: objective,
             location_colors: Default::default(),
             locations: Default::default(),
             locations_copy: VecDeque::new(),
-            show_objectives: settings_window.get_def("show_objectives"),
+            show_objectives: settings.mapper.show_objectives,
             key_len: 0,
         }
     }
@@ -5,8 +5,9 @@ mod code_guess;
 mod foresight_view;
 mod mapper;
 mod mapper_view;
+mod overlay_server;
 mod run_counter;
 mod run_renderer;
-// mod game_run_renderer;
+mod game_run_renderer;
 mod seed_indexer;
 mod timer;
@@ -0,0 +1,375 @@
+use core::comparison;
+use std::{
+    collections::VecDeque,
+    io::{Read, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use glr_core::split::Split;
+use serde::Serialize;
+use tungstenite::{Message, WebSocket};
+
+use crate::windows::live_window::run_renderer::RunRender;
+
+/// things that happened in the live splitter since the last message.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OverlayEvent {
+    RunStarted { objective: String },
+    SplitAdded { name: String, time: String },
+    RunEnded { objective: String, time: String, is_win: bool },
+    ObjectiveChanged { objective: String },
+}
+
+/// which splitter of the live window sent the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlaySplitter {
+    Level,
+    Game,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeltaState {
+    Ahead,
+    Behind,
+    Even,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OverlayDelta {
+    /// the run minus the comparison in milliseconds, negative is ahead.
+    pub millis: i64,
+    pub text: String,
+    pub state: DeltaState,
+}
+
+impl OverlayDelta {
+    pub fn new(millis: i64) -> Self {
+        let state = match millis {
+            ..0 => DeltaState::Ahead,
+            0 => DeltaState::Even,
+            _ => DeltaState::Behind,
+        };
+
+        Self {
+            millis,
+            text: comparison::format_delta(millis),
+            state,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OverlaySplit {
+    pub name: String,
+    pub time: String,
+    pub record_delta: Option<OverlayDelta>,
+    pub best_delta: Option<OverlayDelta>,
+}
+
+/// everything a splitter currently shows.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct OverlaySnapshot {
+    pub objective: String,
+    pub total_time: String,
+    pub splits: Vec<OverlaySplit>,
+}
+
+impl<T: Split> From<&RunRender<T>> for OverlaySnapshot {
+    fn from(value: &RunRender<T>) -> Self {
+        let delta = |deltas: &Option<VecDeque<Option<i64>>>, id: usize| {
+            deltas
+                .as_ref()
+                .and_then(|v| v.get(id).copied().flatten())
+                .map(OverlayDelta::new)
+        };
+
+        let splits = (0..value.split_labels.len())
+            .map(|id| OverlaySplit {
+                name: value
+                    .split_names
+                    .as_ref()
+                    .and_then(|v| v.get(id).cloned())
+                    .or_else(|| value.run_buffer.get(id).map(|v| v.get_name().to_owned()))
+                    .unwrap_or_default(),
+                time: value.split_labels[id].clone(),
+                record_delta: delta(&value.compared_wr, id),
+                best_delta: delta(&value.compared_best, id),
+            })
+            .collect();
+
+        Self {
+            objective: value.objective_str.clone(),
+            total_time: value.total_time.to_string(),
+            splits,
+        }
+    }
+}
+
+/// the snapshot of every splitter, `None` if the splitter is not shown.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct OverlayState {
+    pub level: Option<OverlaySnapshot>,
+    pub game: Option<OverlaySnapshot>,
+}
+
+impl OverlayState {
+    fn set(&mut self, splitter: OverlaySplitter, snapshot: OverlaySnapshot) {
+        match splitter {
+            OverlaySplitter::Level => self.level = Some(snapshot),
+            OverlaySplitter::Game => self.game = Some(snapshot),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct OverlayMessage<'a> {
+    splitter: Option<OverlaySplitter>,
+    event: Option<&'a OverlayEvent>,
+    snapshot: &'a OverlayState,
+}
+
+type Clients = Arc<Mutex<Vec<WebSocket<TcpStream>>>>;
+
+/// local server for stream overlays.
+///
+/// a plain http request gets the latest snapshot as json, a websocket
+/// connection gets the latest snapshot and then every event with the
+/// snapshot after it.
+pub struct OverlayServer {
+    state: Mutex<OverlayState>,
+    latest: Arc<Mutex<String>>,
+    sender: Option<Sender<String>>,
+    stop: Arc<AtomicBool>,
+
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl OverlayServer {
+    /// only listens on localhost.
+    pub fn start(port: u16) -> Result<Self, String> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port)).map_err(|e| e.to_string())?;
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+        let latest = Arc::new(Mutex::new(Self::to_json(None, None, &OverlayState::default())));
+        let clients: Clients = Arc::new(Mutex::new(Vec::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, recv) = mpsc::channel();
+
+        let accept_thread = {
+            let latest = latest.clone();
+            let clients = clients.clone();
+            let stop = stop.clone();
+
+            thread::spawn(move || Self::accept_loop(listener, latest, clients, stop))
+        };
+        let broadcast_thread = thread::spawn(move || Self::broadcast_loop(recv, clients));
+
+        Ok(Self {
+            state: Mutex::new(OverlayState::default()),
+            latest,
+            sender: Some(sender),
+            stop,
+            threads: vec![accept_thread, broadcast_thread],
+        })
+    }
+
+    /// replaces the snapshot of the splitter, the one of the other splitter is sent along.
+    pub fn publish(
+        &self,
+        splitter: OverlaySplitter,
+        event: Option<&OverlayEvent>,
+        snapshot: OverlaySnapshot,
+    ) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.set(splitter, snapshot);
+
+        if let Ok(mut latest) = self.latest.lock() {
+            *latest = Self::to_json(None, None, &state);
+        }
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(Self::to_json(Some(splitter), event, &state));
+        }
+    }
+
+    fn to_json(
+        splitter: Option<OverlaySplitter>,
+        event: Option<&OverlayEvent>,
+        snapshot: &OverlayState,
+    ) -> String {
+        serde_json::to_string(&OverlayMessage {
+            splitter,
+            event,
+            snapshot,
+        })
+        .unwrap_or_default()
+    }
+
+    fn accept_loop(listener: TcpListener, latest: Arc<Mutex<String>>, clients: Clients, stop: Arc<AtomicBool>) {
+        while !stop.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let latest = latest.lock().map(|v| v.clone()).unwrap_or_default();
+                    if let Err(e) = Self::handle_connection(stream, latest, &clients) {
+                        eprintln!("Overlay connection failed: {e}");
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    thread::sleep(Duration::from_millis(50));
+                }
+                Err(e) => {
+                    eprintln!("Overlay server stopped: {e}");
+                    return;
+                }
+            }
+        }
+    }
+
+    fn handle_connection(stream: TcpStream, latest: String, clients: &Clients) -> Result<(), String> {
+        stream.set_nonblocking(false).map_err(|e| e.to_string())?;
+        stream.set_read_timeout(Some(Duration::from_secs(1))).map_err(|e| e.to_string())?;
+        stream.set_write_timeout(Some(Duration::from_secs(1))).map_err(|e| e.to_string())?;
+
+        let head = Self::peek_head(&stream)?;
+        if head.to_ascii_lowercase().contains("upgrade: websocket") {
+            let mut socket = tungstenite::accept(stream).map_err(|e| e.to_string())?;
+            socket.send(Message::text(latest)).map_err(|e| e.to_string())?;
+
+            if let Ok(mut clients) = clients.lock() {
+                clients.push(socket);
+            }
+
+            return Ok(());
+        }
+
+        Self::respond_http(stream, head.len(), &latest)
+    }
+
+    /// reads the request head without consuming it, the websocket
+    /// handshake needs to read it again.
+    fn peek_head(stream: &TcpStream) -> Result<String, String> {
+        let mut buffer = [0u8; 4096];
+
+        for _ in 0..20 {
+            let len = stream.peek(&mut buffer).map_err(|e| e.to_string())?;
+            let text = String::from_utf8_lossy(&buffer[..len]);
+
+            if let Some(end) = text.find("\r\n\r\n") {
+                return Ok(text[..end + 4].to_owned());
+            }
+            if len == buffer.len() {
+                break;
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        Err("Invalid request".to_owned())
+    }
+
+    fn respond_http(mut stream: TcpStream, head_len: usize, body: &str) -> Result<(), String> {
+        let mut head = vec![0u8; head_len];
+        stream.read_exact(&mut head).map_err(|e| e.to_string())?;
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\n\
+            Content-Type: application/json\r\n\
+            Access-Control-Allow-Origin: *\r\n\
+            Content-Length: {}\r\n\
+            Connection: close\r\n\r\n{body}",
+            body.len()
+        );
+
+        stream.write_all(response.as_bytes()).map_err(|e| e.to_string())
+    }
+
+    fn broadcast_loop(recv: Receiver<String>, clients: Clients) {
+        while let Ok(json) = recv.recv() {
+            if let Ok(mut clients) = clients.lock() {
+                clients.retain_mut(|socket| socket.send(Message::text(json.clone())).is_ok());
+            }
+        }
+    }
+}
+
+impl Drop for OverlayServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.sender.take();
+
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glr_core::{split::NamedSplit, time::Time};
+    use serde_json::json;
+
+    use crate::{settings::Settings, windows::live_window::run_renderer::RunRender};
+
+    use super::*;
+
+    #[test]
+    pub fn test_overlay_message() {
+        let mut render: RunRender<NamedSplit> =
+            RunRender::new("R1A1_4p".to_owned(), &Settings::default());
+        let time = Time::from("00:01:30.000").unwrap();
+        render.run_buffer.push(NamedSplit::new(time, "door_1".to_owned()));
+        render.split_labels.push_back(time.to_string());
+        render.compared_wr.as_mut().unwrap().push_back(Some(-1500));
+        render.compared_best.as_mut().unwrap().push_back(None);
+        render.total_time = time;
+
+        let snapshot = OverlaySnapshot::from(&render);
+        assert_eq!(snapshot.splits[0].record_delta, Some(OverlayDelta::new(-1500)));
+        assert_eq!(snapshot.splits[0].best_delta, None);
+
+        let mut state = OverlayState::default();
+        state.set(OverlaySplitter::Level, snapshot);
+        let event = OverlayEvent::SplitAdded {
+            name: "door_1".to_owned(),
+            time: time.to_string(),
+        };
+
+        let json = OverlayServer::to_json(Some(OverlaySplitter::Level), Some(&event), &state);
+        let message: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            message,
+            json!({
+                "splitter": "level",
+                "event": { "type": "split_added", "name": "door_1", "time": time.to_string() },
+                "snapshot": {
+                    "level": {
+                        "objective": "R1A1_4p",
+                        "total_time": time.to_string(),
+                        "splits": [{
+                            "name": "door_1",
+                            "time": time.to_string(),
+                            "record_delta": { "millis": -1500, "text": "-00:01.500", "state": "ahead" },
+                            "best_delta": null,
+                        }],
+                    },
+                    "game": null,
+                },
+            })
+        );
+
+        assert_eq!(OverlayDelta::new(0).state, DeltaState::Even);
+        assert_eq!(OverlayDelta::new(250).text, "+00:00.250");
+        assert_eq!(OverlayDelta::new(250).state, DeltaState::Behind);
+    }
+}
@@ -1,6 +1,10 @@
 use core::{
+    comparison,
+    livesplit_server::LiveSplitClient,
     run::{
+        metadata::RunMetadata,
         objectives::run_objective::RunObjective,
+        rules::RuleSet,
         timed_run::{LevelRun, RunEnum},
         traits::Run,
     },
@@ -13,6 +17,7 @@ use glr_core::{
     run_gen_result::RunGeneratorResult,
     split::{NamedSplit, Split},
     time::Time,
+    token::Token,
 };
 use glr_lib::dll_exports::enums::SubscribeCode;
 use serde::{Deserialize, Serialize};
@@ -20,9 +25,10 @@ use serde::{Deserialize, Serialize};
 use crate::{
     dll::parse_continously::ContinousParser,
     render::Render,
-    windows::{
-        live_window::objective_reader::{ObjectiveReader, UpdateObjective},
-        settings_window::SettingsWindow,
+    settings::Settings,
+    windows::live_window::{
+        objective_reader::{ObjectiveReader, UpdateObjective},
+        overlay_server::{OverlayEvent, OverlayServer, OverlaySplitter},
     },
 };
 
@@ -30,8 +36,10 @@ use crate::{
 pub struct RunRender<T: Split> {
     pub split_labels: VecDeque<String>,
     pub split_names: Option<VecDeque<String>>,
-    pub compared_wr: Option<VecDeque<(String, Color32)>>,
-    pub compared_best: Option<VecDeque<(String, Color32)>>,
+    /// the total time minus the record's after each split, in milliseconds.
+    pub compared_wr: Option<VecDeque<Option<i64>>>,
+    /// the split time minus the best split, in milliseconds.
+    pub compared_best: Option<VecDeque<Option<i64>>>,
     pub longest_name: usize,
 
     pub objective_str: String,
@@ -41,6 +49,21 @@ pub struct RunRender<T: Split> {
     pub run_buffer: Vec<T>,
 }
 
+/// the delta as the splitter shows it, green when ahead and red when behind.
+fn delta_label(delta: Option<i64>) -> (String, Color32) {
+    let Some(delta) = delta else {
+        return ("         ".to_string(), Color32::WHITE);
+    };
+
+    let color = match delta {
+        ..0 => Color32::GREEN,
+        0 => Color32::WHITE,
+        _ => Color32::RED,
+    };
+
+    (comparison::format_millis(delta.unsigned_abs()), color)
+}
+
 impl<T: Split> Render for RunRender<T> {
     type Response = usize;
 
@@ -58,16 +81,12 @@ impl<T: Split> Render for RunRender<T> {
                 
                 ui.label(&self.split_labels[it]);
 
-                if let Some(compared_wr) = &self.compared_wr {
-                    match compared_wr.get(it) {
-                        Some((text, color)) => ui.colored_label(*color, text),
-                        None => ui.label("         "),
-                    };
-                }
-
-                if let Some(compared_best) = &self.compared_best {
-                    match compared_best.get(it) {
-                        Some((text, color)) => ui.colored_label(*color, text),
+                for compared in [&self.compared_wr, &self.compared_best].into_iter().flatten() {
+                    match compared.get(it) {
+                        Some(delta) => {
+                            let (text, color) = delta_label(*delta);
+                            ui.colored_label(color, text)
+                        }
                         None => ui.label("         "),
                     };
                 }
@@ -86,19 +105,19 @@ impl<T: Split> Render for RunRender<T> {
 }
 
 impl<T: Split + Clone> RunRender<T> {
-    pub fn new(objective_str: String, settings: &SettingsWindow) -> Self {
+    pub fn new(objective_str: String, settings: &Settings) -> Self {
         Self {
             longest_name: 0,
-            split_names: match settings.get_def::<bool>("show_split_name") {
+            split_names: match settings.splitter.show_split_name {
                 true => Some(VecDeque::new()),
                 false => None,
             },
             split_labels: VecDeque::new(),
-            compared_wr: match settings.get_def("compare_record") {
+            compared_wr: match settings.splitter.compare_record {
                 true => Some(VecDeque::new()),
                 false => None,
             },
-            compared_best: match settings.get_def("compare_best_splits") {
+            compared_best: match settings.splitter.compare_best_splits {
                 true => Some(VecDeque::new()),
                 false => None,
             },
@@ -124,39 +143,15 @@ impl<T: Split + Clone> RunRender<T> {
             .as_mut()
             .map(|v| v.push_back(name));
         self.split_labels.push_back(total.to_string());
-        match (&mut self.compared_wr, wr_time) {
-            (Some(vd), Some(c_time)) => {
-                vd.push_back(match total.cmp(&c_time) {
-                    std::cmp::Ordering::Less => {
-                        ((c_time - total).to_string_no_hours(), Color32::GREEN)
-                    }
-                    std::cmp::Ordering::Equal => ("00:00.000".to_string(), Color32::WHITE),
-                    std::cmp::Ordering::Greater => {
-                        ((total - c_time).to_string_no_hours(), Color32::RED)
-                    }
-                });
-            }
-            (Some(vd), None) => {
-                vd.push_back(("         ".to_string(), Color32::WHITE));
-            }
-            _ => {}
+        if let Some(vd) = &mut self.compared_wr {
+            vd.push_back(wr_time.map(|c_time| comparison::difference(total, c_time)));
         }
-        match (&mut self.compared_best, split_time, split_compare) {
-            (Some(vd), Some(time), Some(c_time)) => {
-                vd.push_back(match time.cmp(&c_time) {
-                    std::cmp::Ordering::Less => {
-                        ((c_time - time).to_string_no_hours(), Color32::GREEN)
-                    }
-                    std::cmp::Ordering::Equal => ("00:00.000".to_string(), Color32::WHITE),
-                    std::cmp::Ordering::Greater => {
-                        ((time - c_time).to_string_no_hours(), Color32::RED)
-                    }
-                });
-            }
-            (Some(vd), _, _) => {
-                vd.push_back(("         ".to_string(), Color32::WHITE));
-            }
-            _ => {}
+        if let Some(vd) = &mut self.compared_best {
+            vd.push_back(
+                split_time
+                    .zip(split_compare)
+                    .map(|(time, c_time)| comparison::difference(time, c_time)),
+            );
         }
     }
 
@@ -208,24 +203,49 @@ pub struct LevelRunRenderer {
     run_buffer: Option<Vec<NamedSplit>>,
 
     continous_parser: ContinousParser<RunGeneratorResult>,
+    token_parser: ContinousParser<Token>,
+    session_seed: Option<u64>,
     no_save_for_frames: usize,
+
+    livesplit_client: Option<LiveSplitClient>,
 }
 
 impl LevelRunRenderer {
-    pub fn new(settings: &SettingsWindow) -> Self {
+    pub fn new(settings: &Settings) -> Self {
         Self {
             run_render: RunRender::new("".to_owned(), settings),
             continous_parser: ContinousParser::new(SubscribeCode::RunInfo),
+            token_parser: ContinousParser::new(SubscribeCode::Tokenizer),
+            session_seed: None,
             run_buffer: None,
             no_save_for_frames: 5,
+            livesplit_client: settings.splitter.livesplit_server.then(|| {
+                LiveSplitClient::new(settings.splitter.livesplit_server_address.trim().to_owned())
+            }),
+        }
+    }
+
+    fn publish(&self, overlay: Option<&OverlayServer>, event: OverlayEvent) {
+        if let Some(server) = overlay {
+            server.publish(OverlaySplitter::Level, Some(&event), (&self.run_render).into());
+        }
+    }
+
+    fn send_livesplit<F>(&mut self, command: F)
+    where
+        F: FnOnce(&mut LiveSplitClient),
+    {
+        if let Some(client) = &mut self.livesplit_client {
+            command(client);
         }
     }
 
     pub fn render(
         &mut self,
         save_manager: &mut SaveManager,
-        settings: &SettingsWindow,
+        settings: &Settings,
         reader: &impl ObjectiveReader<Objective = RunObjective>,
+        overlay: Option<&OverlayServer>,
         ui: &mut Ui,
     ) -> usize {
         self.no_save_for_frames = self.no_save_for_frames.saturating_sub(1);
@@ -235,6 +255,16 @@ impl LevelRunRenderer {
             for it in buffer {
                 self.run_render.add_split(&it, save_manager);
             }
+
+            self.publish(overlay, OverlayEvent::ObjectiveChanged {
+                objective: self.run_render.objective_str.clone(),
+            });
+        }
+
+        while let Some(token) = self.token_parser.try_recv() {
+            if let Token::SessionSeed(seed) = token {
+                self.session_seed = Some(seed as u64);
+            }
         }
 
         while let Some(r) = self.continous_parser.try_recv() {
@@ -244,9 +274,18 @@ impl LevelRunRenderer {
                         .with_player_count(player_count);
                     self.run_render =
                         RunRender::new(reader.override_obj(obj).to_string(), settings);
+                    self.publish(overlay, OverlayEvent::RunStarted {
+                        objective: self.run_render.objective_str.clone(),
+                    });
+                    self.send_livesplit(|client| client.start_run());
                 }
                 RunGeneratorResult::SplitAdded(named_split) => {
                     self.run_render.add_split(&named_split, save_manager);
+                    self.publish(overlay, OverlayEvent::SplitAdded {
+                        name: named_split.get_name().to_owned(),
+                        time: named_split.get_time().to_string(),
+                    });
+                    self.send_livesplit(|client| client.add_split(&named_split));
                 }
                 RunGeneratorResult::PlayerCountUpdate(new_count) => {
                     println!("<{}>", &self.run_render.objective_str);
@@ -254,16 +293,34 @@ impl LevelRunRenderer {
                     self.run_render.objective_str = RunObjective::try_from(self.run_render.objective_str.as_str())
                         .map(|v| v.with_player_count(new_count).to_string())
                         .unwrap_or(cloned_name);
+                    self.publish(overlay, OverlayEvent::ObjectiveChanged {
+                        objective: self.run_render.objective_str.clone(),
+                    });
                 }
                 RunGeneratorResult::LevelRun(timed_run) => {
                     let mut level_run: LevelRun = timed_run.into();
                     level_run.set_objective_str(&self.run_render.objective_str);
+                    if let Some(seed) = self.session_seed {
+                        let metadata = level_run.get_metadata()
+                            .cloned()
+                            .unwrap_or_else(RunMetadata::now)
+                            .with_session_seed(seed);
+                        level_run.set_metadata(Some(metadata));
+                    }
+                    RuleSet::get().apply(&mut level_run);
 
                     if let Some(split) = level_run.get_split_by_name("WIN") {
                         let split = NamedSplit::new(split.get_time(), split.get_name().to_owned());
                         self.run_render.add_split(&split, save_manager);
                     }
 
+                    self.publish(overlay, OverlayEvent::RunEnded {
+                        objective: self.run_render.objective_str.clone(),
+                        time: level_run.get_time().to_string(),
+                        is_win: level_run.is_win(),
+                    });
+                    self.send_livesplit(|client| client.end_run(&level_run));
+
                     if self.no_save_for_frames == 0 {
                         save_manager.save(RunEnum::Level(level_run));
                     }
@@ -8,11 +8,9 @@ use glr_lib::dll_exports::enums::SubscribeCode;
 use crate::{
     dll::parse_continously::ContinousParser,
     render::Render,
-    windows::{
-        live_window::foresight_view::{
-            AddToConditions, ForesightView, LookUpForesight, OptimizedForesightView
-        },
-        settings_window::SettingsWindow,
+    settings::Settings,
+    windows::live_window::foresight_view::{
+        AddToConditions, ForesightView, LookUpForesight, OptimizedForesightView
     },
 };
 use indexmap::IndexMap;
@@ -52,7 +50,9 @@ pub struct SeedIndexer {
 }
 
 impl SeedIndexer {
-    pub fn new(settings: &SettingsWindow) -> Self {
+    pub fn new(settings: &Settings) -> Self {
+        let foresight = &settings.foresight;
+
         Self {
             overflow_hash_text: Default::default(),
             overflow_size_text: Default::default(),
@@ -61,46 +61,26 @@ impl SeedIndexer {
             continous_parser: ContinousParser::new(SubscribeCode::SeedIndexer),
             views: HashMap::new(),
             objective: Default::default(),
-            size_multi: settings.get_def::<f32>("text_size") / 12f32,
-
-            show_gather_small_items: settings
-                .get("seed_indexer_show_gather_small_items")
-                .unwrap_or(true),
-            show_fog_turbine: settings
-                .get("seed_indexer_show_fog_turbine")
-                .unwrap_or(true),
-            show_cell: settings.get("seed_indexer_show_cell").unwrap_or(true),
-            show_colored_key: settings
-                .get("seed_indexer_show_colored_key")
-                .unwrap_or(true),
-            show_bulkhead_key: settings
-                .get("seed_indexer_show_bulkhead_key")
-                .unwrap_or(true),
-            show_terminal_uplink: settings
-                .get("seed_indexer_show_terminal_uplink")
-                .unwrap_or(true),
-            show_retrieve_big_items: settings
-                .get("seed_indexer_show_retrieve_big_items")
-                .unwrap_or(true),
-            show_special_terminal_command: settings
-                .get("seed_indexer_show_special_terminal_command")
-                .unwrap_or(true),
-            show_hsu: settings.get("seed_indexer_show_hsu").unwrap_or(true),
-            show_power_cell_distribution: settings
-                .get("seed_indexer_show_power_cell_distribution")
-                .unwrap_or(true),
-
-            show_resources: settings.get("seed_indexer_show_resources").unwrap_or(true),
-            show_consumables: settings
-                .get("seed_indexer_show_consumables")
-                .unwrap_or(true),
-            show_artifacts: settings.get_def("seed_indexer_show_artifacts"),
-            number_of_items: settings.get("seed_indexer_length").unwrap_or(10) as usize,
-            show_overflow: settings.get("seed_indexer_show_overflow").unwrap_or(true),
-            show_overflow_hash_size: match settings.get_def("seed_indexer_show_overflow_hash") {
-                true => settings.get::<i32>("seed_indexer_overflow_hash_size")
-                    .map(|v| v as usize)
-                    .unwrap_or(8),
+            size_multi: settings.general.text_size / 12f32,
+
+            show_gather_small_items: foresight.show_gather_small_items,
+            show_fog_turbine: foresight.show_fog_turbine,
+            show_cell: foresight.show_cell,
+            show_colored_key: foresight.show_colored_key,
+            show_bulkhead_key: foresight.show_bulkhead_key,
+            show_terminal_uplink: foresight.show_terminal_uplink,
+            show_retrieve_big_items: foresight.show_retrieve_big_items,
+            show_special_terminal_command: foresight.show_special_terminal_command,
+            show_hsu: foresight.show_hsu,
+            show_power_cell_distribution: foresight.show_power_cell_distribution,
+
+            show_resources: foresight.show_resources,
+            show_consumables: foresight.show_consumables,
+            show_artifacts: foresight.show_artifacts,
+            number_of_items: foresight.length as usize,
+            show_overflow: foresight.show_overflow,
+            show_overflow_hash_size: match foresight.show_overflow_hash {
+                true => foresight.overflow_hash_size as usize,
                 false => 0,
             },
             hash_data: [0; 32],
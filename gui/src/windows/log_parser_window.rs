@@ -1,17 +1,23 @@
 use core::{
+    edit_history::{EditHistory, RunEdit},
     export::Export,
+    import_ledger::{ImportLedger, ImportSummary},
+    import_report::{FileStatus, ImportReport},
     run::{
-        objectives::{Objective, objective_enum::ObjectiveEnum},
-        timed_run::{LevelRun, RunEnum},
+        objectives::{Objective, game_objective::GameObjective, objective_enum::ObjectiveEnum},
+        rules::RunFlag,
+        timed_run::{GameRun, LevelRun, RunEnum},
         traits::Run,
     },
     save_manager::SaveManager,
 };
 use std::fs::File;
 
-use egui::Color32;
+use egui::{Color32, RichText};
 
 use crate::{
+    history_buttons::render_history_buttons,
+    render::Render,
     run::RenderResult,
     sorter_buttons::{OptionalVisualSorterButtons, VisualSorterButtons},
 };
@@ -24,8 +30,17 @@ pub struct LogParserWindow {
     set_all_overload: bool,
     set_all_glitched: bool,
     set_all_early_drop: bool,
-    // game_obj: GameObjective,
-    // player_count_input: String,
+    game_obj: GameObjective,
+    game_run_error: Option<String>,
+    import_summary: Option<ImportSummary>,
+    import_report: Option<ImportReport>,
+    /// the log files of the runs, saved in the ledger once the runs are saved.
+    pending_ledger: Option<ImportLedger>,
+    /// the runs come from the auto import inbox, which follows what is saved or deleted.
+    from_inbox: bool,
+    inbox_changed: bool,
+
+    history: EditHistory<RunEdit<LevelRun>>,
 }
 
 impl LogParserWindow {
@@ -36,13 +51,148 @@ impl LogParserWindow {
             set_all_overload: false,
             set_all_glitched: false,
             set_all_early_drop: false,
-            // game_obj: GameObjective::default(),
-            // player_count_input: "0".to_string(),
+            game_obj: GameObjective::default().with_player_count(4),
+            game_run_error: None,
+            import_summary: None,
+            import_report: None,
+            pending_ledger: None,
+            from_inbox: false,
+            inbox_changed: false,
+            history: EditHistory::default(),
+        }
+    }
+
+    pub fn with_import_summary(mut self, summary: ImportSummary) -> Self {
+        self.import_summary = Some(summary);
+
+        self
+    }
+
+    pub fn with_import_report(mut self, report: ImportReport) -> Self {
+        self.import_report = Some(report);
+
+        self
+    }
+
+    pub fn with_pending_ledger(mut self, ledger: Option<ImportLedger>) -> Self {
+        self.pending_ledger = ledger;
+
+        self
+    }
+
+    pub fn from_inbox(mut self) -> Self {
+        self.from_inbox = true;
+
+        self
+    }
+
+    /// the runs left to review, once some of the inbox runs were saved or deleted.
+    pub fn take_inbox_changes(&mut self) -> Option<Vec<LevelRun>> {
+        match std::mem::take(&mut self.inbox_changed) {
+            true => Some(self.timed_runs.clone()),
+            false => None,
+        }
+    }
+
+    /// called once runs are saved or deleted, the log files are only
+    /// put in the ledger when no run is left to review.
+    fn runs_handled(&mut self) {
+        self.inbox_changed = self.from_inbox;
+
+        if self.timed_runs.is_empty()
+            && let Some(ledger) = self.pending_ledger.take()
+            && let Err(e) = ledger.commit()
+        {
+            eprintln!("Failed to save import ledger: {e}");
+        }
+    }
+
+    /// the edits before a save can not be undone, the runs they touched may be saved already.
+    fn runs_saved(&mut self) {
+        self.history.clear();
+        self.runs_handled();
+    }
+
+    fn render_import_report(ui: &mut egui::Ui, report: &ImportReport) {
+        let failed = report.count(|v| v.is_failed());
+        let title = format!(
+            "Import report: {} parsed, {} warnings, {failed} failed, {} skipped, {} cancelled",
+            report.count(|v| matches!(v, FileStatus::Parsed(_))),
+            report.count(|v| matches!(v, FileStatus::Warning(_))),
+            report.count(|v| matches!(v, FileStatus::Skipped(_))),
+            report.count(|v| matches!(v, FileStatus::Cancelled)),
+        );
+
+        egui::CollapsingHeader::new(title)
+            .id_salt("import_report")
+            .show(ui, |ui| {
+                if ui.button("Export report").clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .set_title("Export import report")
+                        .set_file_name("import_report.csv")
+                        .save_file()
+                {
+                    let result = File::create(path)
+                        .map_err(|e| e.to_string())
+                        .and_then(|file| report.export_csv(file));
+                    if let Err(e) = result {
+                        eprintln!("Failed to export import report: {e}");
+                    }
+                }
+
+                egui::ScrollArea::vertical()
+                    .id_salt("import_report_files")
+                    .max_height(150.0)
+                    .show(ui, |ui| {
+                        for file in &report.files {
+                            let color = match &file.status {
+                                FileStatus::Parsed(_) => Color32::GREEN,
+                                FileStatus::Warning(_) => Color32::YELLOW,
+                                FileStatus::Failed(_) => Color32::RED,
+                                FileStatus::Skipped(_) | FileStatus::Cancelled => Color32::GRAY,
+                            };
+
+                            ui.horizontal(|ui| {
+                                ui.colored_label(color, file.status.to_string());
+                                ui.label(file.path.display().to_string());
+                            });
+                        }
+                    });
+            });
+    }
+
+    fn confirm_all(&mut self, flag: RunFlag) {
+        for timed_run in &mut self.timed_runs {
+            if let Some(mut metadata) = timed_run.get_metadata().cloned() {
+                metadata.confirm(flag);
+                timed_run.set_metadata(Some(metadata));
+            }
+        }
+    }
+
+    /// inferred flags are shown in yellow until they are changed by hand.
+    fn flag_checkbox(ui: &mut egui::Ui, run: &mut LevelRun, value: &mut bool, flag: RunFlag) {
+        let inferred = run
+            .get_metadata()
+            .is_some_and(|v| v.inferred.contains(&flag));
+        let text = match inferred {
+            true => RichText::new(flag.to_string()).color(Color32::YELLOW),
+            false => RichText::new(flag.to_string()),
+        };
+
+        if ui.checkbox(value, text).changed()
+            && let Some(mut metadata) = run.get_metadata().cloned()
+        {
+            metadata.confirm(flag);
+            run.set_metadata(Some(metadata));
         }
     }
 
     pub fn render(&mut self, ui: &mut egui::Ui, save_manager: &mut SaveManager) {
         self.render_buttons(ui);
+        if render_history_buttons(&mut self.history, &mut self.timed_runs, ui) {
+            self.inbox_changed = self.from_inbox;
+        }
 
         // handles all the set all buttons.
         ui.horizontal(|ui| {
@@ -52,6 +202,16 @@ impl LogParserWindow {
             let early_drop_checkbox =
                 ui.checkbox(&mut self.set_all_early_drop, "Set ALL early drop");
 
+            let before = [
+                &secondary_checkbox,
+                &overload_checkbox,
+                &glitched_checkbox,
+                &early_drop_checkbox,
+            ]
+            .iter()
+            .any(|v| v.clicked())
+            .then(|| self.timed_runs.clone());
+
             if secondary_checkbox.clicked() {
                 for timed_run in &mut self.timed_runs {
                     timed_run.set_objective({
@@ -95,6 +255,21 @@ impl LogParserWindow {
                     });
                 }
             }
+
+            for (checkbox, flag) in [
+                (secondary_checkbox, RunFlag::Secondary),
+                (overload_checkbox, RunFlag::Overload),
+                (glitched_checkbox, RunFlag::Glitched),
+                (early_drop_checkbox, RunFlag::EarlyDrop),
+            ] {
+                if checkbox.clicked() {
+                    self.confirm_all(flag);
+                }
+            }
+
+            if let Some(edit) = before.and_then(|v| RunEdit::batch(v, &self.timed_runs)) {
+                self.history.push(edit);
+            }
         });
 
         ui.horizontal(|ui| {
@@ -106,22 +281,15 @@ impl LogParserWindow {
                         .collect(),
                 );
                 self.timed_runs.clear();
+                self.runs_saved();
             }
-            /*
             if ui.button("Save ALL as FULL GAME RUN").clicked() {
-                let mut game_run = GameRun::new(self.game_obj.clone());
-
-                self.timed_runs
-                    .sort_by(|a, b| a.get_objective_str().cmp(b.get_objective_str()));
-                for run in self.timed_runs.drain(0..self.timed_runs.len()) {
-                    game_run.add_split(run);
+                self.game_run_error = self.save_game_run(save_manager).err();
+                if self.game_run_error.is_none() {
+                    self.runs_saved();
                 }
-
-                game_run.validate();
-
-                self.save_manager.save(RunEnum::Game(game_run));
             }
-            */
+            self.game_obj.render(ui);
             if ui.button("Export to CSV").clicked() {
                 if let Some(path) = rfd::FileDialog::new()
                     .set_title("Export to file")
@@ -138,6 +306,29 @@ impl LogParserWindow {
             }
         });
 
+        if let Some(error) = &self.game_run_error {
+            ui.colored_label(Color32::RED, error);
+        }
+
+        if let Some(summary) = &self.import_summary {
+            ui.horizontal(|ui| {
+                ui.label(format!("Log files: {summary}"));
+
+                if ui.button("Forget imported logs").clicked() {
+                    self.pending_ledger = None;
+                    if let Err(e) = ImportLedger::default().save() {
+                        eprintln!("Failed to clear import ledger: {e}");
+                    }
+                }
+            });
+        }
+
+        if let Some(report) = &self.import_report {
+            Self::render_import_report(ui, report);
+        }
+
+        let mut reviewed = false;
+        let mut saved = false;
         egui::ScrollArea::vertical().show_rows(
             ui,
             ui.text_style_height(&egui::TextStyle::Body),
@@ -145,9 +336,11 @@ impl LogParserWindow {
             |ui, row_range| {
                 let mut for_removal = Vec::new();
                 let mut for_saving = Vec::new();
+                let mut edits = Vec::new();
 
                 for row in row_range {
                     let timed_run = &mut self.timed_runs[row];
+                    let before = timed_run.clone();
                     let mut result = RenderResult::default();
 
                     let time = timed_run.get_time();
@@ -168,12 +361,18 @@ impl LogParserWindow {
 
                         ui.colored_label(Color32::WHITE, format!("{:03} stamps", timed_run.len()));
 
-                        ui.checkbox(&mut objective.secondary, "Secondary");
-                        ui.checkbox(&mut objective.overload, "Overload");
-                        ui.checkbox(&mut objective.glitched, "Glitched");
-                        ui.checkbox(&mut objective.early_drop, "Early Drop");
+                        Self::flag_checkbox(ui, timed_run, &mut objective.secondary, RunFlag::Secondary);
+                        Self::flag_checkbox(ui, timed_run, &mut objective.overload, RunFlag::Overload);
+                        Self::flag_checkbox(ui, timed_run, &mut objective.glitched, RunFlag::Glitched);
+                        Self::flag_checkbox(ui, timed_run, &mut objective.early_drop, RunFlag::EarlyDrop);
 
-                        timed_run.set_objective(ObjectiveEnum::Run(objective.clone()));
+                        if timed_run.get_objective().as_level_run() != Some(&objective) {
+                            timed_run.set_objective(ObjectiveEnum::Run(objective.clone()));
+                            edits.push(RunEdit::Replace {
+                                before: before.clone(),
+                                after: timed_run.clone(),
+                            });
+                        }
 
                         if ui.button("SAVE RUN").clicked() {
                             result.save = true;
@@ -193,15 +392,71 @@ impl LogParserWindow {
                 }
 
                 for id in for_removal.iter().rev() {
-                    self.timed_runs.remove(*id);
+                    let run = self.timed_runs.remove(*id);
+                    edits.push(RunEdit::Remove(*id, run));
+                }
+
+                for edit in edits {
+                    self.history.push(edit);
                 }
 
                 for id in for_saving.iter().rev() {
                     let run = self.timed_runs.remove(*id);
                     save_manager.save(RunEnum::Level(run));
                 }
+
+                saved = !for_saving.is_empty();
+                reviewed = saved || !for_removal.is_empty();
             },
         );
+
+        match (saved, reviewed) {
+            (true, _) => self.runs_saved(),
+            (false, true) => self.runs_handled(),
+            (false, false) => {}
+        }
+    }
+}
+
+impl LogParserWindow {
+    /// the fastest win of each level of the objective becomes a split of a single
+    /// game run, in the order they were played. every level run is saved as well.
+    fn save_game_run(&mut self, save_manager: &mut SaveManager) -> Result<(), String> {
+        let mut chosen: Vec<usize> = Vec::new();
+        for level in self.game_obj.get_levels() {
+            let best = self
+                .timed_runs
+                .iter()
+                .enumerate()
+                .filter(|(id, run)| {
+                    !chosen.contains(id) && GameObjective::completes_level(&level, run)
+                })
+                .min_by_key(|(_, run)| run.get_time().get_stamp())
+                .map(|(id, _)| id);
+            chosen.extend(best);
+        }
+        chosen.sort();
+
+        let mut game_run = GameRun::new(ObjectiveEnum::Game(self.game_obj.clone()));
+        for id in chosen {
+            game_run.add_split(self.timed_runs[id].clone());
+        }
+
+        if !game_run.validate() {
+            let missing = game_run
+                .get_missing_levels()
+                .iter()
+                .map(|v| v.level_name.clone())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            return Err(format!("Missing levels: {missing}"));
+        }
+
+        save_manager.save(RunEnum::Game(game_run));
+        save_manager.save_multiple(self.timed_runs.drain(..).map(RunEnum::Level).collect());
+
+        Ok(())
     }
 }
 
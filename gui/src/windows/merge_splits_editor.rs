@@ -0,0 +1,101 @@
+use core::{
+    edit_history::SaveEdit,
+    run::merge_splits::{MergeConfig, MergeSplits},
+    save_manager::SaveManager,
+};
+
+use egui::{Color32, Ui};
+
+/// ron editor for the merge splits of one objective.
+#[derive(Default)]
+pub struct MergeSplitsEditor {
+    text: String,
+    /// parse and validation errors, the text can not be applied while there are any.
+    errors: Vec<String>,
+    warnings: Vec<String>,
+    save_error: Option<String>,
+}
+
+impl MergeSplitsEditor {
+    /// fills the editor with what is saved for the objective.
+    pub fn load(&mut self, save_manager: &SaveManager, objective: &String) {
+        let config = save_manager
+            .get_level_merge(objective)
+            .map(|v| v.get_config().clone())
+            .unwrap_or_default();
+
+        self.text = config.to_ron();
+        self.save_error = None;
+        self.check(save_manager, objective);
+    }
+
+    fn check(&mut self, save_manager: &SaveManager, objective: &str) {
+        (self.errors, self.warnings) = match MergeConfig::parse(&self.text) {
+            Ok(config) => (
+                config.get_errors(),
+                config.get_warnings(&save_manager.get_run_split_names(objective)),
+            ),
+            Err(e) => (vec![e], Vec::new()),
+        };
+    }
+
+    /// returns the edit once the merges are applied so it can be undone.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        save_manager: &mut SaveManager,
+        objective: &String,
+    ) -> Option<SaveEdit> {
+        let mut result = None;
+
+        let response = ui.add(
+            egui::TextEdit::multiline(&mut self.text)
+                .code_editor()
+                .desired_width(640.0)
+                .desired_rows(6)
+                .background_color(Color32::from_rgb(32, 32, 32))
+                .text_color(Color32::WHITE),
+        );
+        if response.changed() {
+            self.check(save_manager, objective);
+        }
+
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(self.errors.is_empty(), egui::Button::new("Apply"))
+                .clicked()
+                && let Ok(after) = MergeSplits::parse(&self.text)
+            {
+                let before = save_manager.get_level_merge(objective).cloned().unwrap_or_default();
+                self.save_error = save_manager.set_merge_splits(objective, after.clone()).err();
+                result = Some(SaveEdit::MergeSplits {
+                    objective: objective.clone(),
+                    before: Box::new(before),
+                    after: Box::new(after),
+                });
+            }
+
+            if ui.button("Revert").clicked() {
+                self.load(save_manager, objective);
+            }
+
+            let parsed = MergeConfig::parse(&self.text);
+            if ui.add_enabled(parsed.is_ok(), egui::Button::new("Add group")).clicked()
+                && let Ok(mut config) = parsed
+            {
+                config.groups.push(Default::default());
+                self.text = config.to_ron();
+                self.check(save_manager, objective);
+            }
+        });
+
+        for error in self.errors.iter().chain(&self.save_error) {
+            ui.colored_label(Color32::RED, error);
+        }
+        for warning in &self.warnings {
+            ui.colored_label(Color32::YELLOW, warning);
+        }
+
+        result
+    }
+}
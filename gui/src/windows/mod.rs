@@ -1,8 +1,12 @@
+pub mod auto_import;
 pub mod await_parse_files;
 pub mod settings_window;
 
+pub mod comparison_view;
 pub mod run_manager_window;
+pub mod split_stats_view;
 
 pub mod live_window;
 pub mod log_parser_window;
+pub mod merge_splits_editor;
 pub mod stats_window;
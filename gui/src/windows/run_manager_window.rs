@@ -1,46 +1,344 @@
-use core::save_manager::{SaveManager, SaveType};
-use std::collections::HashMap;
+use core::{
+    edit_history::{EditHistory, SaveEdit},
+    livesplit::LiveSplit,
+    run::{
+        notes::{RunNotes, VodLink, format_timestamp, parse_timestamp},
+        timed_run::RunEnum,
+        traits::Run,
+    },
+    save_manager::{SaveManager, SaveType},
+    sum_of_best::SumOfBest,
+    trash::TrashReason,
+};
+use std::{collections::HashMap, fs};
 
 use egui::Color32;
-use glr_core::time::Time;
+use glr_core::{split::Split, time::Time};
+
+use crate::{
+    history_buttons::render_history_buttons, run::RenderRun, sorter_buttons::render_buttons,
+    windows::{
+        comparison_view::ComparisonView, merge_splits_editor::MergeSplitsEditor,
+        split_stats_view::SplitStatsView,
+    },
+};
+
+/// the notes of one run while they are being edited.
+struct NotesEditor {
+    /// copy of the run, used to find it again after sorting.
+    run: RunEnum,
+    notes: String,
+    tags: String,
+    vod_url: String,
+    vod_timestamp: String,
+    error: Option<String>,
+}
+
+impl NotesEditor {
+    fn new(run: &RunEnum) -> Self {
+        let notes = run.get_notes().cloned().unwrap_or_default();
+        let vod = notes.vod.unwrap_or_default();
 
-use crate::{run::RenderRun, sorter_buttons::render_buttons, windows::settings_window::SettingsWindow};
+        Self {
+            run: run.clone(),
+            notes: notes.notes,
+            tags: notes.tags.join(", "),
+            vod_url: vod.url,
+            vod_timestamp: vod.timestamp.map(format_timestamp).unwrap_or_default(),
+            error: None,
+        }
+    }
+
+    fn get_notes(&self) -> Result<RunNotes, String> {
+        let timestamp = match self.vod_timestamp.trim().is_empty() {
+            true => None,
+            false => Some(parse_timestamp(&self.vod_timestamp).ok_or(format!(
+                "Invalid VOD timestamp: {}, use h:mm:ss",
+                self.vod_timestamp
+            ))?),
+        };
+        let url = self.vod_url.trim();
+
+        Ok(RunNotes {
+            notes: self.notes.trim().to_owned(),
+            tags: RunNotes::parse_tags(&self.tags),
+            vod: (!url.is_empty()).then(|| VodLink {
+                url: url.to_owned(),
+                timestamp,
+            }),
+        })
+    }
+}
 
 pub struct RunManagerWindow {
     objective: String,
     show_split_times: bool,
+    show_run_info: bool,
     compare_all: bool,
 
     bottom_range: usize,
-    merge_splits_string: String,
+    merge_splits_editor: MergeSplitsEditor,
 
     compare_first: Option<usize>,
     compare_second: Vec<bool>,
-    save_type: SaveType,
+
+    save_error: Option<String>,
+    save_info: Option<String>,
+
+    history: EditHistory<SaveEdit>,
+    show_trash: bool,
+    confirm_empty_trash: bool,
+    show_comparison: bool,
+    comparison_view: ComparisonView,
+    show_split_stats: bool,
+    split_stats_view: SplitStatsView,
+
+    search: String,
+    notes_editor: Option<NotesEditor>,
 }
 
 impl RunManagerWindow {
-    fn sum_run_splits(run: impl Iterator<Item = Time>) -> Time {
-        let mut result = Time::new();
-
-        for time in run {
-            result += time;
-        }
+    /// the theoretical best, hovering it lists the run each gold comes from.
+    fn render_sum_of_best(
+        ui: &mut egui::Ui,
+        sum_of_best: &SumOfBest,
+        save_manager: &SaveManager,
+        objective: &str,
+    ) {
+        let missing: Vec<String> = sum_of_best.get_missing().map(|v| v.to_string()).collect();
+        let label = match missing.is_empty() {
+            true => format!("Theoretical: {}", sum_of_best.get_total()),
+            false => format!("Theoretical: {} (missing {})", sum_of_best.get_total(), missing.len()),
+        };
 
-        result
+        ui.colored_label(Color32::GOLD, label).on_hover_ui(|ui| {
+            let runs = save_manager.get_runs(objective);
+
+            egui::Grid::new("sum_of_best").striped(true).show(ui, |ui| {
+                for gold in sum_of_best.get_golds() {
+                    ui.monospace(gold.segment.to_string());
+                    match (gold.time, gold.run) {
+                        (Some(time), Some(id)) => {
+                            ui.colored_label(Color32::GOLD, time.to_string());
+                            let run_time = runs
+                                .and_then(|v| v.get(id))
+                                .map(|v| v.get_time().to_string())
+                                .unwrap_or_default();
+                            ui.label(format!("from run #{} ({run_time})", id + 1));
+                        }
+                        _ => {
+                            ui.colored_label(Color32::RED, "no run finished it");
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+        });
     }
 
-    pub fn new(settings: &SettingsWindow) -> Self {
+    pub fn new() -> Self {
         Self {
             objective: "".to_owned(),
             show_split_times: false,
+            show_run_info: false,
             bottom_range: 0,
-            merge_splits_string: "".to_owned(),
+            merge_splits_editor: MergeSplitsEditor::default(),
             compare_first: None,
             compare_second: Vec::new(),
             compare_all: false,
-            save_type: settings.get_save_type(),
+            save_error: None,
+            save_info: None,
+            history: EditHistory::default(),
+            show_trash: false,
+            confirm_empty_trash: false,
+            show_comparison: false,
+            comparison_view: ComparisonView::default(),
+            show_split_stats: false,
+            split_stats_view: SplitStatsView::default(),
+            search: String::new(),
+            notes_editor: None,
+        }
+    }
+
+    /// returns true once the editor should be closed.
+    fn render_notes_editor(
+        ui: &mut egui::Ui,
+        editor: &mut NotesEditor,
+        objective: &str,
+        save_manager: &mut SaveManager,
+    ) -> bool {
+        let mut close = false;
+
+        ui.label(format!("Notes for the run of {}", editor.run.get_time()));
+        ui.horizontal(|ui| {
+            ui.monospace("Tags:          ");
+            ui.add(egui::TextEdit::singleline(&mut editor.tags).desired_width(512.0));
+        });
+        ui.horizontal(|ui| {
+            ui.monospace("VOD url:       ");
+            ui.add(egui::TextEdit::singleline(&mut editor.vod_url).desired_width(512.0));
+        });
+        ui.horizontal(|ui| {
+            ui.monospace("VOD timestamp: ");
+            ui.add(egui::TextEdit::singleline(&mut editor.vod_timestamp).desired_width(100.0));
+        });
+        ui.add(
+            egui::TextEdit::multiline(&mut editor.notes)
+                .desired_width(640.0)
+                .desired_rows(3),
+        );
+
+        ui.horizontal(|ui| {
+            if ui.button("Save notes").clicked() {
+                match editor.get_notes() {
+                    Ok(notes) => {
+                        let found = save_manager
+                            .get_runs(objective)
+                            .is_some_and(|runs| runs.contains(&editor.run));
+                        match found {
+                            true => {
+                                save_manager.edit_runs(objective, |runs| {
+                                    if let Some(run) = runs.iter_mut().find(|v| **v == editor.run) {
+                                        run.set_notes(Some(notes));
+                                    }
+                                });
+                                close = true;
+                            }
+                            false => editor.error = Some("The run is no longer loaded".to_owned()),
+                        }
+                    }
+                    Err(e) => editor.error = Some(e),
+                }
+            }
+
+            if ui.button("Cancel").clicked() {
+                close = true;
+            }
+
+            if let Ok(RunNotes { vod: Some(vod), .. }) = editor.get_notes()
+                && ui.button("Open VOD").clicked()
+            {
+                let _ = opener::open_browser(vod.get_link());
+            }
+        });
+
+        if let Some(error) = &editor.error {
+            ui.colored_label(Color32::RED, error);
         }
+
+        ui.separator();
+
+        close
+    }
+
+    /// trashed runs are recorded so they can be brought back with undo.
+    fn push_removed(&mut self, removed: Vec<(usize, RunEnum)>, reason: TrashReason) {
+        if removed.is_empty() {
+            return;
+        }
+
+        self.history.push(SaveEdit::Trash {
+            objective: self.objective.clone(),
+            removed,
+            reason,
+        });
+    }
+
+    fn reset_compare(&mut self, save_manager: &SaveManager) {
+        self.compare_first = None;
+        self.compare_second = vec![
+            false;
+            save_manager.get_runs(&self.objective)
+                .map(|v| v.len())
+                .unwrap_or_default()
+        ];
+    }
+
+    /// the first compared run and then every run checked to be compared to it.
+    fn get_selected(&self) -> Vec<usize> {
+        let second = self
+            .compare_second
+            .iter()
+            .enumerate()
+            .filter(|(id, checked)| **checked && self.compare_first != Some(*id))
+            .map(|(id, _)| id);
+
+        self.compare_first.into_iter().chain(second).collect()
+    }
+
+    fn render_trash(&mut self, ui: &mut egui::Ui, save_manager: &mut SaveManager) {
+        let mut restored = None;
+        let mut purged = None;
+
+        ui.horizontal(|ui| {
+            ui.label(format!("{} runs in the trash", save_manager.get_trash().len()));
+            match self.confirm_empty_trash {
+                true => {
+                    ui.colored_label(Color32::YELLOW, "Delete every run in the trash for good?");
+                    if ui.button("Delete").clicked() {
+                        save_manager.get_trash_mut().clear();
+                        self.confirm_empty_trash = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.confirm_empty_trash = false;
+                    }
+                }
+                false => {
+                    if ui.button("Empty trash").clicked() && !save_manager.get_trash().is_empty() {
+                        self.confirm_empty_trash = true;
+                    }
+                }
+            }
+        });
+
+        ui.separator();
+
+        let trash = save_manager.get_trash().get_runs();
+        egui::ScrollArea::vertical().id_salt("trash").show_rows(
+            ui,
+            ui.text_style_height(&egui::TextStyle::Body),
+            trash.len(),
+            |ui, row_range| {
+                for row in row_range {
+                    let trashed = &trash[row];
+                    let color = match trashed.run.is_win() {
+                        true => Color32::GREEN,
+                        false => Color32::RED,
+                    };
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Restore").clicked() {
+                            restored = Some(row);
+                        }
+                        if ui.button("Purge").clicked() {
+                            purged = Some(row);
+                        }
+
+                        ui.label(&trashed.objective);
+                        ui.colored_label(color, trashed.run.get_time().to_string());
+                        ui.label(trashed.reason.to_string());
+                        ui.label(trashed.deleted_at.format("%Y-%m-%d %H:%M:%S").to_string());
+                    });
+                }
+            },
+        );
+
+        if let Some(row) = restored {
+            match save_manager.restore_from_trash(row) {
+                Ok(_) => self.reset_compare(save_manager),
+                Err(e) => self.save_error = Some(e),
+            }
+        }
+        if let Some(row) = purged {
+            save_manager.get_trash_mut().take(row);
+        }
+    }
+
+    fn set_save_result(&mut self, result: Result<String, String>) {
+        (self.save_info, self.save_error) = match result {
+            Ok(info) => (Some(info), None),
+            Err(e) => (None, Some(e)),
+        };
     }
 
     pub fn render(&mut self, ui: &mut egui::Ui, save_manager: &mut SaveManager) {
@@ -55,6 +353,7 @@ impl RunManagerWindow {
                             .clicked()
                         {
                             self.bottom_range = 0;
+                            self.notes_editor = None;
                             self.compare_first = None;
                             self.compare_second = vec![
                                 false; 
@@ -62,33 +361,28 @@ impl RunManagerWindow {
                                     .map(|v| v.len())
                                     .unwrap_or_default()
                             ];
-                            self.merge_splits_string = save_manager
-                                .get_level_merge_split_str(&self.objective)
-                                .unwrap_or_default();
+                            self.merge_splits_editor.load(save_manager, &self.objective);
                         };
                     }
                 });
 
             if ui.button("Remove useless runs").clicked() {
-                save_manager.optimize_obj(&self.objective);
+                let removed = save_manager.optimize_obj(&self.objective);
+                self.push_removed(removed, TrashReason::Optimized);
+                self.reset_compare(save_manager);
             }
 
-            if let Some(best_splits) = save_manager.get_best_splits(&self.objective) {
-                ui.colored_label(Color32::GOLD, "Theoretical:");
-                ui.colored_label(
-                    Color32::GOLD,
-                    Self::sum_run_splits(
-                        save_manager
-                            .get_split_names(&self.objective)
-                            .unwrap_or(&Vec::new())
-                            .iter()
-                            .map(|name| best_splits.get(name).cloned().unwrap_or_default()),
-                    )
-                    .to_string(),
-                );
+            if render_history_buttons(&mut self.history, save_manager, ui) {
+                self.reset_compare(save_manager);
+                self.merge_splits_editor.load(save_manager, &self.objective);
+            }
+
+            if let Some(sum_of_best) = save_manager.get_sum_of_best(&self.objective) {
+                Self::render_sum_of_best(ui, sum_of_best, save_manager, &self.objective);
             }
 
             ui.checkbox(&mut self.show_split_times, "Show Split Times");
+            ui.checkbox(&mut self.show_run_info, "Show Run Info");
 
             if ui.checkbox(&mut self.compare_all, "Compare ALL").clicked() {
                 if self.compare_all {
@@ -109,17 +403,41 @@ impl RunManagerWindow {
                     }
                 }
             }
+
+            let trash_label = format!("Show Trash ({})", save_manager.get_trash().len());
+            ui.checkbox(&mut self.show_trash, trash_label);
+
+            let comparison_label = format!("Comparison view ({})", self.get_selected().len());
+            ui.checkbox(&mut self.show_comparison, comparison_label);
+            ui.checkbox(&mut self.show_split_stats, "Show Split Stats");
         });
 
         ui.separator();
 
+        if self.show_trash {
+            self.render_trash(ui, save_manager);
+            return;
+        }
+
+        if self.show_split_stats {
+            self.split_stats_view.render(ui, save_manager, &self.objective);
+            return;
+        }
+
+        if self.show_comparison {
+            let selected = self.get_selected();
+            self.comparison_view
+                .render(ui, save_manager, &self.objective, &selected);
+            return;
+        }
+
         ui.horizontal(|ui| {
             if ui.button("Save run to PC").clicked() {
-                save_manager.save_to_file(self.save_type, &self.objective);
+                self.save_error = save_manager.save_to_file(&self.objective).err();
             }
 
             if ui.button("Save ALL runs to PC").clicked() {
-                save_manager.save_to_files();
+                self.save_error = save_manager.save_to_files().err();
             }
 
             if ui.button("Load runs for this objective").clicked() {
@@ -141,31 +459,119 @@ impl RunManagerWindow {
                         .unwrap_or_default()
                 ];
             }
-        });
 
-        ui.horizontal(|ui| {
-            ui.add_space(5.0);
-            ui.monospace("Merge splits: ");
-            if ui
-                .add(
-                    egui::TextEdit::singleline(&mut self.merge_splits_string)
-                        .desired_width(512.0)
-                        .background_color(Color32::from_rgb(32, 32, 32))
-                        .text_color(Color32::WHITE),
-                )
-                .changed()
-            {
-                save_manager.set_merge_splits(&self.objective, &self.merge_splits_string);
-            };
+            if save_manager.get_save_type() == SaveType::Sqlite {
+                if ui.button("Import save files").clicked() {
+                    let result = save_manager.import_save_files();
+                    save_manager.load_all_runs();
+                    self.set_save_result(result.map(|count| format!("Imported {count} runs")));
+                }
+
+                if ui.button("Export to save files").clicked() {
+                    let result = save_manager.export_save_files(SaveType::Binary);
+                    self.set_save_result(result.map(|count| format!("Exported {count} runs")));
+                }
+            }
+
+            if ui.button("Export to LiveSplit").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title("Export to LiveSplit")
+                    .set_file_name(format!("{}.lss", self.objective))
+                    .save_file()
+                {
+                    let result = LiveSplit::export_lss(save_manager, &self.objective)
+                        .and_then(|data| fs::write(path, data).map_err(|e| e.to_string()));
+                    self.set_save_result(result.map(|_| "Exported LiveSplit file".to_owned()));
+                }
+            }
+
+            if ui.button("Import from LiveSplit").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title("Import from LiveSplit")
+                    .add_filter("LiveSplit", &["lss"])
+                    .pick_file()
+                {
+                    let result = fs::read_to_string(path)
+                        .map_err(|e| e.to_string())
+                        .and_then(|data| LiveSplit::import_lss(&data, &self.objective));
+
+                    self.set_save_result(result.map(|runs| {
+                        let count = runs.len();
+                        save_manager.save_multiple(runs.into_iter().map(RunEnum::Level).collect());
+                        format!("Imported {count} runs")
+                    }));
+                    self.compare_first = None;
+                    self.compare_second = vec![
+                        false; 
+                        save_manager.get_runs(&self.objective)
+                            .map(|v| v.len())
+                            .unwrap_or_default()
+                    ];
+                }
+            }
+
+            let backups = save_manager.get_backups(&self.objective);
+            if !backups.is_empty() {
+                let mut restored = None;
+                egui::ComboBox::from_label("Restore backup")
+                    .selected_text("")
+                    .show_ui(ui, |ui| {
+                        for (generation, _) in &backups {
+                            if ui.selectable_label(false, format!("Backup {generation}")).clicked() {
+                                restored = Some(*generation);
+                            }
+                        }
+                    });
+
+                if let Some(generation) = restored {
+                    self.save_error = save_manager.restore_backup(&self.objective, generation).err();
+                    self.compare_first = None;
+                    self.compare_second = vec![
+                        false; 
+                        save_manager.get_runs(&self.objective)
+                            .map(|v| v.len())
+                            .unwrap_or_default()
+                    ];
+                }
+            }
         });
 
+        if let Some(error) = &self.save_error {
+            ui.colored_label(Color32::RED, error);
+        }
+        if let Some(info) = &self.save_info {
+            ui.colored_label(Color32::GREEN, info);
+        }
+
+        egui::CollapsingHeader::new("Merge splits")
+            .id_salt("merge_splits")
+            .show(ui, |ui| {
+                if let Some(edit) = self.merge_splits_editor.render(ui, save_manager, &self.objective) {
+                    self.history.push(edit);
+                }
+            });
+
         ui.separator();
 
-        if let Some(runs) = save_manager.get_runs_mut(&self.objective) {
-            // handles all sorters
-            render_buttons(runs, ui);
+        if let Some(editor) = &mut self.notes_editor
+            && Self::render_notes_editor(ui, editor, &self.objective, save_manager)
+        {
+            self.notes_editor = None;
         }
 
+        // handles all sorters, the runs are only changed once a sort was picked
+        if let Some(sort) = save_manager
+            .get_runs(&self.objective)
+            .and_then(|runs| render_buttons(runs, ui))
+        {
+            save_manager.edit_runs(&self.objective, |runs| sort.apply(runs));
+        }
+
+        ui.horizontal(|ui| {
+            ui.monospace("Search notes and tags: ");
+            ui.add(egui::TextEdit::singleline(&mut self.search).desired_width(256.0));
+        });
+
         let binding = Vec::new();
         let split_names = save_manager
             .get_split_names(&self.objective)
@@ -189,6 +595,7 @@ impl RunManagerWindow {
             }
 
             for (id, name) in split_names.iter().skip(self.bottom_range).enumerate() {
+                let name = save_manager.get_split_display_name(&self.objective, name);
                 ui.label(format!("{: ^12}", name));
 
                 min_size[id] = min_size[id].max(name.len());
@@ -219,13 +626,23 @@ impl RunManagerWindow {
 
         let mut has_deleted = false;
         let mut for_deletion = Vec::new();
+        let mut edit_notes = None;
+
+        let visible: Vec<usize> = (0..timed_runs.len())
+            .filter(|&id| {
+                self.search.trim().is_empty()
+                    || timed_runs[id]
+                        .get_notes()
+                        .is_some_and(|v| v.matches(&self.search))
+            })
+            .collect();
 
         egui::ScrollArea::vertical().show_rows(
             ui,
             ui.text_style_height(&egui::TextStyle::Body),
-            timed_runs.len(),
+            visible.len(),
             |ui, row_range| {
-                for row in row_range {
+                for row in row_range.map(|v| visible[v]) {
                     let timed_run = &timed_runs[row];
 
                     let result = timed_run.show(
@@ -234,6 +651,7 @@ impl RunManagerWindow {
                         &save_manager,
                         ui,
                         self.show_split_times,
+                        self.show_run_info,
                         match self.compare_second.get(row).cloned().unwrap_or_default() {
                             true => self.compare_first
                                 .map(|v| timed_runs.get(v))
@@ -249,6 +667,10 @@ impl RunManagerWindow {
                         has_deleted = true;
                     }
 
+                    if result.notes {
+                        edit_notes = Some(NotesEditor::new(timed_run));
+                    }
+
                     if let Some(val) = result.compare_first {
                         let copy = self.compare_first;
                         self.compare_first = match val {
@@ -268,16 +690,14 @@ impl RunManagerWindow {
             },
         );
 
-        let timed_runs = match save_manager.get_runs_mut(&self.objective) {
-            Some(run) => run,
-            None => return,
-        };
-        for it in for_deletion.iter().rev() {
-            timed_runs.remove(*it);
+        if edit_notes.is_some() {
+            self.notes_editor = edit_notes;
         }
 
         if has_deleted {
-            save_manager.calculate_best_splits(&self.objective.to_string());
+            let removed = save_manager.move_to_trash(&self.objective, for_deletion, TrashReason::Deleted);
+            self.push_removed(removed, TrashReason::Deleted);
+            self.reset_compare(save_manager);
         }
     }
 }
@@ -1,100 +1,19 @@
-use core::{run::default_dirs::{self, get_config_directory}, save_manager::SaveType};
-use std::{collections::HashMap, fs::File, io::Read, path::PathBuf};
+use core::{
+    logs_folder::{self, LogsFolderCandidate, LogsFolderSource},
+    run::default_dirs::get_config_directory,
+};
+use std::path::PathBuf;
 
 use egui::{Color32, Label, RichText, WidgetText};
-use serde::{Deserialize, Serialize};
-
-use crate::render::Render;
-
-const GENERAL_ARR: &[&str] = &[
-    "automatic_loading",
-    "automatic_saving",
-    "show_real_timer",
-    "show_game_splitter",
-    "show_run_splitter",
-    "show_run_counter",
-    "text_size",
-];
-
-const SPLITTER_ARR: &[&str] = &[
-    "window_transparency",
-    "logs_path",
-    "compare_record",
-    "compare_best_splits",
-    "show_split_name",
-    "run_splitter_length",
-    "game_splitter_length",
-    "x_position",
-    "y_position",
-    "x_size",
-];
-
-const MAPPER_ARR: &[&str] = &[
-    "show_mapper",
-    "show_objectives",
-    "show_code_guess",
-    "code_guess_line_count",
-    "code_guess_word_count",
-];
-
-const FORESIGHT_ARR: &[&str] = &[
-    "show_foresight",
-    "seed_indexer_show_overflow",
-    "seed_indexer_show_overflow_hash",
-    "seed_indexer_overflow_hash_size",
-    "seed_indexer_show_resources",
-    "seed_indexer_show_consumables",
-    "seed_indexer_show_artifacts",
-    "seed_indexer_show_gather_small_items",
-    "seed_indexer_show_fog_turbine",
-    "seed_indexer_show_cell",
-    "seed_indexer_show_colored_key",
-    "seed_indexer_show_bulkhead_key",
-    "seed_indexer_show_terminal_uplink",
-    "seed_indexer_show_retrieve_big_items",
-    "seed_indexer_show_special_terminal_command",
-    "seed_indexer_show_hsu",
-    "seed_indexer_show_power_cell_distribution",
-    "seed_indexer_length",
-];
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-enum FieldValue {
-    Boolean(bool),
-    Integer(i32, String), // second value: string is input field, not actually given out
-    Float(f32, String),   // second value: string is input field, not actually given out
-    String(String),       // string here acts as both input field and value
-    Path(PathBuf, String), // it is required to have a string as input as pathbuf cant be edited directly
-}
 
-#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[repr(u8)]
-enum LoggerFontEnum {
-    #[default] JetBrainsMono,
-    ShareTechMono,
-    Ubuntu
-}
-
-static LOGGER_FONT_ENUM_ITER: &'static [LoggerFontEnum] = &[
-    LoggerFontEnum::JetBrainsMono,
-    LoggerFontEnum::ShareTechMono,
-    LoggerFontEnum::Ubuntu,
-];
-
-static LOGGER_SAVE_TYPE_ENUM_ITER: &'static [SaveType] = &[
-    SaveType::Binary,
-    SaveType::Json,
-];
-
-impl ToString for LoggerFontEnum {
-    fn to_string(&self) -> String {
-        match self {
-            LoggerFontEnum::JetBrainsMono => "jetbrains_mono".to_owned(),
-            LoggerFontEnum::ShareTechMono => "share_tech_mono".to_owned(),
-            LoggerFontEnum::Ubuntu => "ubuntu".to_owned(),
-        }
-    }
-}
+use crate::{
+    render::Render,
+    settings::{
+        LOGGER_FONT_ENUM_ITER, LOGGER_SAVE_TYPE_ENUM_ITER, LoggerFontEnum, Settings,
+        profiles,
+        schema::{self, SectionKind, SettingField, SettingValue},
+    },
+};
 
 impl Into<WidgetText> for LoggerFontEnum {
     fn into(self) -> WidgetText {
@@ -102,538 +21,293 @@ impl Into<WidgetText> for LoggerFontEnum {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-struct Field {
-    description: String,
-    value: FieldValue,
+fn text_edit(text: &mut String, width: f32) -> egui::TextEdit<'_> {
+    egui::TextEdit::singleline(text)
+        .desired_width(width)
+        .background_color(Color32::from_rgb(32, 32, 32))
+        .text_color(Color32::WHITE)
 }
 
-impl Field {
-    pub fn new(description: String, value: FieldValue) -> Self {
-        Self { description, value }
-    }
-}
+impl Render for SettingField<'_> {
+    type Response = bool;
 
-impl Into<Option<bool>> for &Field {
-    fn into(self) -> Option<bool> {
-        if let FieldValue::Boolean(b) = self.value {
-            return Some(b);
-        }
+    /// returns true if the value was changed.
+    fn render(&mut self, ui: &mut egui::Ui) -> Self::Response {
+        let description = self.description;
 
-        None
-    }
-}
+        ui.horizontal(|ui| {
+            ui.add_space(5.0);
 
-impl Into<Option<i32>> for &Field {
-    fn into(self) -> Option<i32> {
-        if let FieldValue::Integer(i, _) = self.value {
-            return Some(i);
-        }
+            match &mut self.value {
+                SettingValue::Bool(b) => ui.checkbox(b, description).changed(),
+                SettingValue::Integer(i, range) => {
+                    ui.monospace(description);
+                    ui.add(egui::DragValue::new(*i).range(range.clone()))
+                        .changed()
+                }
+                SettingValue::Float(f, range) => {
+                    ui.monospace(description);
+                    ui.add(egui::DragValue::new(*f).range(range.clone()).speed(0.05))
+                        .changed()
+                }
+                SettingValue::Text(s) => {
+                    ui.monospace(description);
+                    ui.add(text_edit(s, 512.0)).changed()
+                }
+                SettingValue::Path(path_buf) => {
+                    ui.monospace(description);
+                    let mut s = path_buf.to_string_lossy().into_owned();
+                    let changed = ui.add(text_edit(&mut s, 512.0)).changed();
+                    if changed {
+                        **path_buf = s.into();
+                    }
 
-        None
-    }
-}
+                    changed
+                }
+                SettingValue::Font(font) => {
+                    let mut changed = false;
+                    egui::ComboBox::from_label(description)
+                        .selected_text(font.to_string())
+                        .height(500.0)
+                        .show_ui(ui, |ui| {
+                            for key in LOGGER_FONT_ENUM_ITER {
+                                changed |= ui
+                                    .selectable_value(*font, *key, key.to_string())
+                                    .clicked();
+                            }
+                        });
 
-impl Into<Option<f32>> for &Field {
-    fn into(self) -> Option<f32> {
-        if let FieldValue::Float(f, _) = self.value {
-            return Some(f);
-        }
+                    changed
+                }
+                SettingValue::SaveType(save_type) => {
+                    let mut changed = false;
+                    egui::ComboBox::from_label(description)
+                        .selected_text(save_type.to_string())
+                        .height(500.0)
+                        .show_ui(ui, |ui| {
+                            for key in LOGGER_SAVE_TYPE_ENUM_ITER {
+                                changed |= ui
+                                    .selectable_value(*save_type, *key, key.to_string())
+                                    .clicked();
+                            }
+                        });
 
-        None
+                    changed
+                }
+                SettingValue::GameObjective(objective) => {
+                    ui.monospace(description);
+                    objective.render(ui)
+                }
+            }
+        })
+        .inner
     }
 }
 
-impl<'a> Into<Option<&'a String>> for &'a Field {
-    fn into(self) -> Option<&'a String> {
-        if let FieldValue::String(s) = &self.value {
-            return Some(s);
-        }
+pub struct SettingsWindow {
+    settings: Settings,
 
-        None
-    }
+    profiles: Vec<String>,
+    new_profile_name: String,
+    profile_error: Option<String>,
+
+    logs_candidates: Option<Vec<LogsFolderCandidate>>,
 }
 
-impl<'a> Into<Option<&'a PathBuf>> for &'a Field {
-    fn into(self) -> Option<&'a PathBuf> {
-        if let FieldValue::Path(p, _) = &self.value {
-            return Some(p);
+impl Default for SettingsWindow {
+    fn default() -> Self {
+        Self {
+            settings: Settings::load(),
+            profiles: profiles::list_profiles(),
+            new_profile_name: String::new(),
+            profile_error: None,
+            logs_candidates: None,
         }
-
-        None
     }
 }
 
-impl Render for Field {
-    type Response = ();
+impl SettingsWindow {
+    pub fn get_settings(&self) -> &Settings {
+        &self.settings
+    }
 
-    fn render(&mut self, ui: &mut egui::Ui) -> Self::Response {
-        match &mut self.value {
-            FieldValue::Boolean(b) => {
-                ui.horizontal(|ui| {
-                    ui.add_space(5.0);
-                    ui.checkbox(b, &self.description);
-                });
-            }
-            FieldValue::Integer(i, s) => {
-                ui.horizontal(|ui| {
-                    ui.add_space(5.0);
-                    ui.monospace(&self.description);
-                    if ui
-                        .add(
-                            egui::TextEdit::singleline(s)
-                                .desired_width(50.0)
-                                .background_color(Color32::from_rgb(32, 32, 32))
-                                .text_color(Color32::WHITE),
-                        )
-                        .changed()
-                    {
-                        if let Ok(x) = s.parse::<i32>() {
-                            *i = x;
-                        }
-                    }
-                });
-            }
-            FieldValue::Float(f, s) => {
-                ui.horizontal(|ui| {
-                    ui.add_space(5.0);
-                    ui.monospace(&self.description);
-                    if ui
-                        .add(
-                            egui::TextEdit::singleline(s)
-                                .desired_width(50.0)
-                                .background_color(Color32::from_rgb(32, 32, 32))
-                                .text_color(Color32::WHITE),
-                        )
-                        .changed()
-                    {
-                        if let Ok(x) = s.parse::<f32>() {
-                            *f = x;
-                        }
-                    }
-                });
-            }
-            FieldValue::String(s) => {
-                ui.horizontal(|ui| {
-                    ui.add_space(5.0);
-                    ui.monospace(&self.description);
-                    ui.add(
-                        egui::TextEdit::singleline(s)
-                            .desired_width(512.0)
-                            .background_color(Color32::from_rgb(32, 32, 32))
-                            .text_color(Color32::WHITE),
-                    );
-                });
-            }
-            FieldValue::Path(path_buf, s) => {
-                ui.horizontal(|ui| {
-                    ui.add_space(5.0);
-                    ui.monospace(&self.description);
-                    if ui
-                        .add(
-                            egui::TextEdit::singleline(s)
-                                .desired_width(512.0)
-                                .background_color(Color32::from_rgb(32, 32, 32))
-                                .text_color(Color32::WHITE),
-                        )
-                        .changed()
-                    {
-                        *path_buf = (&s).into();
-                    }
-                });
-            }
-        };
+    pub fn get_profiles(&self) -> &[String] {
+        &self.profiles
     }
-}
 
-#[derive(Serialize, Deserialize)]
-pub struct SettingsWindow {
-    setting_hash: HashMap<String, Field>,
+    pub fn get_active_profile(&self) -> Option<&str> {
+        self.settings.profile.as_deref()
+    }
 
-    font_used: LoggerFontEnum,
-    save_type: SaveType,
-}
+    /// replaces every setting with the ones of the profile.
+    ///
+    /// returns true if the profile was loaded.
+    pub fn switch_profile(&mut self, name: &str) -> bool {
+        match profiles::load_profile(name) {
+            Ok(settings) => {
+                self.settings = settings;
+                self.profile_error = None;
+                if let Err(e) = self.settings.save() {
+                    eprintln!("Failed to save settings: {e}");
+                }
 
-impl Default for SettingsWindow {
-    fn default() -> Self {
-        if let Some(s) = Self::load() {
-            return s;
+                true
+            }
+            Err(e) => {
+                eprintln!("Failed to load profile {name}: {e}");
+                self.profile_error = Some(format!("Failed to load profile {name}: {e}"));
+                self.profiles = profiles::list_profiles();
+
+                false
+            }
         }
+    }
 
-        let s = Self {
-            setting_hash: HashMap::new(),
+    /// saves the settings and the active profile along with them.
+    pub fn save_settings(&mut self) -> Option<()> {
+        self.settings.validate();
 
-            font_used: Default::default(),
-            save_type: Default::default(),
-        };
+        if let Some(profile) = &self.settings.profile
+            && let Err(e) = profiles::save_profile(profile, &self.settings)
+        {
+            eprintln!("Failed to save profile {profile}: {e}");
+        }
 
-        s.add_all()
+        self.settings
+            .save()
+            .inspect_err(|e| eprintln!("Failed to save settings: {e}"))
+            .ok()
     }
-}
 
-impl SettingsWindow {
-    pub fn get_save_type(&self) -> SaveType {
-        self.save_type
-    }
-    
-    fn add_all(mut self) -> Self {
-        let log_path = Self::logs_path().unwrap_or_default();
-
-        self.add_to_general(
-            "automatic_loading".into(),
-            Field::new(
-                "Automatic Loading of Runs".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_general(
-            "automatic_saving".into(),
-            Field::new(
-                "Automatic Saving of Runs".into(),
-                FieldValue::Boolean(false),
-            ),
-        );
-        self.add_to_general(
-            "show_real_timer".into(), 
-            Field::new("Show real timer   Warning: this timer may not be accurate. Use the in game timer for that.".into(), FieldValue::Boolean(false))
-        );
-        self.add_to_general(
-            "show_game_splitter".into(),
-            Field::new("Show game splitter".into(), FieldValue::Boolean(false)),
-        );
-        self.add_to_general(
-            "show_run_splitter".into(),
-            Field::new("Show run splitter".into(), FieldValue::Boolean(true)),
-        );
-        self.add_to_general(
-            "show_run_counter".into(),
-            Field::new("Show run counter".into(), FieldValue::Boolean(true)),
-        );
-        self.add_to_general(
-            "text_size".into(), 
-            Field::new("Text size".into(), FieldValue::Float(12f32, "12.0".to_owned()))
-        );
-
-        self.add_to_splitter(
-            "window_transparency".into(),
-            Field::new(
-                "LiveWindow Transparency".into(),
-                FieldValue::Float(0.6f32, "0.6".to_owned()),
-            ),
-        );
-        self.add_to_splitter(
-            "logs_path".into(),
-            Field::new(
-                "Path to logs folder: ".into(),
-                FieldValue::Path(
-                    log_path.clone(),
-                    log_path.into_os_string().into_string().unwrap_or_default(), // unfortunately there is no nicer way of doing this
-                                                                                 // as it is required
-                ),
-            ),
-        );
-        self.add_to_splitter(
-            "compare_record".into(),
-            Field::new("Compare to saved record".into(), FieldValue::Boolean(true)),
-        );
-        self.add_to_splitter(
-            "compare_best_splits".into(),
-            Field::new("Compare to best splits".into(), FieldValue::Boolean(true)),
-        );
-        self.add_to_splitter(
-            "show_split_name".into(), 
-            Field::new("Show the split name".into(), FieldValue::Boolean(false)),
-        );
-        self.add_to_splitter(
-            "run_splitter_length".into(),
-            Field::new(
-                "Run Splitter max length".into(),
-                FieldValue::Integer(100, "100".into()),
-            ),
-        );
-        self.add_to_splitter(
-            "game_splitter_length".into(),
-            Field::new(
-                "Game Splitter max length".into(),
-                FieldValue::Integer(5, "5".into()),
-            ),
-        );
-        self.add_to_splitter(
-            "x_position".into(),
-            Field::new("X position".into(), FieldValue::Float(0f32, "0".into())),
-        );
-        self.add_to_splitter(
-            "y_position".into(),
-            Field::new("Y position".into(), FieldValue::Float(250f32, "250".into())),
-        );
-        self.add_to_splitter(
-            "x_size".into(),
-            Field::new("X size    ".into(), FieldValue::Float(250f32, "250".into())),
-        );
-
-        self.add_to_mapper(
-            "show_mapper".into(),
-            Field::new(
-                "Show mapper in live splitter".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_mapper(
-            "show_objectives".into(),
-            Field::new(
-                "Show objectives items in live splitter".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_mapper(
-            "show_code_guess".into(),
-            Field::new("Show code guess".into(), FieldValue::Boolean(false)),
-        );
-        self.add_to_mapper(
-            "code_guess_line_count".into(),
-            Field::new(
-                "Code guess number of lines: ".into(),
-                FieldValue::Integer(3, "3".into()),
-            ),
-        );
-        self.add_to_mapper(
-            "code_guess_word_count".into(),
-            Field::new(
-                "Code guess number of words per line: ".into(),
-                FieldValue::Integer(7, "7".into()),
-            ),
-        );
-
-        self.add_to_foresight(
-            "show_foresight".into(),
-            Field::new(
-                "Show Foresight in live splitter".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_overflow".into(), 
-            Field::new(
-                "Show the Marker Set in Foresight".into(), 
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_overflow_hash".into(), 
-            Field::new(
-                "Show the Marker Set Hash in Foresight. This is for specific zone spawns".into(),
-                FieldValue::Boolean(false),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_overflow_hash_size".into(), 
-            Field::new(
-                "How many characters to show in the hash size".into(), 
-                FieldValue::Integer(8, "8".into()),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_resources".into(),
-            Field::new(
-                "Show resources in foresight".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_consumables".into(),
-            Field::new(
-                "Show consumables in foresight".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_artifacts".into(),
-            Field::new(
-                "Show artifacts in foresight".into(),
-                FieldValue::Boolean(false),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_gather_small_items".into(),
-            Field::new(
-                "Show GatherSmallItem in foresight".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_fog_turbine".into(),
-            Field::new(
-                "Show FogTurbine in foresight".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_cell".into(),
-            Field::new("Show Cell in foresight".into(), FieldValue::Boolean(true)),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_colored_key".into(),
-            Field::new(
-                "Show ColoredKey in foresight".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_bulkhead_key".into(),
-            Field::new(
-                "Show BulkKey in foresight".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_terminal_uplink".into(),
-            Field::new(
-                "Show TerminalUplink in foresight".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_retrieve_big_items".into(),
-            Field::new(
-                "Show RetrieveBigItems in foresight".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_special_terminal_command".into(),
-            Field::new(
-                "Show SpecialTerminalCommand in foresight".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_hsu".into(),
-            Field::new(
-                "Show HSU_FindTakeSample in foresight".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_show_power_cell_distribution".into(),
-            Field::new(
-                "Show PowerCellDistribution in foresight".into(),
-                FieldValue::Boolean(true),
-            ),
-        );
-        self.add_to_foresight(
-            "seed_indexer_length".into(),
-            Field::new(
-                "Size of Foresight".into(),
-                FieldValue::Integer(10, "10".into()),
-            ),
-        );
-
-        self
+    fn set_profile_result(&mut self, result: Result<(), String>) {
+        self.profile_error = result.err();
+        self.profiles = profiles::list_profiles();
     }
 
-    fn add_to_general(&mut self, id: String, field: Field) {
-        if self.setting_hash.contains_key(&id) {
-            return;
-        }
+    fn render_profiles(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.monospace(format!(
+                "Profile: {}",
+                self.get_active_profile().unwrap_or("none"),
+            ));
+
+            ui.add(text_edit(&mut self.new_profile_name, 150.0));
+
+            if ui.button("Save as profile").clicked() {
+                let name = self.new_profile_name.trim().to_owned();
+                let result = profiles::save_profile(&name, &self.settings);
+                if result.is_ok() {
+                    self.settings.profile = Some(name);
+                    self.new_profile_name.clear();
+                }
 
-        self.setting_hash.insert(id, field);
-    }
+                self.set_profile_result(result);
+            }
 
-    fn add_to_splitter(&mut self, id: String, field: Field) {
-        if self.setting_hash.contains_key(&id) {
-            return;
-        }
+            if let Some(profile) = self.settings.profile.clone() {
+                if ui.button("Delete profile").clicked() {
+                    let result = profiles::delete_profile(&profile);
+                    if result.is_ok() {
+                        self.settings.profile = None;
+                    }
 
-        self.setting_hash.insert(id, field);
-    }
+                    self.set_profile_result(result);
+                }
 
-    fn add_to_mapper(&mut self, id: String, field: Field) {
-        if self.setting_hash.contains_key(&id) {
-            return;
-        }
+                if ui.button("Export profile").clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .set_title("Export profile")
+                        .set_file_name(format!("{profile}.yaml"))
+                        .save_file()
+                {
+                    let result = profiles::export_profile(&self.settings, &path);
+                    self.set_profile_result(result);
+                }
+            }
 
-        self.setting_hash.insert(id, field);
-    }
+            if ui.button("Import profile").clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .set_title("Import profile")
+                    .add_filter("Profile", &["yaml"])
+                    .pick_file()
+            {
+                let result = profiles::import_profile(&path).map(|_| ());
+                self.set_profile_result(result);
+            }
+        });
 
-    fn add_to_foresight(&mut self, id: String, field: Field) {
-        if self.setting_hash.contains_key(&id) {
-            return;
+        if let Some(error) = &self.profile_error {
+            ui.colored_label(Color32::RED, error);
         }
 
-        self.setting_hash.insert(id, field);
+        ui.separator();
     }
 
-    fn logs_path() -> Option<PathBuf> {
-        if let Some(dirs) = directories::UserDirs::new() {
-            return Some(
-                dirs.home_dir()
-                    .to_path_buf()
-                    .join("AppData\\LocalLow\\10 Chambers Collective\\GTFO"),
-            );
+    /// lists the detected logs folders, returns the one picked.
+    fn render_logs_detection(
+        ui: &mut egui::Ui,
+        candidates: &mut Option<Vec<LogsFolderCandidate>>,
+    ) -> Option<PathBuf> {
+        if ui.button("Detect logs folder").clicked() {
+            *candidates = Some(logs_folder::find_logs_folders());
         }
 
-        None
-    }
-
-    pub fn load() -> Option<Self> {
-        let path = default_dirs::get_config_directory()?.join("app.properties");
-        let mut file = File::open(path).ok()?;
-        let mut file_str = String::new();
-        file.read_to_string(&mut file_str).ok()?;
-
-        let p = serde_yaml::from_str(&file_str)
-            .ok()
-            .map(|v: SettingsWindow| v.add_all());
+        let list = candidates.as_ref()?;
+        if list.is_empty() {
+            ui.colored_label(Color32::RED, "No GTFO logs folder found, set the path by hand.");
+            return None;
+        }
 
-        p
-    }
+        let mut picked = None;
+        for candidate in list {
+            ui.horizontal(|ui| {
+                ui.add_space(5.0);
+                if ui.button("Use").clicked() {
+                    picked = Some(candidate.path.clone());
+                }
 
-    pub fn save_settings(&self) -> Option<()> {
-        let path = default_dirs::get_config_directory()?;
-        if !path.exists() {
-            let _ = std::fs::create_dir_all(&path);
+                let source = match candidate.source {
+                    LogsFolderSource::Native => "Windows",
+                    LogsFolderSource::Proton => "Proton",
+                    LogsFolderSource::Custom => "Custom",
+                };
+                let text = format!(
+                    "{source} ({} logs): {}",
+                    candidate.log_count,
+                    candidate.path.display(),
+                );
+                match candidate.is_valid() {
+                    true => ui.label(text),
+                    false => ui.colored_label(Color32::GRAY, text),
+                };
+            });
         }
-        let path = path.join("app.properties");
-        
-        let text = serde_yaml::to_string(self).ok()?;
-        let _ = std::fs::write(path, text).ok()?;
 
-        Some(())
-    }
+        if picked.is_some() {
+            *candidates = None;
+        }
 
-    #[allow(private_bounds)]
-    pub fn get<V>(&self, field_name: &str) -> Option<V>
-    where
-        for<'a> &'a Field: Into<Option<V>>,
-    {
-        self.setting_hash
-            .get(field_name)
-            .map(|v| v.into())
-            .flatten()
+        picked
     }
 
-    #[allow(private_bounds)]
-    pub fn get_def<V>(&self, field_name: &str) -> V
-    where
-        for<'a> &'a Field: Into<Option<V>>,
-        V: Default,
-    {
-        self.get(field_name).unwrap_or_else(|| {
-            eprintln!("Failed to find: {field_name}");
-            V::default()
-        })
-    }
+    fn open_folder_buttons(ui: &mut egui::Ui, folder: &str, view_name: &str, examples: &str) {
+        ui.horizontal(|ui| {
+            if ui.button(format!("Open {view_name} folder")).clicked()
+                && let Some(path) = get_config_directory().map(|v| v.join(folder))
+            {
+                if !path.exists() {
+                    let _ = std::fs::create_dir_all(&path);
+                }
 
-    #[allow(private_bounds)]
-    pub fn get_path(&self, field_name: &str) -> Option<&PathBuf> {
-        self.setting_hash
-            .get(field_name)
-            .map(|v| v.into())
-            .flatten()
-    }
+                let _ = opener::open(path);
+            }
 
-    pub fn get_font_name(&self) -> String {
-        self.font_used.to_string()
+            if ui.button(format!("Open examples for {view_name}")).clicked() {
+                let _ = opener::open_browser(format!(
+                    "https://github.com/Tgb03/Logger/tree/master/examples/{examples}",
+                ));
+            }
+        });
     }
 }
 
@@ -641,135 +315,60 @@ impl Render for SettingsWindow {
     type Response = bool;
 
     fn render(&mut self, ui: &mut egui::Ui) -> Self::Response {
-        let mut changed_font_data = false;
+        let font = (self.settings.general.font, self.settings.general.text_size);
+        let mut picked_logs_folder = None;
+
+        self.render_profiles(ui);
 
         egui::ScrollArea::vertical()
             .max_height(ui.available_height() - 60.0)
             .show(ui, |ui| {
-                ui.add(Label::new(
-                    Into::<RichText>::into("General settings: ").size(14.0),
-                ));
-
-                ui.add_space(10.0);
-
-                for id in GENERAL_ARR {
-                    self.setting_hash.get_mut(*id).map(|v| v.render(ui));
-                }
-
-                egui::ComboBox::from_label("Select font")
-                .selected_text(self.font_used.to_string())
-                .height(500.0)
-                .show_ui(ui, |ui| {
-                    for key in LOGGER_FONT_ENUM_ITER {
-                        if ui
-                            .selectable_value(
-                                &mut self.font_used, 
-                                key.clone(), 
-                                key.to_string()
-                            )
-                            .clicked()
-                        {
-                            changed_font_data = true;
-                        };
+                for (id, section) in schema::sections(&mut self.settings).into_iter().enumerate() {
+                    if id > 0 {
+                        ui.separator();
                     }
-                });
-                
-                egui::ComboBox::from_label("Select SaveType")
-                    .selected_text(self.save_type.to_string())
-                    .height(500.0)
-                    .show_ui(ui, |ui| {
-                        for key in LOGGER_SAVE_TYPE_ENUM_ITER {
-                            ui.selectable_value(
-                                &mut self.save_type, 
-                                key.clone(),
-                                key.to_string(),
-                            );
-                        }
-                    });
-
-                ui.separator();
 
-                ui.add(Label::new(
-                    Into::<RichText>::into("LiveSplitter settings: ").size(14.0),
-                ));
-
-                ui.add_space(10.0);
-
-                for id in SPLITTER_ARR {
-                    self.setting_hash.get_mut(*id).map(|v| v.render(ui));
-                }
+                    ui.add(Label::new(
+                        Into::<RichText>::into(section.title).size(14.0),
+                    ));
 
-                ui.separator();
-
-                ui.add(Label::new(
-                    Into::<RichText>::into("Mapper settings: ").size(14.0),
-                ));
-
-                ui.add_space(10.0);
-
-                for id in MAPPER_ARR {
-                    self.setting_hash.get_mut(*id).map(|v| v.render(ui));
-                }
-
-                ui.horizontal(|ui| {
-                    if ui.button("Open LevelView folder").clicked() {
-                        if let Some(mut path) = get_config_directory() {
-                            path = path.join("levels");
-
-                            if !path.exists() {
-                                let _ = std::fs::create_dir_all(&path);
-                            }
+                    ui.add_space(10.0);
 
-                            let _ = opener::open(path);
+                    for mut field in section.fields {
+                        if field.render(ui) {
+                            field.value.clamp();
                         }
                     }
 
-                    if ui.button("Open examples for LevelView").clicked() {
-                        let _ = opener::open_browser(
-                            "https://github.com/Tgb03/Logger/tree/master/examples/level_view",
-                        );
-                    }
-                });
-
-                ui.separator();
-
-                ui.add(Label::new(
-                    Into::<RichText>::into("Foresight settings: ").size(14.0),
-                ));
-
-                ui.add_space(10.0);
-
-                for id in FORESIGHT_ARR {
-                    self.setting_hash.get_mut(*id).map(|v| v.render(ui));
-                }
-
-                ui.horizontal(|ui| {
-                    if ui.button("Open ForesightView folder").clicked() {
-                        if let Some(mut path) = get_config_directory() {
-                            path = path.join("foresight");
-
-                            if !path.exists() {
-                                let _ = std::fs::create_dir_all(&path);
-                            }
-
-                            let _ = opener::open(path);
+                    match section.kind {
+                        SectionKind::Mapper => {
+                            Self::open_folder_buttons(ui, "levels", "LevelView", "level_view")
                         }
+                        SectionKind::Foresight => Self::open_folder_buttons(
+                            ui,
+                            "foresight",
+                            "ForesightView",
+                            "foresight_view",
+                        ),
+                        SectionKind::Splitter => {
+                            picked_logs_folder =
+                                Self::render_logs_detection(ui, &mut self.logs_candidates)
+                        }
+                        SectionKind::General => {}
                     }
-
-                    if ui.button("Open examples for ForesightView").clicked() {
-                        let _ = opener::open_browser(
-                            "https://github.com/Tgb03/Logger/tree/master/examples/foresight_view",
-                        );
-                    }
-                });
+                }
             });
 
+        if let Some(path) = picked_logs_folder {
+            self.settings.splitter.logs_path = path;
+        }
+
         ui.separator();
         ui.add_space(5.0);
 
         ui.label(format!("App version: {}", env!("CARGO_PKG_VERSION")));
         ui.label(format!("Made by Tgb03"));
 
-        changed_font_data
+        font != (self.settings.general.font, self.settings.general.text_size)
     }
 }
@@ -0,0 +1,102 @@
+use core::{
+    comparison::format_millis,
+    save_manager::SaveManager,
+    split_stats::{ObjectiveStats, PERCENTILES},
+};
+use std::fs::File;
+
+use egui::{Color32, Ui};
+
+/// consistency and time save of every merged split of an objective.
+#[derive(Default)]
+pub struct SplitStatsView {
+    export_result: Option<Result<String, String>>,
+}
+
+impl SplitStatsView {
+    pub fn render(&mut self, ui: &mut Ui, save_manager: &SaveManager, objective: &String) {
+        let Some(stats) = ObjectiveStats::from_save_manager(save_manager, objective) else {
+            ui.label("Select an objective with loaded runs.");
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(format!("{} runs", stats.runs));
+            ui.colored_label(
+                Color32::GOLD,
+                format!("Usual time save: {}", format_millis(stats.get_total_time_save())),
+            );
+
+            if ui.button("Export stats").clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .set_title("Export split stats")
+                    .add_filter("CSV", &["csv"])
+                    .add_filter("JSON", &["json"])
+                    .set_file_name(format!("{objective}_stats.csv"))
+                    .save_file()
+            {
+                let result = File::create(&path).map_err(|e| e.to_string()).and_then(|file| {
+                    match path.extension().is_some_and(|v| v == "json") {
+                        true => stats.export_json(file),
+                        false => stats.export_csv(file),
+                    }
+                });
+                self.export_result = Some(result.map(|_| format!("Exported to {}", path.display())));
+            }
+
+            match &self.export_result {
+                Some(Ok(info)) => {
+                    ui.colored_label(Color32::GREEN, info);
+                }
+                Some(Err(e)) => {
+                    ui.colored_label(Color32::RED, e);
+                }
+                None => {}
+            }
+        });
+
+        ui.separator();
+
+        egui::ScrollArea::both().id_salt("split_stats").show(ui, |ui| {
+            egui::Grid::new("split_stats_table").striped(true).show(ui, |ui| {
+                for header in ["Split", "Done", "Best", "Mean", "Median", "Std dev"] {
+                    ui.monospace(header);
+                }
+                for percent in PERCENTILES {
+                    ui.monospace(format!("P{percent}"));
+                }
+                ui.monospace("Time save");
+                ui.end_row();
+
+                for split in &stats.splits {
+                    ui.monospace(save_manager.get_split_display_name(objective, &split.name));
+                    ui.monospace(format!(
+                        "{}/{} ({:.0}%)",
+                        split.completions,
+                        split.attempts,
+                        split.get_completion_rate()
+                    ));
+
+                    let Some(times) = &split.times else {
+                        ui.end_row();
+                        continue;
+                    };
+
+                    ui.colored_label(Color32::GREEN, format_millis(times.best));
+                    ui.monospace(format_millis(times.mean));
+                    ui.monospace(format_millis(times.median));
+                    ui.monospace(format!(
+                        "{} ({:.1}%)",
+                        format_millis(times.std_dev),
+                        times.get_variation()
+                    ));
+                    for (_, time) in &times.percentiles {
+                        ui.monospace(format_millis(*time));
+                    }
+                    ui.colored_label(Color32::GOLD, format_millis(times.time_save));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
@@ -1,4 +1,4 @@
-use core::run::{timed_run::LevelRun, traits::Run};
+use core::run::{objectives::level_catalog::LevelCatalog, timed_run::LevelRun, traits::Run};
 use std::{collections::HashMap, fmt::Display};
 
 use egui::{Color32, Ui};
@@ -125,6 +125,16 @@ impl Stats {
             s.calculate_percent(time_total);
         }
 
+        // levels the catalog knows are shown in play order, the rest after them.
+        let catalog = LevelCatalog::get();
+        let level_order = |name: &String| (catalog.get_position(name).unwrap_or(usize::MAX), name.clone());
+        let display_name = |name: &String| {
+            catalog
+                .get_level(name)
+                .map(|v| v.get_display_name().to_owned())
+                .unwrap_or_else(|| name.clone())
+        };
+
         Self {
             text_total_time: format!("   Total time {}", time_total.to_string()),
             text_winrate: format!(
@@ -134,8 +144,8 @@ impl Stats {
             text_number_of_runs: format!("   Number of runs: {number_of_runs}"),
             text_split_times: splits
                 .iter()
-                .sorted_by(|(a, _), (b, _)| a.cmp(b))
-                .map(|(name, stat)| format!("   {: <8}: {}", name, stat))
+                .sorted_by_key(|(name, _)| level_order(name))
+                .map(|(name, stat)| format!("   {: <8}: {}", display_name(name), stat))
                 .collect(),
         }
     }